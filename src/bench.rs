@@ -0,0 +1,137 @@
+//! Time each step of Tidy's pipeline on an inputted list, for `tidy bench`
+use crate::list_manipulations::{
+    dedup_without_sorting, Capitalization, SortOrder, DEFAULT_CONFUSABLE_CHARS,
+    DEFAULT_GIBBERISH_THRESHOLD, DEFAULT_LEET_SUBSTITUTIONS,
+};
+use crate::logging::{peak_memory_usage, reset_peak_memory_usage};
+use crate::pipeline::PipelineStep;
+use crate::{is_requested, tidy_list_explained, TidyRequest};
+use std::time::{Duration, Instant};
+
+/// Settings for the steps whose speed depends on a number the user picks
+#[derive(Debug, Clone, Copy)]
+pub struct BenchSettings {
+    pub minimum_edit_distance: usize,
+    /// Used by both the shared-prefix and truncate-to-unique steps
+    pub shared_prefix_length: usize,
+}
+
+/// How one step of the pipeline did on the inputted list
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageBenchmark {
+    pub step: PipelineStep,
+    pub words_left: usize,
+    pub elapsed: Duration,
+    /// Peak memory use of the process while running the step, in bytes, if
+    /// known (see `logging::peak_memory_usage`)
+    pub peak_memory: Option<u64>,
+}
+
+/// Makes a request that only runs the given step, with typical settings.
+/// Returns `None` for steps that need more from the user than a number to
+/// run, like a list of words to reject, or delimiters.
+/// ```
+/// use tidy::bench::{request_for_step, BenchSettings};
+/// use tidy::pipeline::PipelineStep;
+/// let settings = BenchSettings { minimum_edit_distance: 3, shared_prefix_length: 4 };
+/// let req = request_for_step(PipelineStep::EditDistance, &settings).unwrap();
+/// assert_eq!(req.minimum_edit_distance, Some(3));
+/// assert!(request_for_step(PipelineStep::Reject, &settings).is_none());
+/// ```
+pub fn request_for_step(step: PipelineStep, settings: &BenchSettings) -> Option<TidyRequest> {
+    let mut req = TidyRequest {
+        pipeline: Some(vec![step]),
+        ..Default::default()
+    };
+    match step {
+        PipelineStep::DeleteIntegers => req.should_delete_integers = true,
+        PipelineStep::DeleteNonalphanumeric => req.should_delete_nonalphanumeric = true,
+        PipelineStep::Lowercase => req.to_lowercase = true,
+        PipelineStep::StraightenQuotes => req.should_straighten_quotes = true,
+        PipelineStep::DeleteApostrophes => req.should_delete_apostrophes = true,
+        PipelineStep::NormalizeHyphens => req.should_normalize_hyphens = true,
+        PipelineStep::JoinMultiword => req.join_multiword_separator = Some("-".to_string()),
+        PipelineStep::RemoveNonascii => req.should_remove_nonascii = true,
+        PipelineStep::RemoveNonalphanumeric => req.should_remove_nonalphanumeric = true,
+        PipelineStep::RemoveNonalphabetic => req.should_remove_nonalphabetic = true,
+        PipelineStep::RemoveNonLatinAlphabetic => req.should_remove_non_latin_alphabetic = true,
+        PipelineStep::RemoveMixedScript => req.should_remove_mixed_script = true,
+        PipelineStep::RemoveMultiword => req.should_remove_multiword = true,
+        PipelineStep::RemoveIntegers => req.should_remove_integers = true,
+        PipelineStep::RemoveConfusableChars => {
+            req.confusable_chars = Some(
+                DEFAULT_CONFUSABLE_CHARS
+                    .iter()
+                    .map(|chars| chars.to_string())
+                    .collect(),
+            )
+        }
+        PipelineStep::RemoveGibberish => {
+            req.gibberish_threshold = Some(DEFAULT_GIBBERISH_THRESHOLD)
+        }
+        PipelineStep::MinimumLength => req.minimum_length = Some(3),
+        PipelineStep::MaximumLength => req.maximum_length = Some(9),
+        PipelineStep::RemoveLeetVariants => {
+            req.leet_substitutions = Some(DEFAULT_LEET_SUBSTITUTIONS.to_vec())
+        }
+        PipelineStep::RemoveInflections => req.should_remove_inflections = true,
+        PipelineStep::SharedPrefix => {
+            req.maximum_shared_prefix_length = Some(settings.shared_prefix_length)
+        }
+        PipelineStep::TruncateToUnique => {
+            req.truncate_to_unique_length = Some(settings.shared_prefix_length)
+        }
+        PipelineStep::EditDistance => {
+            req.minimum_edit_distance = Some(settings.minimum_edit_distance)
+        }
+        PipelineStep::RemoveSuffixWords => req.should_remove_suffix_words = true,
+        PipelineStep::RemovePrefixWords => req.should_remove_prefix_words = true,
+        PipelineStep::RemoveInfixWords => req.should_remove_infix_words = true,
+        PipelineStep::RemoveCompoundCollisions => req.should_remove_compound_collisions = true,
+        PipelineStep::BestPrune => req.should_best_prune = true,
+        PipelineStep::SchlinkertPrune => req.should_schlinkert_prune = true,
+        PipelineStep::Capitalize => req.capitalization = Capitalization::All,
+        PipelineStep::FirstLetters => req.max_per_first_letter = Some(1000),
+        PipelineStep::Sort => req.sort_by = SortOrder::Alphabetical,
+        PipelineStep::Dedup => (),
+        _ => return None,
+    }
+    debug_assert!(is_requested(step, &req));
+    Some(req)
+}
+
+/// Runs each of the given steps on its own, on the de-duplicated `list`,
+/// timing it and measuring the peak memory used while it ran. Steps that
+/// `request_for_step` can't make a request for are skipped. Calls
+/// `on_stage` as each step finishes, since slow steps can take a while.
+pub fn bench_stages(
+    list: &[String],
+    steps: &[PipelineStep],
+    settings: &BenchSettings,
+    on_stage: &dyn Fn(&StageBenchmark),
+) -> Vec<StageBenchmark> {
+    let list = dedup_without_sorting(list);
+    let mut benchmarks = vec![];
+    for step in steps {
+        let mut req = match request_for_step(*step, settings) {
+            Some(req) => req,
+            None => continue,
+        };
+        req.list = list.clone();
+        reset_peak_memory_usage();
+        let started = Instant::now();
+        let words_left = match tidy_list_explained(&req) {
+            Ok((tidied_list, _report)) => tidied_list.len(),
+            Err(e) => panic!("{}", e),
+        };
+        let benchmark = StageBenchmark {
+            step: *step,
+            words_left,
+            elapsed: started.elapsed(),
+            peak_memory: peak_memory_usage(),
+        };
+        on_stage(&benchmark);
+        benchmarks.push(benchmark);
+    }
+    benchmarks
+}
@@ -0,0 +1,146 @@
+//! An on-disk cache of tidied lists, so that running Tidy again with the
+//! same inputs and list-making options, but different output options, can
+//! skip straight to printing the list.
+use crate::checksum::bytes_checksum;
+use crate::registry::{fetched_list_path, find_registry_list};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Options that don't change which words are on the list, like ones that
+/// only change how it's printed, so they're left out of cache keys.
+pub const OUTPUT_OPTIONS: [&str; 56] = [
+    "cache",
+    "checkpoint",
+    "resume",
+    "quiet",
+    "dry-run",
+    "plan",
+    "debug",
+    "verbose",
+    "log-format",
+    "color",
+    "explain",
+    "assert-length",
+    "assert-unique-prefix",
+    "assert-free-of",
+    "audit",
+    "check-decodable",
+    "find-inflections",
+    "find-compound-collisions",
+    "warn-skew",
+    "no-length-advice",
+    "suggest-to",
+    "attributes",
+    "separator",
+    "guesses-per-second",
+    "compare-to",
+    "samples",
+    "checksum",
+    "checksum-header",
+    "metadata-header",
+    "pin-words",
+    "dice",
+    "sides-as-base",
+    "cards",
+    "coins",
+    "reroll-table",
+    "dice-separator",
+    "dice-digit-separator",
+    "no-dice-digit-padding",
+    "print-columns",
+    "page-height",
+    "format",
+    "annotate",
+    "companion",
+    "companion-hint",
+    "title",
+    "output",
+    "line-ending",
+    "output-encoding",
+    "force",
+    "diff",
+    "sample-count",
+    "sample-words",
+    "sample-separator",
+    "sample-case",
+    "samples-output",
+    "sample-strength",
+];
+
+/// Makes the key of a run's cached list: a SHA-256 hash of the version of
+/// Tidy and the given options (by name, with their values), leaving out
+/// `OUTPUT_OPTIONS`. Values that are paths to files, like reject lists, or
+/// names of fetched lists (see `registry`), are hashed along with the
+/// contents of the file. So are the given input files, which should be the
+/// files that any inputted directories and glob patterns expanded to. That
+/// way, changing a file changes the key.
+/// ```
+/// use tidy::cache::cache_key;
+/// let options = |format: &str| {
+///     vec![
+///         ("lowercase".to_string(), vec!["true".to_string()]),
+///         ("format".to_string(), vec![format.to_string()]),
+///     ]
+/// };
+/// // Output options don't change the key
+/// assert_eq!(
+///     cache_key(&options("text"), &[]).unwrap(),
+///     cache_key(&options("html"), &[]).unwrap()
+/// );
+/// ```
+pub fn cache_key(options: &[(String, Vec<String>)], input_files: &[PathBuf]) -> io::Result<String> {
+    let mut bytes: Vec<u8> = env!("CARGO_PKG_VERSION").as_bytes().to_vec();
+    for (name, values) in options {
+        if OUTPUT_OPTIONS.contains(&name.as_str()) {
+            continue;
+        }
+        bytes.extend(format!("\n{}", name).as_bytes());
+        for value in values {
+            bytes.extend(format!("\0{}", value).as_bytes());
+            if let Some(path) = file_to_hash(Path::new(value)) {
+                bytes.extend(bytes_checksum(&fs::read(path)?).as_bytes());
+            }
+        }
+    }
+    for input_file in input_files {
+        bytes.extend(format!("\n{}", input_file.display()).as_bytes());
+        if let Some(path) = file_to_hash(input_file) {
+            bytes.extend(bytes_checksum(&fs::read(path)?).as_bytes());
+        }
+    }
+    Ok(bytes_checksum(&bytes))
+}
+
+/// The file whose contents a value should be hashed with, if any: the file
+/// at that path, or, if there's nothing there, the fetched list by that name
+fn file_to_hash(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        Some(path.to_path_buf())
+    } else if path.exists() {
+        None
+    } else {
+        find_registry_list(&path.to_string_lossy())
+            .map(|list| fetched_list_path(list.name))
+            .filter(|fetched| fetched.is_file())
+    }
+}
+
+/// Where the list with the given key is cached
+fn cached_list_path(cache_dir: &Path, key: &str) -> PathBuf {
+    cache_dir.join(format!("{}.txt", key))
+}
+
+/// Reads the list cached with the given key, if there is one
+pub fn read_cached_list(cache_dir: &Path, key: &str) -> Option<Vec<String>> {
+    let contents = fs::read_to_string(cached_list_path(cache_dir, key)).ok()?;
+    Some(contents.lines().map(|line| line.to_string()).collect())
+}
+
+/// Caches a tidied list with the given key, making the cache directory if
+/// it doesn't exist yet
+pub fn write_cached_list(cache_dir: &Path, key: &str, list: &[String]) -> io::Result<()> {
+    fs::create_dir_all(cache_dir)?;
+    let contents: String = list.iter().map(|word| format!("{}\n", word)).collect();
+    fs::write(cached_list_path(cache_dir, key), contents)
+}
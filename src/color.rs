@@ -0,0 +1,97 @@
+//! Coloring of the attributes and reports Tidy prints to the terminal
+use std::env;
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether to color what Tidy prints
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorChoice {
+    /// Color only when printing to a terminal and `NO_COLOR` isn't set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl FromStr for ColorChoice {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => Err(format!(
+                "Unknown color choice {}. Use auto, always, or never.",
+                s
+            )),
+        }
+    }
+}
+
+/// How to highlight a value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Highlight {
+    Plain,
+    /// Something worth a second look, like a list with prefix words.
+    /// Yellow.
+    Warning,
+    /// A check the list failed, like not being uniquely decodable. Red.
+    Failure,
+}
+
+static USE_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Decides whether to color output, from the user's choice, whether the
+/// `NO_COLOR` environment variable is set (see <https://no-color.org>) and
+/// whether output goes to a terminal. Only `Always` overrides `NO_COLOR`.
+/// ```
+/// use tidy::color::{should_use_color, ColorChoice};
+/// assert!(should_use_color(ColorChoice::Auto, false, true));
+/// assert!(!should_use_color(ColorChoice::Auto, true, true));
+/// assert!(!should_use_color(ColorChoice::Auto, false, false));
+/// assert!(should_use_color(ColorChoice::Always, true, false));
+/// assert!(!should_use_color(ColorChoice::Never, false, true));
+/// ```
+pub fn should_use_color(choice: ColorChoice, no_color_set: bool, is_terminal: bool) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => !no_color_set && is_terminal,
+    }
+}
+
+/// Turns color on or off for everything Tidy prints afterward. Everything
+/// colored is printed to stderr, so that's the terminal that's checked.
+pub fn init_color(choice: ColorChoice) {
+    let no_color_set = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    USE_COLOR.store(
+        should_use_color(choice, no_color_set, io::stderr().is_terminal()),
+        Ordering::Relaxed,
+    );
+}
+
+/// Wraps text in the ANSI color codes of the highlight, if color is on
+/// ```
+/// use tidy::color::{highlight, Highlight};
+/// // Color is off until turned on with init_color
+/// assert_eq!(highlight("false", Highlight::Failure), "false");
+/// ```
+pub fn highlight(text: &str, highlight: Highlight) -> String {
+    let code = match highlight {
+        Highlight::Plain => return text.to_string(),
+        Highlight::Warning => "33",
+        Highlight::Failure => "31",
+    };
+    if USE_COLOR.load(Ordering::Relaxed) {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// The label printed before warnings, in yellow if color is on
+pub fn warning_label() -> String {
+    highlight("WARNING:", Highlight::Warning)
+}
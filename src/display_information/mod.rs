@@ -1,13 +1,25 @@
 //! Display attributes and information about the generated word list
 
-pub mod uniquely_decodable;
+use crate::color::{highlight, warning_label, Highlight};
 use crate::count_characters;
+use crate::display_information::distributions::*;
 use crate::display_information::uniquely_decodable::check_decodability;
+use crate::list_manipulations::{
+    find_prefix_words, find_suffix_words, is_mixed_script, ListChange,
+};
 use crate::parse_delimiter;
+use crate::pipeline::PipelineStep;
 use crate::split_and_vectorize;
+use crate::{TidyReport, WORD_STEPS_STAGE};
+use std::fmt;
+use std::time::Duration;
+pub use tidy_core::distributions;
+pub use tidy_core::uniquely_decodable;
 
 /// This is a large and long function that prints all of the attributes of
-/// the generated (new) list.
+/// the generated (new) list, as a table of names and values. Values that
+/// deserve a second look are highlighted in yellow, and failed checks in
+/// red, when color is on (see `color::init_color`).
 ///
 /// We just want to "display" this information, rather than print it to files
 /// or stdout, so we use `eprintln!`
@@ -16,81 +28,120 @@ pub fn display_list_information(
     level: u8,
     ignore_ending_metadata_delimiter: Option<char>,
     ignore_starting_metadata_delimiter: Option<char>,
+    separator: &str,
+    guesses_per_second: f64,
 ) {
-    let list = match (
+    let (list, metadata) = split_words_and_metadata(
+        list,
         ignore_ending_metadata_delimiter,
         ignore_starting_metadata_delimiter,
-    ) {
-        (Some(delimiter), None) => {
-            let delimiter = parse_delimiter(delimiter).unwrap();
-            let mut just_the_words = vec![];
-            for word in list {
-                let split_vec = split_and_vectorize(word, &delimiter.to_string());
-                just_the_words.push(split_vec[1].to_string());
-            }
-            just_the_words
-        }
-        (None, Some(delimiter)) => {
-            let delimiter = parse_delimiter(delimiter).unwrap();
-            let mut just_the_words = vec![];
-            for word in list {
-                let split_vec = split_and_vectorize(word, &delimiter.to_string());
-                just_the_words.push(split_vec[0].to_string());
-            }
-            just_the_words
-        }
-        (Some(ref _delimiter1), Some(ref _delimiter2)) => {
-            panic!("Can't ignore metadata on both sides currently")
-        }
-        (None, None) => list.to_vec(),
-    };
-    eprintln!("Attributes of new list");
-    eprintln!("----------------------");
+    );
+    display_heading("Attributes of new list");
     let list_length = list.len();
-    eprintln!("List length               : {} words", list_length);
-    eprintln!(
-        "Mean word length          : {:.2} characters",
-        mean_word_length(&list)
+    display_attribute("List length", format!("{} words", list_length));
+    display_attribute(
+        "Mean word length",
+        format!("{:.2} characters", mean_word_length(&list)),
+    );
+    display_attribute(
+        "Median word length",
+        format!("{} characters", median_word_length(&list)),
+    );
+    display_attribute(
+        &format!("Mean {}-word passphrase", SAMPLE_PASSPHRASE_LENGTH),
+        format!(
+            "{:.1} characters (with separators)",
+            mean_passphrase_length(&list, SAMPLE_PASSPHRASE_LENGTH, separator)
+        ),
     );
     let shortest_word = list
         .iter()
         .min_by(|a, b| count_characters(a).cmp(&count_characters(b)))
         .unwrap();
-    eprintln!(
-        "Length of shortest word   : {} characters ({})",
-        count_characters(shortest_word),
-        shortest_word
+    display_attribute(
+        "Length of shortest word",
+        format!(
+            "{} characters ({})",
+            count_characters(shortest_word),
+            shortest_word
+        ),
     );
     let longest_word = list
         .iter()
         .max_by(|a, b| count_characters(a).cmp(&count_characters(b)))
         .unwrap();
-    eprintln!(
-        "Length of longest word    : {} characters ({})",
-        count_characters(longest_word),
-        longest_word
+    display_attribute(
+        "Length of longest word",
+        format!(
+            "{} characters ({})",
+            count_characters(longest_word),
+            longest_word
+        ),
+    );
+    display_check(
+        "Free of prefix words?",
+        !has_prefix_words(&list),
+        Highlight::Warning,
+    );
+    display_check(
+        "Free of suffix words?",
+        !has_suffix_words(&list),
+        Highlight::Warning,
     );
-    let free_of_prefix_words = !has_prefix_words(&list);
-    eprintln!("Free of prefix words?     : {}", free_of_prefix_words);
-
-    let free_of_suffix_words = !has_suffix_words(&list);
-    eprintln!("Free of suffix words?     : {}", free_of_suffix_words);
 
     // At least for now, this one is EXPENSIVE
     if level >= 4 {
-        eprintln!("Uniquely decodable?       : {}", check_decodability(&list));
+        display_check(
+            "Uniquely decodable?",
+            check_decodability(&list),
+            Highlight::Failure,
+        );
     }
 
     let entropy_per_word = calc_entropy_per_word(list.len());
-    eprintln!("Entropy per word          : {:.3} bits", entropy_per_word);
-    eprintln!(
-        "Efficiency per character  : {:.3} bits",
-        efficiency_per_character(&list)
+    display_attribute("Entropy per word", format!("{:.3} bits", entropy_per_word));
+    // If metadata is word frequencies, show entropy of picking words in
+    // proportion to how common they are
+    if let Some(frequencies) = parse_frequencies(&metadata) {
+        display_attribute(
+            "Shannon entropy per word",
+            format!(
+                "{:.3} bits (picking by frequency)",
+                shannon_entropy(&frequencies)
+            ),
+        );
+    }
+    for (number_of_words, bits, seconds) in passphrase_entropy_table(list.len(), guesses_per_second)
+    {
+        display_attribute(
+            &format!("Entropy of {} words", number_of_words),
+            format!(
+                "{:.1} bits (cracked in {} on average)",
+                bits,
+                format_crack_time(seconds)
+            ),
+        );
+    }
+    display_attribute(
+        "Assumed guessing rate",
+        format!("{:e} guesses per second", guesses_per_second),
+    );
+    display_attribute(
+        "Efficiency per character",
+        format!("{:.3} bits", efficiency_per_character(&list)),
+    );
+    display_attribute(
+        "Bits per typed character",
+        format!(
+            "{:.3} bits (with {:?} separator)",
+            bits_per_typed_character(&list, separator),
+            separator
+        ),
     );
     let assumed_entropy_per_character = assumed_entropy_per_character(&list);
-    eprintln!(
-        "Assumed entropy per char  : {:.3} bits",
-        assumed_entropy_per_character
+    display_attribute(
+        "Assumed entropy per char",
+        format!("{:.3} bits", assumed_entropy_per_character),
     );
     // If user gets a passphrase consisting entirely of shortest words,
     // it's theoretically possible that we could OVERESTIMATE entropy
@@ -102,9 +153,10 @@ pub fn display_list_information(
     let g: i32 = 26; // roughly: assumed alphabet length
     let shortest_word_length = get_shortest_word_length(&list) as u32;
     let list_length = list.len() as i32;
-    eprintln!(
-        "Above brute force line?   : {}",
-        list_length <= g.pow(shortest_word_length)
+    display_check(
+        "Above brute force line?",
+        list_length <= g.pow(shortest_word_length),
+        Highlight::Failure,
     );
 
     // In 1951, Claude Shannon estimated that English words only have
@@ -113,47 +165,457 @@ pub fn display_list_information(
     // Thus, this is a more difficult line for a given list to pass above than
     // the "brute force" line described above.
     let g: f64 = 6.1; // 2**2.6 is 6.1 when we maintain correct number of significant digits.
-    eprintln!(
-        "Above Shannon line?       : {}",
-        list_length as f64 <= g.powf(shortest_word_length.into())
+    display_check(
+        "Above Shannon line?",
+        list_length as f64 <= g.powf(shortest_word_length.into()),
+        Highlight::Warning,
     );
 
     if level >= 2 {
-        eprintln!(
-            "Shortest edit distance    : {}",
-            find_shortest_edit_distance(&list)
-        );
+        let histogram = word_length_histogram(&list);
+        let most_words = histogram.iter().map(|(_length, count)| *count).max();
+        for (length, count) in &histogram {
+            // Scale bars so the longest is 40 characters
+            let bar_length = count * 40 / most_words.unwrap();
+            display_attribute(
+                &format!("Words of length {}", length),
+                format!("{:>5} {}", count, "#".repeat(bar_length)),
+            );
+        }
+        display_attribute("Shortest edit distance", find_shortest_edit_distance(&list));
         if level >= 3 {
-            eprintln!(
-                "Mean edit distance        : {:.3}",
-                find_mean_edit_distance(&list)
+            display_attribute(
+                "Mean edit distance",
+                format!("{:.3}", find_mean_edit_distance(&list)),
             );
         }
         let longest_shared_prefix = find_longest_shared_prefix(&list);
-        eprintln!("Longest shared prefix     : {}", longest_shared_prefix);
+        display_attribute("Longest shared prefix", longest_shared_prefix);
         // Numbers of characters required to definitely get to a unique
         // prefix
-        eprintln!("Unique character prefix   : {}", longest_shared_prefix + 1);
+        display_attribute("Unique character prefix", longest_shared_prefix + 1);
+        let inflections = find_inflections(&list);
+        match inflections.first() {
+            Some((base, inflection)) => display_highlighted_attribute(
+                "Probable inflections",
+                format!("{} (like {} → {})", inflections.len(), base, inflection),
+                Highlight::Warning,
+            ),
+            None => display_attribute("Probable inflections", 0),
+        }
+        let mixed_script_words: Vec<&String> =
+            list.iter().filter(|word| is_mixed_script(word)).collect();
+        match mixed_script_words.first() {
+            Some(word) => display_highlighted_attribute(
+                "Mixed-script words",
+                format!("{} (like {})", mixed_script_words.len(), word),
+                Highlight::Warning,
+            ),
+            None => display_attribute("Mixed-script words", 0),
+        }
     }
     if level >= 5 {
-        let mcmillan = if satisfies_mcmillan(&list) {
-            "satisfied"
+        if satisfies_mcmillan(&list) {
+            display_attribute("Kraft-McMillan inequality", "satisfied");
         } else {
-            "not satisfied"
-        };
-        eprintln!("Kraft-McMillan inequality : {}", mcmillan);
+            display_highlighted_attribute(
+                "Kraft-McMillan inequality",
+                "not satisfied",
+                Highlight::Failure,
+            );
+        }
+        display_attribute(
+            "Kraft-McMillan sum",
+            format!("{:.6}", kraft_mcmillan_sum(&list)),
+        );
+        for (length, max_words) in max_additional_words_by_length(&list) {
+            display_attribute(
+                &format!("Room for {}-char words", length),
+                format!("{} words", max_words),
+            );
+        }
+    }
+    if level >= 6 {
+        let letters = letter_frequencies(&list);
+        let total_letters = letters.iter().map(|(_letter, count)| count).sum();
+        display_attribute(
+            "Most common letters",
+            format_top_frequencies(&letters, total_letters, 10),
+        );
+        display_attribute(
+            "Most common first letters",
+            format_top_frequencies(&first_letter_frequencies(&list), list.len(), 10),
+        );
+        let bigrams = bigram_frequencies(&list);
+        let total_bigrams = bigrams.iter().map(|(_bigram, count)| count).sum();
+        display_attribute(
+            "Most common bigrams",
+            format_top_frequencies(&bigrams, total_bigrams, 10),
+        );
+    }
+}
+
+/// How wide the names in a table of attributes are padded to, so that
+/// their values line up
+const ATTRIBUTE_NAME_WIDTH: usize = 26;
+
+/// Formats one row of a table of attributes: a name, padded so that values
+/// line up, and its value
+/// ```
+/// use tidy::display_information::format_attribute;
+/// assert_eq!(format_attribute("List length", "7776 words"), "List length               : 7776 words");
+/// // Long names push their value over, rather than being cut off
+/// assert_eq!(
+///     format_attribute("Most common first letters", 5),
+///     "Most common first letters : 5"
+/// );
+/// ```
+pub fn format_attribute(name: &str, value: impl fmt::Display) -> String {
+    let padding = ATTRIBUTE_NAME_WIDTH.saturating_sub(count_characters(name));
+    format!("{}{}: {}", name, " ".repeat(padding), value)
+}
+
+/// Prints a heading of a table of attributes, underlined
+fn display_heading(heading: &str) {
+    eprintln!("{}", heading);
+    eprintln!("{}", "-".repeat(count_characters(heading)));
+}
+
+/// Prints one row of a table of attributes
+fn display_attribute(name: &str, value: impl fmt::Display) {
+    eprintln!("{}", format_attribute(name, value));
+}
+
+/// Prints one row of a table of attributes, with its value highlighted
+fn display_highlighted_attribute(name: &str, value: impl fmt::Display, how: Highlight) {
+    display_attribute(name, highlight(&value.to_string(), how));
+}
+
+/// Prints the result of a yes-or-no check, highlighting it if the list
+/// failed it
+fn display_check(name: &str, passed: bool, how_to_highlight_failure: Highlight) {
+    if passed {
+        display_attribute(name, passed);
+    } else {
+        display_highlighted_attribute(name, passed, how_to_highlight_failure);
+    }
+}
+
+/// Finds the clean power, of 6 (for dice) or 2 (for bits), that a list of
+/// the given length falls just short of: within 2 percent of it. Returns the
+/// base, the exponent, and the power itself. If a list is just short of more
+/// than one power, the smallest is returned.
+/// ```
+/// use tidy::display_information::find_power_just_above;
+/// assert_eq!(find_power_just_above(7740), Some((6, 5, 7776)));
+/// assert_eq!(find_power_just_above(2040), Some((2, 11, 2048)));
+/// assert_eq!(find_power_just_above(7776), None);
+/// assert_eq!(find_power_just_above(5000), None);
+/// ```
+pub fn find_power_just_above(list_length: usize) -> Option<(usize, u32, usize)> {
+    let powers_of_six = (3..=8).map(|exponent| (6_usize, exponent));
+    let powers_of_two = (7..=20).map(|exponent| (2_usize, exponent));
+    powers_of_six
+        .chain(powers_of_two)
+        .map(|(base, exponent)| (base, exponent, base.pow(exponent)))
+        .filter(|(_base, _exponent, power)| {
+            list_length < *power && (power - list_length) as f64 <= *power as f64 * 0.02
+        })
+        .min_by_key(|(_base, _exponent, power)| *power)
+}
+
+/// If the list is just short of a clean power, like 6^5 (7,776) words,
+/// print how many more words it would need to reach it.
+pub fn display_length_advice(list_length: usize) {
+    if let Some((base, exponent, power)) = find_power_just_above(list_length) {
+        eprintln!(
+            "NOTE: New list has {} words, {} short of {}^{} ({} words). To reach it, add words or relax a filter (like a minimum or maximum word length). Use --no-length-advice to hide this note.",
+            format_with_commas(list_length),
+            format_with_commas(power - list_length),
+            base,
+            exponent,
+            format_with_commas(power)
+        );
+    }
+}
+
+/// Prints how much longer the list would be with each of the given
+/// filters relaxed, from `suggest_relaxations`, and which would get it to
+/// the target length.
+pub fn display_relaxation_suggestions(
+    suggestions: &[(String, usize)],
+    list_length: usize,
+    target: usize,
+) {
+    eprintln!(
+        "New list has {} words, {} short of the target of {}.",
+        format_with_commas(list_length),
+        format_with_commas(target - list_length),
+        format_with_commas(target)
+    );
+    if suggestions.is_empty() {
+        eprintln!("Relaxing any one filter wouldn't make the list longer.");
+        return;
+    }
+    eprintln!("Relaxing one filter would make:");
+    for (relaxation, relaxed_length) in suggestions {
+        eprintln!(
+            "  {:<34}: {} words (+{}){}",
+            relaxation,
+            format_with_commas(*relaxed_length),
+            format_with_commas(relaxed_length - list_length),
+            if *relaxed_length >= target {
+                ", enough to reach the target"
+            } else {
+                ""
+            }
+        );
+    }
+}
+
+/// Print a warning for each 1- or 2-character prefix that more than
+/// `maximum_percent` of the list's words start with.
+pub fn display_skew_warnings(
+    list: &[String],
+    maximum_percent: f64,
+    ignore_ending_metadata_delimiter: Option<char>,
+    ignore_starting_metadata_delimiter: Option<char>,
+) {
+    let (list, _metadata) = split_words_and_metadata(
+        list,
+        ignore_ending_metadata_delimiter,
+        ignore_starting_metadata_delimiter,
+    );
+    for (prefix, percent) in find_skewed_prefixes(&list, maximum_percent) {
+        eprintln!(
+            "{} {:.1}% of words start with \"{}\", more than the {}% allowed by --warn-skew",
+            warning_label(),
+            percent,
+            prefix,
+            maximum_percent
+        );
+    }
+}
+
+/// Prints the parts of the report on how the list was made that the user
+/// asked to see, along with any warnings and the best prune comparison.
+pub fn display_tidy_report(report: &TidyReport, explain: bool, audit: bool, summary: bool) {
+    for line in &report.lines_without_metadata {
+        eprintln!("No metadata found for word: {:?}", line);
+    }
+    for conflict in &report.always_include_conflicts {
+        eprintln!(
+            "{} Always-included word conflict: {}",
+            warning_label(),
+            conflict
+        );
+    }
+    if report.schlinkert_timed_out {
+        eprintln!(
+            "{} Schlinkert prune timed out, so prefix words were removed instead.\n",
+            warning_label()
+        );
+    }
+    if let Some((method, ref comparison)) = report.best_prune {
+        eprintln!("Best prune comparison:");
+        for (candidate_method, length) in comparison {
+            eprintln!("  {:<20}: {} words", candidate_method, length);
+        }
+        eprintln!("Kept list from: {}\n", method);
+    }
+    if explain && !report.whittle_guesses.is_empty() {
+        eprintln!(
+            "Whittled in {} guesses (words taken: list length):",
+            report.whittle_guesses.len()
+        );
+        for (words_taken, length) in &report.whittle_guesses {
+            eprintln!("  {:<26}: {}", words_taken, length);
+        }
+        eprintln!();
+    }
+    if explain {
+        display_step_counts(&report.step_counts);
+    }
+    if audit {
+        eprintln!("Audit: {}\n", format_audit_trail(&report.step_counts));
+    }
+    if summary {
+        eprintln!(
+            "{}\n",
+            format_step_summary(&report.step_counts, &report.step_timings)
+        );
+    }
+}
+
+/// Prints each step Tidy ran on the list, in order, and how many words were
+/// left after it. See `tidy_list_explained`.
+pub fn display_step_counts(counts: &[(&str, usize)]) {
+    eprintln!("Pipeline (words left after each step):");
+    for (step, words_left) in counts {
+        eprintln!("  {:<26}: {}", step, words_left);
+    }
+    eprintln!();
+}
+
+/// Formats step counts and timings (see `TidyReport`) as a table of how many
+/// words were left after each step, how many it removed, and how long it
+/// took. Word modifications and removals are each timed in their own rows,
+/// and timed all together in a row before the first step done to the whole
+/// list.
+/// ```
+/// use std::time::Duration;
+/// use tidy::display_information::format_step_summary;
+/// let counts = vec![("input", 1200), ("lowercase", 1200), ("minimum-length", 980), ("dedup", 845)];
+/// let timings = vec![
+///     ("word-steps", Duration::from_millis(2)),
+///     ("lowercase", Duration::from_micros(500)),
+///     ("minimum-length", Duration::from_micros(1250)),
+///     ("dedup", Duration::from_millis(1)),
+/// ];
+/// let summary = format_step_summary(&counts, &timings);
+/// let rows: Vec<&str> = summary.lines().collect();
+/// assert_eq!(rows[4], "  minimum-length            :          980          220       1.25ms");
+/// assert_eq!(rows[5], "  word-steps                :                                 2.00ms");
+/// assert_eq!(rows[6], "  dedup                     :          845          135       1.00ms");
+/// ```
+pub fn format_step_summary(counts: &[(&str, usize)], timings: &[(&str, Duration)]) -> String {
+    let time_of = |stage: &str| {
+        timings
+            .iter()
+            .find(|(timed_stage, _elapsed)| *timed_stage == stage)
+            .map(|(_timed_stage, elapsed)| format!("{:.2?}", elapsed))
+            .unwrap_or_default()
+    };
+    let row = |stage: &str, words_left: &str, removed: &str, elapsed: &str| {
+        format!(
+            "  {:<26}: {:>12} {:>12} {:>12}",
+            stage, words_left, removed, elapsed
+        )
+        .trim_end()
+        .to_string()
+    };
+    let mut rows = vec![
+        "Pipeline summary (words left after each step, words it removed, and time it took):"
+            .to_string(),
+        row("step", "words left", "removed", "time"),
+    ];
+    let mut word_steps_shown = !timings
+        .iter()
+        .any(|(timed_stage, _elapsed)| *timed_stage == WORD_STEPS_STAGE);
+    let mut previous_words_left: Option<usize> = None;
+    for (stage, words_left) in counts {
+        let is_list_step = stage
+            .parse::<PipelineStep>()
+            .is_ok_and(|step| !step.is_word_step());
+        if is_list_step && !word_steps_shown {
+            rows.push(row(WORD_STEPS_STAGE, "", "", &time_of(WORD_STEPS_STAGE)));
+            word_steps_shown = true;
+        }
+        let removed = previous_words_left
+            .map(|previous| format_with_commas(previous.saturating_sub(*words_left)))
+            .unwrap_or_default();
+        rows.push(row(
+            stage,
+            &format_with_commas(*words_left),
+            &removed,
+            &time_of(stage),
+        ));
+        previous_words_left = Some(*words_left);
+    }
+    rows.join("\n")
+}
+
+/// Formats step counts as a one-line audit trail, from the inputted words to
+/// the final list. See `tidy_list_explained`.
+/// ```
+/// use tidy::display_information::format_audit_trail;
+/// let counts = vec![("input", 120000), ("dedup", 84512), ("sort", 84512)];
+/// assert_eq!(
+///     format_audit_trail(&counts),
+///     "input 120,000 → dedup 84,512 → sort 84,512 → final 84,512"
+/// );
+/// ```
+pub fn format_audit_trail(counts: &[(&str, usize)]) -> String {
+    let mut stages: Vec<String> = counts
+        .iter()
+        .map(|(step, words_left)| format!("{} {}", step, format_with_commas(*words_left)))
+        .collect();
+    if let Some((_step, final_count)) = counts.last() {
+        stages.push(format!("final {}", format_with_commas(*final_count)));
+    }
+    stages.join(" → ")
+}
+
+/// Formats a number with commas between each group of three digits
+/// ```
+/// use tidy::display_information::format_with_commas;
+/// assert_eq!(format_with_commas(7776), "7,776");
+/// assert_eq!(format_with_commas(100), "100");
+/// ```
+pub fn format_with_commas(number: usize) -> String {
+    let digits = number.to_string();
+    let mut formatted = String::new();
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+/// Separates words from any metadata the user asked Tidy to ignore. Returns
+/// the words and, if the metadata comes after the words (like frequency
+/// counts), the metadata.
+pub fn split_words_and_metadata(
+    list: &[String],
+    ignore_ending_metadata_delimiter: Option<char>,
+    ignore_starting_metadata_delimiter: Option<char>,
+) -> (Vec<String>, Vec<String>) {
+    match (
+        ignore_ending_metadata_delimiter,
+        ignore_starting_metadata_delimiter,
+    ) {
+        (Some(delimiter), None) => {
+            let delimiter = parse_delimiter(delimiter).unwrap();
+            let mut just_the_words = vec![];
+            for word in list {
+                let split_vec = split_and_vectorize(word, &delimiter.to_string());
+                just_the_words.push(split_vec[1].to_string());
+            }
+            // Metadata before words is usually dice rolls, not frequencies,
+            // so don't keep it
+            (just_the_words, vec![])
+        }
+        (None, Some(delimiter)) => {
+            let delimiter = parse_delimiter(delimiter).unwrap();
+            let mut just_the_words = vec![];
+            let mut metadata = vec![];
+            for word in list {
+                let split_vec = split_and_vectorize(word, &delimiter.to_string());
+                just_the_words.push(split_vec[0].to_string());
+                metadata.push(split_vec[1].to_string());
+            }
+            (just_the_words, metadata)
+        }
+        (Some(ref _delimiter1), Some(ref _delimiter2)) => {
+            panic!("Can't ignore metadata on both sides currently")
+        }
+        (None, None) => (list.to_vec(), vec![]),
     }
 }
+
 use rand::seq::SliceRandom;
-/// Print 5 sample 6-word passphrases from the newly created
-/// word list.
+/// Pick `number_of_words` pseudorandom words from the newly created word
+/// list, without their metadata, to make sample passphrases from.
 pub fn generate_samples(
     list: &[String],
     ignore_ending_metadata_delimiter: Option<char>,
     ignore_starting_metadata_delimiter: Option<char>,
+    number_of_words: usize,
 ) -> Vec<String> {
     let mut samples: Vec<String> = vec![];
-    for _n in 0..30 {
+    for _n in 0..number_of_words {
         match list.choose(&mut rand::thread_rng()) {
             Some(word) => {
                 match (
@@ -193,11 +655,120 @@ pub fn calc_entropy_per_word(list_length: usize) -> f64 {
     (list_length as f64).log2()
 }
 
+/// For passphrases of 3 to 7 words from a list of the given length, the
+/// number of words, the passphrase's entropy in bits, and how many seconds
+/// an attacker making `guesses_per_second` guesses would take, on average,
+/// to find it. On average, an attacker has to try half of all possible
+/// passphrases.
+/// ```
+/// use tidy::display_information::passphrase_entropy_table;
+/// let table = passphrase_entropy_table(7776, 1e12);
+/// assert_eq!(table.len(), 5);
+/// let (number_of_words, bits, seconds) = table[0];
+/// assert_eq!(number_of_words, 3);
+/// assert_eq!(format!("{:.2}", bits), "38.77");
+/// assert_eq!(format!("{:.3}", seconds), "0.235");
+/// ```
+pub fn passphrase_entropy_table(
+    list_length: usize,
+    guesses_per_second: f64,
+) -> Vec<(usize, f64, f64)> {
+    (3..=7)
+        .map(|number_of_words| {
+            let bits = calc_entropy_per_word(list_length) * number_of_words as f64;
+            let possible_passphrases = (list_length as f64).powi(number_of_words as i32);
+            (
+                number_of_words,
+                bits,
+                possible_passphrases / 2.0 / guesses_per_second,
+            )
+        })
+        .collect()
+}
+
+/// Describes a number of seconds in the largest unit that fits, like
+/// "3 hours" or "1,200 years". Very long times are given in years, in
+/// scientific notation.
+/// ```
+/// use tidy::display_information::format_crack_time;
+/// assert_eq!(format_crack_time(0.2), "less than a second");
+/// assert_eq!(format_crack_time(90.0), "2 minutes");
+/// assert_eq!(format_crack_time(3.0 * 24.0 * 60.0 * 60.0), "3 days");
+/// assert_eq!(format_crack_time(1e20), "3.2e12 years");
+/// ```
+pub fn format_crack_time(seconds: f64) -> String {
+    let units = [
+        ("second", 1.0),
+        ("minute", 60.0),
+        ("hour", 60.0 * 60.0),
+        ("day", 24.0 * 60.0 * 60.0),
+        ("year", 365.25 * 24.0 * 60.0 * 60.0),
+    ];
+    if seconds < 1.0 {
+        return "less than a second".to_string();
+    }
+    let (unit, seconds_per_unit) = units
+        .iter()
+        .rev()
+        .find(|(_unit, seconds_per_unit)| seconds >= *seconds_per_unit)
+        .unwrap();
+    let amount = (seconds / seconds_per_unit).round();
+    if amount >= 1e9 {
+        format!("{:.1e} {}s", amount, unit)
+    } else if amount == 1.0 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", format_with_commas(amount as usize), unit)
+    }
+}
+
+use crate::strength::{estimate_strength, score_for_guesses};
+/// Prints zxcvbn's estimate of how strong each sample passphrase is, then,
+/// to compare, the strength of a passphrase of as many words from a list of
+/// the given length, by its entropy. An attacker is assumed to make
+/// `guesses_per_second` guesses. Exits with an error if Tidy was built
+/// without the strength feature.
+pub fn display_sample_strengths(
+    passphrases: &[String],
+    list_length: usize,
+    words_per_passphrase: usize,
+    guesses_per_second: f64,
+) {
+    eprintln!();
+    display_heading("Estimated strength of sample passphrases (zxcvbn)");
+    for passphrase in passphrases {
+        let estimate = match estimate_strength(passphrase) {
+            Ok(estimate) => estimate,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        };
+        eprintln!(
+            "{} (score {}/4, cracked in {})",
+            passphrase,
+            estimate.score,
+            format_crack_time(10f64.powf(estimate.guesses_log10) / guesses_per_second)
+        );
+    }
+    // On average, an attacker has to try half of all possible passphrases
+    let average_guesses = (list_length as f64).powi(words_per_passphrase as i32) / 2.0;
+    display_attribute(
+        &format!("Entropy of {} words", words_per_passphrase),
+        format!(
+            "{:.1} bits (score {}/4, cracked in {} on average)",
+            calc_entropy_per_word(list_length) * words_per_passphrase as f64,
+            score_for_guesses(average_guesses),
+            format_crack_time(average_guesses / guesses_per_second)
+        ),
+    );
+}
+
 use crate::edit_distance::find_edit_distance;
 /// Calculate the shortest edit distance between any two words on the list.
 fn find_shortest_edit_distance(list: &[String]) -> usize {
-    // This use of max_value is smelly, but not sure I know how to do it better.
-    let mut shortest_edit_distance = u32::max_value();
+    // This use of MAX is smelly, but not sure I know how to do it better.
+    let mut shortest_edit_distance = u32::MAX;
     // I think I can cheat and only go through half of the list here
     for word1 in list[0..(list.len() / 2)].iter() {
         for word2 in list {
@@ -305,27 +876,13 @@ pub fn find_first_different_character_zero_indexed(word1: &str, word2: &str) ->
 /// Checks if a list has any words that are prefixs of other
 /// words on the list.
 fn has_prefix_words(list: &[String]) -> bool {
-    for word1 in list {
-        for word2 in list {
-            if word1 != word2 && word1.starts_with(word2) {
-                return true;
-            }
-        }
-    }
-    false
+    !find_prefix_words(list).is_empty()
 }
 
 /// Checks if a list has any words that are suffixes of other
 /// words on the list.
 fn has_suffix_words(list: &[String]) -> bool {
-    for word1 in list {
-        for word2 in list {
-            if word1 != word2 && word1.ends_with(word2) {
-                return true;
-            }
-        }
-    }
-    false
+    !find_suffix_words(list).is_empty()
 }
 
 /// Assuming that users get a passphrase consisting solely of
@@ -343,6 +900,48 @@ pub fn assumed_entropy_per_character(list: &[String]) -> f64 {
     assumed_entropy_per_word / shortest_word_length
 }
 
+/// Parses word metadata as frequency counts. Returns `None` if there is no
+/// metadata or if any of it isn't a non-negative number.
+/// ```
+/// use tidy::display_information::parse_frequencies;
+/// let metadata: Vec<String> = vec!["1039008".to_string(), "42".to_string()];
+/// assert_eq!(parse_frequencies(&metadata), Some(vec![1039008.0, 42.0]));
+/// assert_eq!(parse_frequencies(&["11111".to_string(), "n/a".to_string()]), None);
+/// ```
+pub fn parse_frequencies(metadata: &[String]) -> Option<Vec<f64>> {
+    if metadata.is_empty() {
+        return None;
+    }
+    metadata
+        .iter()
+        .map(|m| match m.trim().parse::<f64>() {
+            Ok(frequency) if frequency >= 0.0 && frequency.is_finite() => Some(frequency),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Calculates the Shannon entropy, in bits, of picking a word with
+/// probability proportional to its frequency. This equals the
+/// uniform `log2(N)` only if every word is equally common; otherwise
+/// it's lower.
+/// ```
+/// use tidy::display_information::shannon_entropy;
+/// assert_eq!(shannon_entropy(&[5.0, 5.0, 5.0, 5.0]), 2.0);
+/// assert_eq!(shannon_entropy(&[3.0, 1.0, 0.0]), 0.8112781244591328);
+/// ```
+pub fn shannon_entropy(frequencies: &[f64]) -> f64 {
+    let total: f64 = frequencies.iter().sum();
+    frequencies
+        .iter()
+        .filter(|&&frequency| frequency > 0.0)
+        .map(|frequency| {
+            let probability = frequency / total;
+            -probability * probability.log2()
+        })
+        .sum()
+}
+
 /// Calculates the "efficiency" of the list.
 /// Basically this is the number of bits of entropy generated by
 /// the AVERAGE character. Thus it is different from
@@ -356,17 +955,79 @@ pub fn efficiency_per_character(list: &[String]) -> f64 {
     entropy_per_word / mean_word_length
 }
 
+/// Like `efficiency_per_character`, but also counts the characters of the
+/// separator the user types between words: `log2(N)` divided by the mean word
+/// length plus the length of the separator. Useful for comparing lists on
+/// typing efficiency.
+/// ```
+/// use tidy::display_information::bits_per_typed_character;
+/// let list: Vec<String> = ["abc", "def", "ghi", "jkl"].iter().map(|w| w.to_string()).collect();
+/// // 2 bits per word, over 3 letters plus 1 separator
+/// assert_eq!(bits_per_typed_character(&list, "-"), 0.5);
+/// ```
+pub fn bits_per_typed_character(list: &[String], separator: &str) -> f64 {
+    let typed_characters_per_word =
+        mean_word_length(list) as f64 + count_characters(separator) as f64;
+    calc_entropy_per_word(list.len()) / typed_characters_per_word
+}
+
+/// Calculates the mean number of characters in a passphrase of
+/// `number_of_words` words from the list, including separators.
+/// ```
+/// use tidy::display_information::mean_passphrase_length;
+/// let list: Vec<String> = ["to", "cold"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(mean_passphrase_length(&list, 6, " "), 23.0);
+/// ```
+pub fn mean_passphrase_length(list: &[String], number_of_words: usize, separator: &str) -> f32 {
+    mean_word_length(list) * number_of_words as f32
+        + (count_characters(separator) * number_of_words.saturating_sub(1)) as f32
+}
+
 /// This function returns a bool based on whether the list fulfills something
 /// called the McMillan Inequality
 /// See: https://www.youtube.com/watch?v=yHw1ka-4g0s
 pub fn satisfies_mcmillan(list: &[String]) -> bool {
-    let alphabet_size = count_unique_characters(list);
-    let mut running_total: f64 = 0.0;
-    for word in list {
-        running_total +=
-            1.0 / (alphabet_size.pow(count_characters(word).try_into().unwrap()) as f64);
-    }
-    running_total <= 1.0
+    kraft_mcmillan_sum(list) <= 1.0
+}
+
+/// Calculates the left-hand side of the Kraft-McMillan inequality: the sum
+/// of `D^-l` for every word, where `D` is the number of unique characters on
+/// the list and `l` is the length of the word. Every uniquely decodable list
+/// has a sum of 1 or less.
+/// ```
+/// use tidy::display_information::kraft_mcmillan_sum;
+/// let list: Vec<String> = ["a", "ba", "bb"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(kraft_mcmillan_sum(&list), 1.0);
+/// ```
+pub fn kraft_mcmillan_sum(list: &[String]) -> f64 {
+    let alphabet_size = count_unique_characters(list) as f64;
+    list.iter()
+        .map(|word| 1.0 / alphabet_size.powi(count_characters(word) as i32))
+        .sum()
+}
+
+/// For each word length from the shortest to the longest on the list,
+/// calculates the maximum number of words of that length (using the list's
+/// own alphabet) that could be added to the list while it still satisfies the
+/// Kraft-McMillan inequality.
+/// ```
+/// use tidy::display_information::max_additional_words_by_length;
+/// let list: Vec<String> = ["a", "ba"].iter().map(|w| w.to_string()).collect();
+/// // Kraft-McMillan sum is 0.75, so there's room for one more 2-letter word
+/// assert_eq!(max_additional_words_by_length(&list), vec![(1, 0), (2, 1)]);
+/// ```
+pub fn max_additional_words_by_length(list: &[String]) -> Vec<(usize, u64)> {
+    let room = (1.0 - kraft_mcmillan_sum(list)).max(0.0);
+    let alphabet_size = count_unique_characters(list) as f64;
+    let shortest = get_shortest_word_length(list);
+    let longest = list.iter().map(|w| count_characters(w)).max().unwrap();
+    (shortest..=longest)
+        .map(|length| {
+            // Round away floating-point error before taking the floor
+            let max_words = room * alphabet_size.powi(length as i32);
+            (length, (max_words + 1e-9).floor() as u64)
+        })
+        .collect()
 }
 
 fn count_unique_characters(list: &[String]) -> usize {
@@ -391,6 +1052,47 @@ pub fn get_shortest_word_length(list: &[String]) -> usize {
     )
 }
 
+/// Number of words in the passphrases used to estimate typing effort
+/// (matches the sample passphrases)
+const SAMPLE_PASSPHRASE_LENGTH: usize = 6;
+
+/// Calculates the median word length of given word list. If the list has an
+/// even number of words, this is the mean of the two middle lengths.
+/// ```
+/// use tidy::display_information::median_word_length;
+/// let list: Vec<String> = ["to", "cold", "canopy"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(median_word_length(&list), 4.0);
+/// ```
+pub fn median_word_length(list: &[String]) -> f32 {
+    let mut lengths: Vec<usize> = list.iter().map(|word| count_characters(word)).collect();
+    lengths.sort_unstable();
+    let middle = lengths.len() / 2;
+    if lengths.len().is_multiple_of(2) {
+        (lengths[middle - 1] + lengths[middle]) as f32 / 2.0
+    } else {
+        lengths[middle] as f32
+    }
+}
+
+/// Counts how many words there are of each length, from the
+/// shortest length on the list to the longest.
+/// ```
+/// use tidy::display_information::word_length_histogram;
+/// let list: Vec<String> = ["to", "cold", "fire", "canopy"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(
+///     word_length_histogram(&list),
+///     vec![(2, 1), (3, 0), (4, 2), (5, 0), (6, 1)]
+/// );
+/// ```
+pub fn word_length_histogram(list: &[String]) -> Vec<(usize, usize)> {
+    let lengths: Vec<usize> = list.iter().map(|word| count_characters(word)).collect();
+    let shortest = *lengths.iter().min().unwrap();
+    let longest = *lengths.iter().max().unwrap();
+    (shortest..=longest)
+        .map(|length| (length, lengths.iter().filter(|&&l| l == length).count()))
+        .collect()
+}
+
 /// Calculates mean (or average) word length of given word
 /// list
 pub fn mean_word_length(list: &[String]) -> f32 {
@@ -399,3 +1101,368 @@ pub fn mean_word_length(list: &[String]) -> f32 {
         .sum::<usize>() as f32
         / list.len() as f32
 }
+
+use crate::bip39::find_bip39_violations;
+/// Print whether the list complies with the BIP39 standard and,
+/// if it does not, every violation found.
+pub fn display_bip39_report(list: &[String]) {
+    let violations = find_bip39_violations(list);
+    eprintln!();
+    display_heading("BIP39 compliance");
+    display_check(
+        "BIP39 compliant?",
+        violations.is_empty(),
+        Highlight::Failure,
+    );
+    for violation in violations {
+        eprintln!("  {}", violation);
+    }
+}
+
+use crate::display_information::uniquely_decodable::find_ambiguous_concatenation;
+/// Print whether the list is uniquely decodable and, if it is not, an
+/// example of a string that can be split into words from the list in two
+/// different ways.
+pub fn display_decodability_report(list: &[String]) {
+    eprintln!();
+    display_heading("Unique decodability");
+    match find_ambiguous_concatenation(list) {
+        None => display_check("Uniquely decodable?", true, Highlight::Failure),
+        Some((first, second)) => {
+            display_check("Uniquely decodable?", false, Highlight::Failure);
+            display_attribute(
+                "Ambiguous example",
+                format!(
+                    "\"{}\" is both {} and {}",
+                    first.concat(),
+                    first.join(" + "),
+                    second.join(" + ")
+                ),
+            );
+        }
+    }
+}
+
+use crate::compound_collisions::find_compound_collisions;
+/// Print every word, or pair of words, on the list that spells the same
+/// thing as another pair of words, when typed without a separator
+pub fn display_compound_collisions_report(list: &[String]) {
+    let collisions = find_compound_collisions(list);
+    eprintln!();
+    display_heading("Compound collisions");
+    if collisions.is_empty() {
+        display_attribute("Compound collisions", 0);
+    } else {
+        display_highlighted_attribute("Compound collisions", collisions.len(), Highlight::Warning);
+    }
+    for (first, second) in collisions {
+        eprintln!(
+            "  \"{}\" is both {} and {}",
+            first.concat(),
+            first.join(" + "),
+            second.join(" + ")
+        );
+    }
+}
+
+use std::collections::HashSet;
+/// Formats examples of strings that can be split into words in two ways
+/// (see `find_ambiguous_concatenations`) for an ambiguity report, under a
+/// heading. Words of each example that aren't on the new list are noted as
+/// removed.
+/// ```
+/// use tidy::display_information::format_ambiguity_examples;
+/// let examples = vec![(
+///     vec!["newspaper".to_string()],
+///     vec!["news".to_string(), "paper".to_string()],
+/// )];
+/// let new_list = vec!["newspaper".to_string(), "paper".to_string()];
+/// assert_eq!(
+///     format_ambiguity_examples("Before prune", &examples, &new_list),
+///     "Before prune\n------------\n\"newspaper\" is both newspaper and news + paper (removed: news)\n\n"
+/// );
+/// ```
+pub fn format_ambiguity_examples(
+    heading: &str,
+    examples: &[(Vec<String>, Vec<String>)],
+    new_list: &[String],
+) -> String {
+    let kept: HashSet<&str> = new_list.iter().map(|word| word.as_str()).collect();
+    let mut text = format!("{}\n{}\n", heading, "-".repeat(count_characters(heading)));
+    if examples.is_empty() {
+        text += "None found\n";
+    }
+    for (first, second) in examples {
+        text += &format!(
+            "\"{}\" is both {} and {}",
+            first.concat(),
+            first.join(" + "),
+            second.join(" + ")
+        );
+        let mut removed: Vec<&str> = vec![];
+        for word in first.iter().chain(second) {
+            if !kept.contains(word.as_str()) && !removed.contains(&word.as_str()) {
+                removed.push(word);
+            }
+        }
+        if !removed.is_empty() {
+            text += &format!(" (removed: {})", removed.join(", "));
+        }
+        text += "\n";
+    }
+    text + "\n"
+}
+
+use crate::dice::{count_dice_needed, count_unreachable_rolls, make_reroll_table, DiceFormat};
+/// Warn when some dice rolls don't correspond to any word of the list,
+/// along with the exact number of these rolls. If requested, also print
+/// a table of the rolls to re-roll.
+pub fn display_unreachable_rolls(
+    list_length: usize,
+    dice_sides: u8,
+    use_letters: bool,
+    dice_format: &DiceFormat,
+    reroll_table: bool,
+) {
+    let unreachable_rolls = count_unreachable_rolls(list_length, dice_sides);
+    if list_length == 0 || unreachable_rolls == 0 {
+        return;
+    }
+    let dice = count_dice_needed(list_length, dice_sides);
+    eprintln!(
+        "{} List length of {} is not a power of {}. {} of the {} possible rolls of {} {}-sided dice don't correspond to a word and must be re-rolled.",
+        warning_label(),
+        list_length,
+        dice_sides,
+        unreachable_rolls,
+        unreachable_rolls + list_length,
+        dice,
+        dice_sides
+    );
+    if reroll_table {
+        eprintln!("\nRe-roll table");
+        eprintln!("-------------");
+        match make_reroll_table(list_length, dice_sides, use_letters, dice_format) {
+            Ok(table) => {
+                for (first, last) in table {
+                    if first == last {
+                        eprintln!("Re-roll {}", first);
+                    } else {
+                        eprintln!("Re-roll {} through {}", first, last);
+                    }
+                }
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+use crate::inflections::find_inflections;
+/// Print every pair of words on the list where one is probably an
+/// inflection of the other
+pub fn display_inflections_report(list: &[String]) {
+    let inflections = find_inflections(list);
+    eprintln!("\nProbable inflections");
+    eprintln!("--------------------");
+    eprintln!("Probable inflections      : {}", inflections.len());
+    for (base, inflection) in inflections {
+        eprintln!("  {} → {}", base, inflection);
+    }
+}
+
+/// Print each change made to a previous version of the list while keeping
+/// its words' positions (see `keep_previous_positions`). `label` gives the
+/// dice roll, or other label, of each position.
+pub fn display_previous_list_changes(changes: &[ListChange], label: &dyn Fn(usize) -> String) {
+    eprintln!("\nChanges from previous list");
+    eprintln!("--------------------------");
+    eprintln!("Positions changed         : {}", changes.len());
+    for (position, old_word, new_word) in changes {
+        eprintln!(
+            "  {}: {} → {}",
+            label(*position),
+            old_word.as_deref().unwrap_or("(nothing)"),
+            new_word.as_deref().unwrap_or("(nothing)")
+        );
+    }
+}
+
+/// Print how many words of the list came from each input file (see
+/// `find_word_sources`), in the order of the files
+pub fn display_source_counts(sources: &[Option<String>]) {
+    let mut counts: Vec<(&str, usize)> = vec![];
+    for source in sources {
+        let source = source.as_deref().unwrap_or("(no input file)");
+        match counts.iter_mut().find(|(name, _count)| *name == source) {
+            Some((_name, count)) => *count += 1,
+            None => counts.push((source, 1)),
+        }
+    }
+    eprintln!("Words from each input file:");
+    for (source, count) in counts {
+        eprintln!("  {:<24}: {}", source, count);
+    }
+}
+
+use crate::compare::compare_lists;
+/// Print how the new list compares to a baseline list: how many words they
+/// share, words unique to the new list, and the difference in some key
+/// attributes.
+pub fn display_comparison(
+    list: &[String],
+    baseline_name: &str,
+    baseline: &[String],
+    ignore_ending_metadata_delimiter: Option<char>,
+    ignore_starting_metadata_delimiter: Option<char>,
+) {
+    let (list, _metadata) = split_words_and_metadata(
+        list,
+        ignore_ending_metadata_delimiter,
+        ignore_starting_metadata_delimiter,
+    );
+    let comparison = compare_lists(&list, baseline);
+    eprintln!("\nComparison to {}", baseline_name);
+    eprintln!(
+        "--------------{}",
+        "-".repeat(baseline_name.chars().count())
+    );
+    eprintln!(
+        "Words on both lists       : {} ({:.1}% of new list)",
+        comparison.overlap,
+        comparison.overlap_percent()
+    );
+    eprintln!(
+        "Words only on new list    : {}{}",
+        comparison.unique_to_list.len(),
+        format_examples(&comparison.unique_to_list)
+    );
+    eprintln!(
+        "Words only on {:<12}: {}{}",
+        baseline_name,
+        comparison.unique_to_baseline.len(),
+        format_examples(&comparison.unique_to_baseline)
+    );
+    eprintln!(
+        "Entropy per word          : {:+.3} bits ({:.3} vs {:.3})",
+        calc_entropy_per_word(list.len()) - calc_entropy_per_word(baseline.len()),
+        calc_entropy_per_word(list.len()),
+        calc_entropy_per_word(baseline.len())
+    );
+    eprintln!(
+        "Mean word length          : {:+.2} characters ({:.2} vs {:.2})",
+        mean_word_length(&list) - mean_word_length(baseline),
+        mean_word_length(&list),
+        mean_word_length(baseline)
+    );
+    eprintln!(
+        "Free of prefix words?     : {} vs {}",
+        !has_prefix_words(&list),
+        !has_prefix_words(baseline)
+    );
+}
+
+use crate::compare::find_shared_words;
+use crate::compound_collisions::find_cross_list_collisions;
+/// For passphrases made of a word from the first list followed by a word
+/// from the second, like an adjective and a noun, prints the words on both
+/// lists, every pair that spells the same thing as another pair when typed
+/// without a separator, and the entropy of a pair. Returns whether the lists
+/// passed: no shared words and no such collisions.
+pub fn display_cross_list_report(
+    first_name: &str,
+    first_list: &[String],
+    second_name: &str,
+    second_list: &[String],
+) -> bool {
+    let shared_words = find_shared_words(first_list, second_list);
+    let collisions = find_cross_list_collisions(first_list, second_list);
+    display_heading("Cross-list check");
+    display_attribute(
+        "First list",
+        format!("{} words ({})", first_list.len(), first_name),
+    );
+    display_attribute(
+        "Second list",
+        format!("{} words ({})", second_list.len(), second_name),
+    );
+    if shared_words.is_empty() {
+        display_attribute("Words on both lists", 0);
+    } else {
+        display_highlighted_attribute(
+            "Words on both lists",
+            format!("{}{}", shared_words.len(), format_examples(&shared_words)),
+            Highlight::Failure,
+        );
+    }
+    if collisions.is_empty() {
+        display_attribute("Ambiguous pairs", 0);
+    } else {
+        display_highlighted_attribute("Ambiguous pairs", collisions.len(), Highlight::Failure);
+    }
+    for (first, second) in &collisions {
+        eprintln!(
+            "  \"{}\" is both {} and {}",
+            first.concat(),
+            first.join(" + "),
+            second.join(" + ")
+        );
+    }
+    let first_entropy = calc_entropy_per_word(first_list.len());
+    let second_entropy = calc_entropy_per_word(second_list.len());
+    display_attribute(
+        "Entropy per pair",
+        format!(
+            "{:.3} bits ({:.3} + {:.3})",
+            first_entropy + second_entropy,
+            first_entropy,
+            second_entropy
+        ),
+    );
+    shared_words.is_empty() && collisions.is_empty()
+}
+
+use crate::compare::LinesDiff;
+/// Print how a file would change if the new list were written to it: every
+/// line added and removed, and how many lines would move, with a few
+/// examples, since adding or removing one word near the top of a list moves
+/// every word after it.
+pub fn display_lines_diff(diff: &LinesDiff, file_name: &str) {
+    if diff.is_empty() {
+        eprintln!("No differences from {}", file_name);
+        return;
+    }
+    eprintln!("\nDifferences from {}", file_name);
+    eprintln!("-----------------{}", "-".repeat(file_name.chars().count()));
+    eprintln!("Lines added               : {}", diff.added.len());
+    for (line_number, line) in &diff.added {
+        eprintln!("  + {}: {}", line_number, line);
+    }
+    eprintln!("Lines removed             : {}", diff.removed.len());
+    for (line_number, line) in &diff.removed {
+        eprintln!("  - {}: {}", line_number, line);
+    }
+    eprintln!("Lines moved               : {}", diff.moved.len());
+    for (line, old_line_number, new_line_number) in diff.moved.iter().take(5) {
+        eprintln!("  {}: {} → {}", line, old_line_number, new_line_number);
+    }
+    if diff.moved.len() > 5 {
+        eprintln!("  ...");
+    }
+}
+
+/// Formats up to the first 5 words as examples, like " (e.g. a, b, c)"
+fn format_examples(words: &[String]) -> String {
+    if words.is_empty() {
+        String::new()
+    } else {
+        format!(
+            " (e.g. {})",
+            words
+                .iter()
+                .take(5)
+                .cloned()
+                .collect::<Vec<String>>()
+                .join(", ")
+        )
+    }
+}
@@ -1,66 +1,387 @@
+use crate::checksum::bytes_checksum;
+use crate::compare::{builtin_list, parse_baseline_list, BUILTIN_LISTS};
+use crate::encodings::decode;
+use crate::encodings::InputEncoding;
+use crate::registry::{fetched_list_path_in, find_registry_list, registry_dir, RegistryList};
 use crate::split_and_vectorize;
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::io::BufRead;
 use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
 use std::path::PathBuf;
 /// Takes a slice of `PathBuf`s representing the word list(s)
 /// that the user has inputted to the program. Then iterates
 /// through each file and addes each line to Vec<String>. (Blank
 /// lines and duplicate links will be handled elsewhere.)
+///
+/// If `auto_strip_pgp` is true, PGP clearsign armor is detected and
+/// removed from each file. No rows are skipped from files that had armor,
+/// since the rows to skip were most likely the armor. If given a
+/// `comment_prefix`, comment lines and trailing comments are removed
+/// after rows are skipped.
+///
+/// If given an `input_encoding`, files are decoded from that encoding
+/// (see `read_lines_from_file`).
 pub fn make_vec_from_filenames(
     filenames: &[PathBuf],
     skip_rows_start: Option<usize>,
     skip_rows_end: Option<usize>,
+    auto_strip_pgp: bool,
+    comment_prefix: Option<&str>,
+    input_encoding: Option<InputEncoding>,
+    strict_encoding: bool,
 ) -> Vec<String> {
     let mut word_list: Vec<String> = [].to_vec();
     for filename in filenames {
-        let f = match File::open(filename) {
-            Ok(file) => file,
-            Err(e) => panic!("Error opening file {:?}: {}", filename, e),
-        };
-        let file = BufReader::new(&f);
-        let mut raw_lines = vec![];
-        for line in file.lines() {
-            let l = match line {
-                Ok(l) => l,
-                Err(e) => {
-                    eprintln!(
-                        "Error reading a line from file {:?}: {}\nWill continue reading file.",
-                        filename, e
-                    );
-                    continue;
-                }
-            };
-            raw_lines.push(l);
+        let mut raw_lines = read_lines_from_file(filename, input_encoding, strict_encoding);
+        let (mut skip_rows_start, mut skip_rows_end) = (skip_rows_start, skip_rows_end);
+        if auto_strip_pgp {
+            let number_of_lines_with_armor = raw_lines.len();
+            raw_lines = strip_pgp_armor(raw_lines);
+            let stripped_armor = raw_lines.len() < number_of_lines_with_armor;
+            if stripped_armor && (skip_rows_start.is_some() || skip_rows_end.is_some()) {
+                eprintln!(
+                    "WARNING: Removed PGP armor from {}, so not skipping any rows of it. To skip rows instead, use --no-auto-pgp.\n",
+                    filename.display()
+                );
+                skip_rows_start = None;
+                skip_rows_end = None;
+            }
         }
         let number_of_lines_in_file = raw_lines.len();
 
+        let mut lines_of_file = vec![];
         for (line_number, line) in raw_lines.into_iter().enumerate() {
             match (skip_rows_start, skip_rows_end) {
                 (Some(skip_rows_start), Some(skip_rows_end)) => {
                     if line_number >= skip_rows_start
                         && line_number < number_of_lines_in_file - skip_rows_end
                     {
-                        word_list.push(line);
+                        lines_of_file.push(line);
                     }
                 }
                 (Some(skip_rows_start), None) => {
                     if line_number >= skip_rows_start {
-                        word_list.push(line);
+                        lines_of_file.push(line);
                     }
                 }
                 (None, Some(skip_rows_end)) => {
                     if line_number < number_of_lines_in_file - skip_rows_end {
-                        word_list.push(line);
+                        lines_of_file.push(line);
                     }
                 }
-                (None, None) => word_list.push(line),
+                (None, None) => lines_of_file.push(line),
             }
         }
+        if let Some(comment_prefix) = comment_prefix {
+            lines_of_file = strip_comments(lines_of_file, comment_prefix);
+        }
+        word_list.append(&mut lines_of_file);
     }
     word_list
 }
 
+/// Opens an inputted word list for reading, downloading it if it's a URL.
+/// Panics if the file can't be opened.
+fn open_input(filename: &Path) -> Box<dyn Read> {
+    if is_url(filename) {
+        match fetch_url(&filename.to_string_lossy()) {
+            Ok(reader) => reader,
+            Err(e) => panic!("Error downloading {:?}: {}", filename, e),
+        }
+    } else if let Some((list, fetched_path)) = find_fetched_list(filename, &registry_dir()) {
+        match File::open(fetched_path) {
+            Ok(file) => Box::new(file),
+            Err(_e) => panic!(
+                "{} is a list Tidy can download, but it hasn't been fetched yet. Run `tidy fetch {}` first.",
+                list.name, list.name
+            ),
+        }
+    } else {
+        match File::open(filename) {
+            Ok(file) => Box::new(file),
+            Err(e) => panic!("Error opening file {:?}: {}", filename, e),
+        }
+    }
+}
+
+/// If there's no file at the given path, and the path is the name of a list
+/// Tidy can fetch (see `registry`), gets that list and where it's kept once
+/// fetched into `list_dir`
+pub fn find_fetched_list(path: &Path, list_dir: &Path) -> Option<(&'static RegistryList, PathBuf)> {
+    if path.exists() {
+        None
+    } else {
+        find_registry_list(&path.to_string_lossy())
+            .map(|list| (list, fetched_list_path_in(list_dir, list.name)))
+    }
+}
+
+/// Checks if an inputted word list is a remote URL rather than a local file.
+/// ```
+/// use std::path::Path;
+/// use tidy::file_readers::is_url;
+/// assert!(is_url(Path::new("https://www.eff.org/files/2016/07/18/eff_large_wordlist.txt")));
+/// assert!(!is_url(Path::new("word_lists/example.txt")));
+/// ```
+pub fn is_url(path: &Path) -> bool {
+    let path = path.to_string_lossy();
+    path.starts_with("https://") || path.starts_with("http://")
+}
+
+/// Starts downloading the given URL, returning a reader that streams the
+/// body of the response.
+#[cfg(feature = "net")]
+pub fn fetch_url(url: &str) -> Result<Box<dyn Read>, String> {
+    match ureq::get(url).call() {
+        Ok(response) => Ok(Box::new(response.into_reader())),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(not(feature = "net"))]
+pub fn fetch_url(_url: &str) -> Result<Box<dyn Read>, String> {
+    Err("This copy of Tidy was built without the net feature, so it can't read word lists from URLs. Reinstall with `cargo install --features net`.".to_string())
+}
+
+/// Reads all lines of a file, or of the response from a URL.
+///
+/// Without an `input_encoding`, the file is read as UTF-8 and any lines
+/// that aren't valid UTF-8 are skipped (with a warning). With one, the whole
+/// file is decoded to UTF-8 first. In that case, if `strict_encoding` is true,
+/// any invalid bytes cause a panic; otherwise they become replacement
+/// characters (�).
+pub fn read_lines_from_file(
+    filename: &Path,
+    input_encoding: Option<InputEncoding>,
+    strict_encoding: bool,
+) -> Vec<String> {
+    match input_encoding {
+        Some(input_encoding) => {
+            let mut bytes = vec![];
+            if let Err(e) = open_input(filename).read_to_end(&mut bytes) {
+                panic!("Error reading file {:?}: {}", filename, e)
+            }
+            match decode(&bytes, input_encoding, strict_encoding) {
+                Ok(text) => text.lines().map(|l| l.to_string()).collect(),
+                Err(e) => panic!(
+                    "Error decoding file {:?} as {}: {}",
+                    filename, input_encoding, e
+                ),
+            }
+        }
+        None => {
+            let file = BufReader::new(open_input(filename));
+            let mut raw_lines = vec![];
+            for line in file.lines() {
+                let l = match line {
+                    Ok(l) => l,
+                    Err(e) => {
+                        eprintln!(
+                            "Error reading a line from file {:?}: {}\nWill continue reading file.",
+                            filename, e
+                        );
+                        continue;
+                    }
+                };
+                raw_lines.push(l);
+            }
+            raw_lines
+        }
+    }
+}
+
+/// Removes comments from the given lines. Lines that begin with
+/// `comment_prefix` (ignoring leading whitespace) are removed entirely.
+/// Trailing comments are cut from other lines, as long as the prefix is
+/// preceded by whitespace, so that words like "C#" survive.
+/// ```
+/// use tidy::file_readers::strip_comments;
+/// let lines: Vec<String> = vec!["# Header", "abbey", "C#", "zoo # a note"]
+///     .iter()
+///     .map(|l| l.to_string())
+///     .collect();
+/// assert_eq!(strip_comments(lines, "#"), vec!["abbey", "C#", "zoo"]);
+/// ```
+pub fn strip_comments(lines: Vec<String>, comment_prefix: &str) -> Vec<String> {
+    lines
+        .into_iter()
+        .filter(|line| !line.trim_start().starts_with(comment_prefix))
+        .map(|line| {
+            let trailing_comment = line
+                .match_indices(comment_prefix)
+                .find(|(i, _)| line[..*i].chars().last().is_some_and(|c| c.is_whitespace()));
+            match trailing_comment {
+                Some((i, _)) => line[..i].trim_end().to_string(),
+                None => line,
+            }
+        })
+        .collect()
+}
+
+/// Finds the comment lines at the top of a file's lines, so they can be
+/// carried into the outputted list. Blank lines and PGP clearsign headers are
+/// skipped over; the first other line ends the header comments.
+/// ```
+/// use tidy::file_readers::find_header_comments;
+/// let lines: Vec<String> = ["# Word list v2", "", "# by me", "abacus", "# not a header"]
+///     .iter()
+///     .map(|l| l.to_string())
+///     .collect();
+/// assert_eq!(find_header_comments(&lines, "#"), vec!["# Word list v2", "# by me"]);
+/// ```
+pub fn find_header_comments(lines: &[String], comment_prefix: &str) -> Vec<String> {
+    let mut header_comments = vec![];
+    for line in lines {
+        let trimmed_line = line.trim();
+        if trimmed_line.starts_with(comment_prefix) {
+            header_comments.push(trimmed_line.to_string());
+        } else if !(trimmed_line.is_empty()
+            || trimmed_line == "-----BEGIN PGP SIGNED MESSAGE-----"
+            || trimmed_line.starts_with("Hash:"))
+        {
+            break;
+        }
+    }
+    header_comments
+}
+
+/// If the given lines are a PGP clearsigned message, returns only the
+/// signed text: the armor header (and its "Hash:" lines), the signature
+/// block, and any dash-escaping of lines are all removed. Lines that are
+/// not a clearsigned message are returned unchanged.
+/// ```
+/// use tidy::file_readers::strip_pgp_armor;
+/// let lines: Vec<String> = vec![
+///     "-----BEGIN PGP SIGNED MESSAGE-----",
+///     "Hash: SHA256",
+///     "",
+///     "abbey",
+///     "- -dashed",
+///     "-----BEGIN PGP SIGNATURE-----",
+///     "iQIzBAEBCAAdFiEE",
+///     "-----END PGP SIGNATURE-----",
+/// ]
+/// .iter()
+/// .map(|l| l.to_string())
+/// .collect();
+/// assert_eq!(strip_pgp_armor(lines), vec!["abbey", "-dashed"]);
+/// ```
+pub fn strip_pgp_armor(lines: Vec<String>) -> Vec<String> {
+    if !lines
+        .iter()
+        .any(|l| l.trim() == "-----BEGIN PGP SIGNED MESSAGE-----")
+    {
+        return lines;
+    }
+    let borrowed_lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    strip_pgp_armor_from_slices(&borrowed_lines)
+        .into_iter()
+        .map(|l| l.to_string())
+        .collect()
+}
+
+/// Like `strip_pgp_armor`, but for lines borrowed from a larger text, like
+/// a memory-mapped file. The returned lines borrow from the same text.
+pub fn strip_pgp_armor_from_slices<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let begin_message = match lines
+        .iter()
+        .position(|l| l.trim() == "-----BEGIN PGP SIGNED MESSAGE-----")
+    {
+        Some(begin_message) => begin_message,
+        None => return lines.to_vec(),
+    };
+    // Armor headers (like "Hash: SHA256") run until the first blank line
+    let begin_text = match lines[begin_message..]
+        .iter()
+        .position(|l| l.trim().is_empty())
+    {
+        Some(blank_line) => begin_message + blank_line + 1,
+        None => return lines.to_vec(),
+    };
+    let end_text = lines[begin_text..]
+        .iter()
+        .position(|l| l.trim() == "-----BEGIN PGP SIGNATURE-----")
+        .map_or(lines.len(), |signature| begin_text + signature);
+    lines[begin_text..end_text]
+        .iter()
+        // Undo dash-escaping
+        .map(|l| l.strip_prefix("- ").unwrap_or(l))
+        .collect()
+}
+
+/// The bytes of a file mapped into memory (see `map_file`)
+#[cfg(feature = "mmap")]
+type MappedBytes = memmap2::Mmap;
+#[cfg(not(feature = "mmap"))]
+type MappedBytes = Vec<u8>;
+
+/// An inputted word list mapped into memory, so that its lines can be
+/// tidied without copying each of them into a `String` first. Useful for
+/// corpora with tens of millions of lines.
+pub struct MappedFile {
+    bytes: MappedBytes,
+}
+
+impl MappedFile {
+    /// Borrows each line of the file, without line endings. Like
+    /// `read_lines_from_file`, lines that aren't valid UTF-8 are skipped,
+    /// with a warning.
+    pub fn lines(&self, filename: &Path) -> Vec<&str> {
+        let mut bytes: &[u8] = &self.bytes;
+        // Like `BufRead::lines`, don't count a final newline as the start
+        // of another (blank) line
+        if let Some(without_final_newline) = bytes.strip_suffix(b"\n") {
+            bytes = without_final_newline;
+        }
+        if bytes.is_empty() {
+            return vec![];
+        }
+        let mut lines = vec![];
+        for (line_number, line) in bytes.split(|b| *b == b'\n').enumerate() {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            match std::str::from_utf8(line) {
+                Ok(l) => lines.push(l),
+                Err(e) => eprintln!(
+                    "Error reading line {} from file {:?}: {}\nWill continue reading file.",
+                    line_number + 1,
+                    filename,
+                    e
+                ),
+            }
+        }
+        lines
+    }
+}
+
+/// Maps an inputted word list into memory. Only local files can be mapped,
+/// not URLs.
+#[cfg(feature = "mmap")]
+pub fn map_file(filename: &Path) -> Result<MappedFile, String> {
+    if is_url(filename) {
+        return Err(format!(
+            "Error: Can't memory-map {:?}, since it's a URL",
+            filename
+        ));
+    }
+    let file =
+        File::open(filename).map_err(|e| format!("Error opening file {:?}: {}", filename, e))?;
+    // Safety: Tidy never writes to the mapped file, but another program
+    // could. Like other tools that map files, Tidy assumes inputted files
+    // aren't changed while it's running.
+    let bytes = unsafe { memmap2::Mmap::map(&file) }
+        .map_err(|e| format!("Error memory-mapping file {:?}: {}", filename, e))?;
+    Ok(MappedFile { bytes })
+}
+
+#[cfg(not(feature = "mmap"))]
+pub fn map_file(_filename: &Path) -> Result<MappedFile, String> {
+    Err("Error: This copy of Tidy was built without the mmap feature, so it can't memory-map inputted files. Reinstall with `cargo install --features mmap`.".to_string())
+}
+
 /// Like `make_vec_from_filenames`, this function takes a slice of `PathBuf`s of
 /// files. But in this case these files represent lists of homophones that the
 /// user wants to make sure aren't both on the resulting list.
@@ -98,3 +419,182 @@ pub fn read_homophones_list_from_filenames(filenames: &[PathBuf]) -> Vec<(String
     }
     homophones_list
 }
+
+/// Expands the input paths the user gave into a list of files to read.
+/// Directories are read recursively, skipping hidden files. Paths that don't
+/// exist but contain a glob wildcard (`*`, `?`, or `**` for any number of
+/// directories) are matched against the file system. Both are sorted by path
+/// so that the order of the resulting list doesn't depend on the file system.
+///
+/// Errors if a directory or glob pattern matches no files.
+pub fn expand_input_paths(paths: &[PathBuf]) -> Result<Vec<PathBuf>, String> {
+    let mut files = vec![];
+    for path in paths {
+        if path.is_dir() {
+            let mut found = vec![];
+            walk_directory(path, &mut found)?;
+            if found.is_empty() {
+                return Err(format!("Error: Given directory {:?} has no files", path));
+            }
+            found.sort();
+            files.append(&mut found);
+        } else if is_url(path) {
+            files.push(path.to_path_buf());
+        } else if !path.exists() && is_glob_pattern(path) {
+            let mut found = find_glob_matches(path)?;
+            if found.is_empty() {
+                return Err(format!("Error: No files match pattern {:?}", path));
+            }
+            found.sort();
+            files.append(&mut found);
+        } else {
+            files.push(path.to_path_buf());
+        }
+    }
+    Ok(files)
+}
+
+fn is_glob_pattern(path: &Path) -> bool {
+    path.to_string_lossy().contains(['*', '?'])
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+}
+
+/// Adds every non-hidden file within `directory`, and within its
+/// sub-directories, to `found`.
+fn walk_directory(directory: &Path, found: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(directory)
+        .map_err(|e| format!("Error: Unable to read directory {:?}: {}", directory, e))?;
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("Error: Unable to read directory {:?}: {}", directory, e))?
+            .path();
+        if is_hidden(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            walk_directory(&path, found)?;
+        } else {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Finds every file matching a glob pattern, starting the search from the
+/// longest leading part of the pattern that has no wildcards.
+fn find_glob_matches(pattern: &Path) -> Result<Vec<PathBuf>, String> {
+    let components: Vec<String> = pattern
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    let first_wildcard = components
+        .iter()
+        .position(|c| c.contains(['*', '?']))
+        .unwrap_or(components.len());
+    let base: PathBuf = if first_wildcard == 0 {
+        PathBuf::from(".")
+    } else {
+        components[..first_wildcard].iter().collect()
+    };
+    let mut candidates = vec![];
+    if base.is_dir() {
+        walk_directory(&base, &mut candidates)?;
+    }
+    let pattern_components = &components[first_wildcard..];
+    Ok(candidates
+        .into_iter()
+        .filter(|candidate| {
+            let relative: Vec<String> = candidate
+                .strip_prefix(&base)
+                .unwrap_or(candidate)
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().to_string())
+                .collect();
+            glob_components_match(pattern_components, &relative)
+        })
+        .collect())
+}
+
+/// Matches path components against glob pattern components, where a `**`
+/// component matches any number of directories.
+fn glob_components_match(pattern: &[String], path: &[String]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some(p) if p == "**" => {
+            (0..=path.len()).any(|skip| glob_components_match(&pattern[1..], &path[skip..]))
+        }
+        Some(p) => {
+            !path.is_empty()
+                && glob_matches(p, &path[0])
+                && glob_components_match(&pattern[1..], &path[1..])
+        }
+    }
+}
+
+/// Checks if a file name matches a single glob pattern component, where
+/// `*` matches any run of characters and `?` matches exactly one.
+/// ```
+/// use tidy::file_readers::glob_matches;
+/// assert!(glob_matches("chapter*.txt", "chapter12.txt"));
+/// assert!(glob_matches("ch?.txt", "ch1.txt"));
+/// assert!(!glob_matches("*.txt", "notes.md"));
+/// ```
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    // Position to return to in pattern and name when a `*` needs to match
+    // more characters
+    let mut backtrack: Option<(usize, usize)> = None;
+    let (mut p, mut n) = (0, 0);
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Calculates the SHA-256 hash of the raw contents of a file.
+pub fn file_checksum(path: &Path) -> io::Result<String> {
+    Ok(bytes_checksum(&fs::read(path)?))
+}
+
+/// Gets a list to compare against: either the name of one of the
+/// `compare::BUILTIN_LISTS` or a path to a file with one word per line.
+/// ```
+/// use tidy::file_readers::load_baseline_list;
+/// assert_eq!(load_baseline_list("eff-long").unwrap().len(), 7776);
+/// assert!(load_baseline_list("no-such-list.txt").is_err());
+/// ```
+pub fn load_baseline_list(name_or_path: &str) -> Result<Vec<String>, String> {
+    if let Some(list) = builtin_list(name_or_path) {
+        return Ok(list);
+    }
+    match fs::read_to_string(name_or_path) {
+        Ok(contents) => Ok(parse_baseline_list(&contents)),
+        Err(e) => Err(format!(
+            "Error: {} is neither a built-in list ({}) nor a readable file: {}",
+            name_or_path,
+            BUILTIN_LISTS
+                .iter()
+                .map(|(name, _)| *name)
+                .collect::<Vec<&str>>()
+                .join(", "),
+            e
+        )),
+    }
+}
@@ -1,132 +1,914 @@
 use crate::cards::print_as_cards;
-use crate::dice::print_as_dice;
+use crate::checksum::list_checksum;
+use crate::coins::print_as_coins;
+use crate::compare::diff_lines;
+use crate::dice::check_dice_sides;
+use crate::dice::format_dice_roll;
+use crate::dice::DiceFormat;
+use crate::display_information::display_bip39_report;
+use crate::display_information::display_comparison;
+use crate::display_information::display_compound_collisions_report;
+use crate::display_information::display_decodability_report;
+use crate::display_information::display_inflections_report;
+use crate::display_information::display_length_advice;
+use crate::display_information::display_lines_diff;
 use crate::display_information::display_list_information;
+use crate::display_information::display_previous_list_changes;
+use crate::display_information::display_sample_strengths;
+use crate::display_information::display_skew_warnings;
+use crate::display_information::display_source_counts;
+use crate::display_information::display_unreachable_rolls;
 use crate::display_information::generate_samples;
+use crate::display_information::parse_frequencies;
+use crate::display_information::split_words_and_metadata;
+use crate::encodings::encode;
+use crate::encodings::OutputEncoding;
+use crate::html_writer::render_html;
+use crate::json_writer::render_json;
+use crate::list_manipulations::ListChange;
+use std::fs;
 use std::fs::File;
+use std::io;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 #[derive(Default, Debug, Clone)]
 pub struct PrintRequest {
     pub tidied_list: Vec<String>,
     pub dry_run: bool,
+    /// Instead of writing the new list to the output file, print how the
+    /// file would change
+    pub diff: bool,
     pub quiet: bool,
-    pub output: Option<PathBuf>,
+    /// Files to write the list to, each in the format its extension calls
+    /// for (see `infer_output_format`). If there are none, the list is
+    /// printed to stdout.
+    pub outputs: Vec<PathBuf>,
     pub dice_sides: Option<u8>,
     pub cards: bool,
+    pub coins: bool,
     pub print_dice_sides_as_their_base: bool,
+    pub dice_separator: Option<String>,
+    pub dice_format: DiceFormat,
+    pub print_columns: Option<usize>,
+    pub annotations: Vec<Annotation>,
+    pub format: OutputFormat,
+    pub title: Option<String>,
+    pub page_height: Option<usize>,
+    pub reroll_table: bool,
     pub attributes: u8,
+    /// How fast an attacker is assumed to guess passphrases, for the
+    /// time-to-crack estimates in the attributes
+    pub guesses_per_second: f64,
     pub samples: bool,
+    pub sample_options: SampleOptions,
+    pub bip39: bool,
+    pub check_decodable: bool,
+    pub find_inflections: bool,
+    pub find_compound_collisions: bool,
+    pub previous_list_changes: Vec<ListChange>,
+    /// The input file each word came from, if the user asked to tag words
+    /// with their source
+    pub sources: Option<Vec<Option<String>>>,
+    pub warn_skew: Option<f64>,
+    /// Whether to point out that the list is just short of a clean power,
+    /// like 7,776 words
+    pub length_advice: bool,
+    pub separator: String,
+    pub compare_to: Option<(String, Vec<String>)>,
+    pub checksum: bool,
+    pub checksum_header: bool,
+    pub preserved_comments: Vec<String>,
+    /// Lines describing how the list was made, shown on HTML pages and, if
+    /// `text_metadata_header` is set, as comments above a text list
+    pub metadata_header: Option<Vec<String>>,
+    pub text_metadata_header: bool,
+    pub line_ending: LineEnding,
+    pub output_encoding: OutputEncoding,
     pub ignore_before_delimiter: Option<char>,
     pub ignore_after_delimiter: Option<char>,
+    /// File to write a companion of the list to, for study sheets (see
+    /// `make_companion_lines`)
+    pub companion: Option<PathBuf>,
+    /// Template to fill in each word's memorization hint in the companion
+    /// from (see `fill_hint_template`). If `None`, hints are left blank.
+    pub companion_hint: Option<String>,
 }
 
-/// Print to terminal or file
-pub fn print_list(print_req: PrintRequest) {
+/// Print to terminal or file. Returns an error message if the list can't be
+/// printed.
+pub fn print_list(print_req: PrintRequest) -> Result<(), String> {
+    // Every word gets a dice roll, so check the dice before printing any
+    if let Some(dice_sides) = print_req.dice_sides {
+        check_dice_sides(dice_sides).map_err(|e| e.to_string())?;
+    }
     if !print_req.quiet {
         if print_req.tidied_list.is_empty() {
             eprintln!(
                 "WARNING: All words removed (tidied list is empty). Check inputted list and given options."
             );
-        } else if !print_req.dry_run {
+        } else if !print_req.dry_run && !print_req.diff {
             eprintln!("Printing new list...");
         }
     }
+    // Each output file gets the format its extension calls for, if any
+    let outputs: Vec<(Option<&PathBuf>, OutputFormat)> = if print_req.outputs.is_empty() {
+        vec![(None, print_req.format)]
+    } else {
+        print_req
+            .outputs
+            .iter()
+            .map(|output| (Some(output), infer_output_format(output, print_req.format)))
+            .collect()
+    };
+    // A metadata header always ends with the list's checksum
+    let checksum_header = print_req.checksum_header || print_req.text_metadata_header;
+    // Only calculate the checksum once, as it may be used more than once
+    let checksum = if print_req.checksum
+        || checksum_header
+        || outputs
+            .iter()
+            .any(|(_output, format)| *format != OutputFormat::Text)
+    {
+        Some(list_checksum(&print_req.tidied_list))
+    } else {
+        None
+    };
     if !print_req.dry_run {
-        match print_req.output {
-            Some(output) => {
-                // Print to file
-                print_list_to_file(
-                    &print_req.tidied_list,
-                    output,
-                    print_req.cards,
-                    print_req.dice_sides,
-                    print_req.print_dice_sides_as_their_base,
-                );
-            }
-            // If no output file destination, print resulting list, word by word,
-            // to println (which goes to stdout, allowing use of > on command line)
-            None => {
-                for (i, word) in print_req.tidied_list.iter().enumerate() {
-                    if let Some(dice_sides) = print_req.dice_sides {
-                        print!(
-                            "{:}\t",
-                            print_as_dice(
-                                i,
-                                dice_sides,
-                                print_req.tidied_list.len(),
-                                print_req.print_dice_sides_as_their_base
-                            )
-                        );
-                    } else if print_req.cards {
-                        print!("{:}\t", print_as_cards(i, print_req.tidied_list.len()));
-                    }
-                    println!("{}", word);
+        for (output, format) in outputs {
+            let lines = render_list(&print_req, format, checksum_header, checksum.as_deref());
+            match output {
+                Some(output) if print_req.diff => {
+                    let contents = fs::read_to_string(output).map_err(|e| {
+                        format!(
+                            "Error: Unable to read {} to compare the new list to: {}",
+                            output.display(),
+                            e
+                        )
+                    })?;
+                    let old_lines: Vec<String> =
+                        contents.lines().map(|line| line.to_string()).collect();
+                    display_lines_diff(
+                        &diff_lines(&old_lines, &lines),
+                        &output.display().to_string(),
+                    );
                 }
+                _ => write_lines(&print_req, &lines, output)?,
             }
         }
+        // Diffing writes nothing, not even the companion
+        if let Some(companion) = print_req.companion.as_ref().filter(|_| !print_req.diff) {
+            write_lines(
+                &print_req,
+                &make_companion_lines(&print_req),
+                Some(companion),
+            )?;
+        }
+    }
+    // User explicitly asked for the checksum, so print it even if quiet
+    if print_req.checksum {
+        eprintln!("SHA-256 of new list: {}", checksum.unwrap());
+    }
+    // Likewise, user explicitly asked for this report
+    if print_req.check_decodable {
+        display_decodability_report(&print_req.tidied_list);
+    }
+    if print_req.find_inflections {
+        display_inflections_report(&print_req.tidied_list);
+    }
+    if print_req.find_compound_collisions {
+        display_compound_collisions_report(&print_req.tidied_list);
+    }
+    if !print_req.previous_list_changes.is_empty() {
+        display_previous_list_changes(&print_req.previous_list_changes, &|position| {
+            make_index_code(&print_req, position).unwrap_or_else(|| (position + 1).to_string())
+        });
+    }
+    if let Some((ref baseline_name, ref baseline)) = print_req.compare_to {
+        display_comparison(
+            &print_req.tidied_list,
+            baseline_name,
+            baseline,
+            print_req.ignore_before_delimiter,
+            print_req.ignore_after_delimiter,
+        );
+    }
+    if let Some(maximum_percent) = print_req.warn_skew {
+        display_skew_warnings(
+            &print_req.tidied_list,
+            maximum_percent,
+            print_req.ignore_before_delimiter,
+            print_req.ignore_after_delimiter,
+        );
     }
     if !print_req.quiet {
-        if !print_req.dry_run && !print_req.tidied_list.is_empty() {
+        if !print_req.dry_run && !print_req.diff && !print_req.tidied_list.is_empty() {
             eprintln!("\nDone making list.");
         } else if print_req.dry_run {
             eprintln!("Dry run complete");
         }
+        if print_req.length_advice {
+            display_length_advice(print_req.tidied_list.len());
+        }
+        if let Some(dice_sides) = print_req.dice_sides {
+            display_unreachable_rolls(
+                print_req.tidied_list.len(),
+                dice_sides,
+                print_req.print_dice_sides_as_their_base,
+                &print_req.dice_format,
+                print_req.reroll_table,
+            );
+        }
         if print_req.attributes > 0 {
             display_list_information(
                 &print_req.tidied_list,
                 print_req.attributes,
-                print_req.ignore_after_delimiter,
                 print_req.ignore_before_delimiter,
-            );
-        }
-        if print_req.samples {
-            let samples = generate_samples(
-                &print_req.tidied_list,
                 print_req.ignore_after_delimiter,
-                print_req.ignore_before_delimiter,
+                &print_req.separator,
+                print_req.guesses_per_second,
             );
+            if let Some(ref sources) = print_req.sources {
+                display_source_counts(sources);
+            }
+        }
+        if print_req.bip39 {
+            display_bip39_report(&print_req.tidied_list);
+        }
+    }
+    // Samples written to a file or stdout were asked for explicitly, so
+    // are printed even when quiet
+    if print_req.samples && (!print_req.quiet || print_req.sample_options.output.is_some()) {
+        print_samples(
+            &print_req.tidied_list,
+            print_req.ignore_before_delimiter,
+            print_req.ignore_after_delimiter,
+            &print_req.sample_options,
+            print_req.guesses_per_second,
+        );
+    }
+    Ok(())
+}
+
+/// How the words of sample passphrases are capitalized
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SampleCase {
+    /// As they are on the list
+    #[default]
+    Unchanged,
+    Lowercase,
+    Uppercase,
+    /// With the first letter of each word uppercase
+    Capitalized,
+}
+
+impl FromStr for SampleCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "unchanged" => Ok(SampleCase::Unchanged),
+            "lowercase" => Ok(SampleCase::Lowercase),
+            "uppercase" => Ok(SampleCase::Uppercase),
+            "capitalized" => Ok(SampleCase::Capitalized),
+            _ => Err(format!(
+                "Unknown sample case {}. Use unchanged, lowercase, uppercase, or capitalized.",
+                s
+            )),
+        }
+    }
+}
+
+/// How many sample passphrases to make, what they look like, and where to
+/// print them
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleOptions {
+    pub passphrases: usize,
+    pub words_per_passphrase: usize,
+    pub separator: String,
+    pub case: SampleCase,
+    /// File to write the passphrases to, or "-" for stdout. If `None`, they
+    /// are printed to the terminal (stderr) under a heading.
+    pub output: Option<PathBuf>,
+    /// Also print zxcvbn's estimate of how strong each passphrase is (needs
+    /// Tidy built with the `strength` feature)
+    pub estimate_strength: bool,
+}
+
+impl Default for SampleOptions {
+    fn default() -> Self {
+        SampleOptions {
+            passphrases: 5,
+            words_per_passphrase: 6,
+            separator: " ".to_string(),
+            case: SampleCase::Unchanged,
+            output: None,
+            estimate_strength: false,
+        }
+    }
+}
+
+/// Capitalizes a word of a sample passphrase
+/// ```
+/// use tidy::file_writer::{change_sample_case, SampleCase};
+/// assert_eq!(change_sample_case("éclair", SampleCase::Capitalized), "Éclair");
+/// assert_eq!(change_sample_case("Apple", SampleCase::Lowercase), "apple");
+/// ```
+pub fn change_sample_case(word: &str, case: SampleCase) -> String {
+    match case {
+        SampleCase::Unchanged => word.to_string(),
+        SampleCase::Lowercase => word.to_lowercase(),
+        SampleCase::Uppercase => word.to_uppercase(),
+        SampleCase::Capitalized => {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        }
+    }
+}
+
+/// Makes sample passphrases from pseudorandomly picked words of the list,
+/// as the options ask
+/// ```
+/// use tidy::file_writer::{make_sample_passphrases, SampleCase, SampleOptions};
+/// let list = vec!["apple".to_string()];
+/// let options = SampleOptions {
+///     passphrases: 2,
+///     words_per_passphrase: 3,
+///     separator: "-".to_string(),
+///     case: SampleCase::Capitalized,
+///     output: None,
+///     estimate_strength: false,
+/// };
+/// assert_eq!(
+///     make_sample_passphrases(&list, None, None, &options),
+///     vec!["Apple-Apple-Apple", "Apple-Apple-Apple"]
+/// );
+/// ```
+pub fn make_sample_passphrases(
+    list: &[String],
+    ignore_before_delimiter: Option<char>,
+    ignore_after_delimiter: Option<char>,
+    options: &SampleOptions,
+) -> Vec<String> {
+    let words = generate_samples(
+        list,
+        ignore_before_delimiter,
+        ignore_after_delimiter,
+        options.passphrases * options.words_per_passphrase,
+    );
+    words
+        .chunks(options.words_per_passphrase.max(1))
+        .map(|passphrase| {
+            passphrase
+                .iter()
+                .map(|word| change_sample_case(word, options.case))
+                .collect::<Vec<String>>()
+                .join(&options.separator)
+        })
+        .collect()
+}
+
+/// Prints sample passphrases, one to a line, to the terminal, stdout, or a
+/// file, as the options ask. Strength estimates, if asked for, always go to
+/// the terminal, and assume an attacker making `guesses_per_second` guesses.
+pub fn print_samples(
+    list: &[String],
+    ignore_before_delimiter: Option<char>,
+    ignore_after_delimiter: Option<char>,
+    options: &SampleOptions,
+    guesses_per_second: f64,
+) {
+    let passphrases = make_sample_passphrases(
+        list,
+        ignore_before_delimiter,
+        ignore_after_delimiter,
+        options,
+    );
+    match options.output {
+        None => {
             eprintln!("\nPseudorandomly generated sample passphrases");
             eprintln!("-------------------------------------------");
-            for n in 0..30 {
-                if n != 0 && n % 6 == 0 {
-                    eprintln!();
+            for passphrase in &passphrases {
+                eprintln!("{}", passphrase);
+            }
+        }
+        Some(ref output) => {
+            let text: String = passphrases
+                .iter()
+                .map(|passphrase| format!("{}\n", passphrase))
+                .collect();
+            if output.as_os_str() == "-" {
+                io::stdout()
+                    .lock()
+                    .write_all(text.as_bytes())
+                    .expect("Unable to print samples");
+            } else {
+                let mut f = File::create(output).expect("Unable to create samples file");
+                f.write_all(text.as_bytes())
+                    .expect("Unable to write samples to file");
+            }
+        }
+    }
+    if options.estimate_strength {
+        display_sample_strengths(
+            &passphrases,
+            list.len(),
+            options.words_per_passphrase,
+            guesses_per_second,
+        );
+    }
+}
+
+/// Makes the lines of the list to print in the given format, starting with
+/// any header of comment lines for text.
+fn render_list(
+    print_req: &PrintRequest,
+    format: OutputFormat,
+    checksum_header: bool,
+    checksum: Option<&str>,
+) -> Vec<String> {
+    match format {
+        OutputFormat::Text => {
+            let mut lines = print_req.preserved_comments.to_vec();
+            if print_req.text_metadata_header {
+                for line in print_req.metadata_header.iter().flatten() {
+                    lines.push(format!("# {}", line));
                 }
-                eprint!("{} ", samples[n]);
             }
-            eprintln!();
+            if checksum_header {
+                lines.push(format!("# SHA-256: {}", checksum.unwrap()));
+            }
+            let mut list_lines = make_lines_of_list(print_req);
+            if print_req.print_columns.is_some() || print_req.page_height.is_some() {
+                list_lines = arrange_in_columns(
+                    &list_lines,
+                    print_req.print_columns.unwrap_or(1),
+                    print_req.page_height,
+                );
+            }
+            lines.append(&mut list_lines);
+            lines
         }
+        // The HTML page shows the metadata header and checksum itself
+        OutputFormat::Html => render_html(print_req, checksum.unwrap()),
+        OutputFormat::Json => vec![render_json(print_req, checksum.unwrap())],
     }
 }
 
-fn print_list_to_file(
-    tidied_list: &[String],
-    output: PathBuf,
-    cards: bool,
-    dice_sides: Option<u8>,
-    print_dice_sides_as_their_base: bool,
-) {
-    let mut f = File::create(output).expect("Unable to create file");
-    for (i, word) in tidied_list.iter().enumerate() {
-        // If user set a number of dice_sides, we'll add the appropriate
-        // dice roll information, then a tab, then the word.
-        if let Some(dice_sides) = dice_sides {
-            write!(
-                f,
-                "{}\t",
-                print_as_dice(
-                    i,
-                    dice_sides,
-                    tidied_list.len(),
-                    print_dice_sides_as_their_base
-                ),
-            )
-            .expect("Unable to write dice roll to file");
-        } else if cards {
-            write!(f, "{}\t", print_as_cards(i, tidied_list.len()))
-                .expect("Unable to write corresponding card to file");
-        }
-
-        writeln!(f, "{}", word).expect("Unable to write word to file");
+/// Writes lines of output to the given file, or to stdout if there isn't one.
+/// Returns an error message if the lines can't be encoded in the output
+/// encoding, or can't be written.
+fn write_lines(
+    print_req: &PrintRequest,
+    lines: &[String],
+    output: Option<&PathBuf>,
+) -> Result<(), String> {
+    let mut text = lines.join(print_req.line_ending.as_str());
+    // An empty list makes an empty file
+    if !lines.is_empty() {
+        text.push_str(print_req.line_ending.as_str());
+    }
+    let bytes = encode(&text, print_req.output_encoding)
+        .map_err(|e| format!("Error: Unable to encode new list: {}", e))?;
+    match output {
+        // Print to file
+        Some(output) => File::create(output)
+            .and_then(|mut f| f.write_all(&bytes))
+            .map_err(|e| format!("Error: Unable to write {}: {}", output.display(), e)),
+        // If no output file destination, print resulting list to stdout,
+        // allowing use of > on command line
+        None => io::stdout()
+            .lock()
+            .write_all(&bytes)
+            .map_err(|e| format!("Error: Unable to print list: {}", e)),
+    }
+}
+
+/// Formats each word of the list as a line of output. If user set a number
+/// of dice sides or chose cards or coins, each line starts with the word's
+/// dice roll, card code, or coin flips, followed by the dice separator (a
+/// tab by default, or a space when printing in columns, since tabs would
+/// throw off the columns' alignment).
+/// ```
+/// use tidy::file_writer::{make_lines_of_list, PrintRequest};
+/// let print_req = PrintRequest {
+///     tidied_list: (0..36).map(|n| format!("word{}", n)).collect(),
+///     dice_sides: Some(6),
+///     ..Default::default()
+/// };
+/// let lines = make_lines_of_list(&print_req);
+/// assert_eq!(lines[0], "11\tword0");
+/// assert_eq!(lines[35], "66\tword35");
+/// ```
+pub fn make_lines_of_list(print_req: &PrintRequest) -> Vec<String> {
+    let default_separator = if print_req.print_columns.is_some() {
+        " "
+    } else {
+        "\t"
+    };
+    let separator = print_req
+        .dice_separator
+        .as_deref()
+        .unwrap_or(default_separator);
+    print_req
+        .tidied_list
+        .iter()
+        .enumerate()
+        .zip(make_annotations(print_req))
+        .map(|((i, word), annotations)| {
+            let mut line = match make_index_code(print_req, i) {
+                Some(code) => format!("{}{}{}", code, separator, word),
+                None => word.to_string(),
+            };
+            for annotation in annotations {
+                line.push('\t');
+                line += &annotation;
+            }
+            line
+        })
+        .collect()
+}
+
+/// Makes the lines of a companion to the list, for printing study sheets:
+/// a header, then a line for each word with its dice roll (or card code,
+/// coin flips, or, if none of those were asked for, line number), the word
+/// without its metadata, and a memorization hint, separated by tabs. Hints
+/// are filled in from the `companion_hint` template, or left blank to be
+/// written in by hand.
+/// ```
+/// use tidy::file_writer::{make_companion_lines, PrintRequest};
+/// let print_req = PrintRequest {
+///     tidied_list: (0..36).map(|n| format!("word{}", n)).collect(),
+///     dice_sides: Some(6),
+///     companion_hint: Some("{roll} is {word}".to_string()),
+///     ..Default::default()
+/// };
+/// let lines = make_companion_lines(&print_req);
+/// assert_eq!(lines[0], "roll\tword\thint");
+/// assert_eq!(lines[1], "11\tword0\t11 is word0");
+/// assert_eq!(lines.len(), 37);
+/// ```
+pub fn make_companion_lines(print_req: &PrintRequest) -> Vec<String> {
+    let (words, _metadata) = split_words_and_metadata(
+        &print_req.tidied_list,
+        print_req.ignore_before_delimiter,
+        print_req.ignore_after_delimiter,
+    );
+    let mut lines = vec!["roll\tword\thint".to_string()];
+    for (i, word) in words.iter().enumerate() {
+        let roll = make_index_code(print_req, i).unwrap_or_else(|| (i + 1).to_string());
+        let hint = match print_req.companion_hint {
+            Some(ref template) => fill_hint_template(template, word, &roll),
+            None => String::new(),
+        };
+        lines.push(format!("{}\t{}\t{}", roll, word, hint));
+    }
+    lines
+}
+
+/// Fills in a memorization hint template for a word. `{word}` is replaced
+/// with the word, `{roll}` with its dice roll, `{first}` with its first
+/// character, and `{length}` with its number of characters.
+/// ```
+/// use tidy::file_writer::fill_hint_template;
+/// assert_eq!(
+///     fill_hint_template("{first} is for {word} ({length} letters)", "zebra", "66666"),
+///     "z is for zebra (5 letters)"
+/// );
+/// ```
+pub fn fill_hint_template(template: &str, word: &str, roll: &str) -> String {
+    let first = word.chars().next().map(String::from).unwrap_or_default();
+    template
+        .replace("{word}", word)
+        .replace("{roll}", roll)
+        .replace("{first}", &first)
+        .replace("{length}", &count_characters(word).to_string())
+}
+
+/// Columns of information about each word that can be printed after it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Annotation {
+    /// Number of characters in the word
+    Length,
+    /// The word's share of the list's entropy per word, in bits: `-p *
+    /// log2(p)`, where `p` is the chance of picking the word. Picking is
+    /// assumed to be in proportion to frequency if the list's metadata are
+    /// frequencies, and uniform otherwise. Summed over all words, these make
+    /// up the entropy per word of the list.
+    EntropyContribution,
+    /// The word's frequency, read from its metadata. Blank if the metadata
+    /// aren't frequencies.
+    Frequency,
+}
+
+impl FromStr for Annotation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "length" => Ok(Annotation::Length),
+            "entropy-contribution" => Ok(Annotation::EntropyContribution),
+            "frequency" => Ok(Annotation::Frequency),
+            _ => Err(format!(
+                "Unknown annotation {}. Use length, entropy-contribution, or frequency.",
+                s
+            )),
+        }
+    }
+}
+
+/// Makes the requested annotations of every word of the list, in the order
+/// requested. Each word gets an empty `Vec` if no annotations were requested.
+/// ```
+/// use tidy::file_writer::{make_annotations, Annotation, PrintRequest};
+/// let print_req = PrintRequest {
+///     tidied_list: vec!["the,300".to_string(), "zebra,100".to_string()],
+///     ignore_after_delimiter: Some(','),
+///     annotations: vec![Annotation::Length, Annotation::Frequency, Annotation::EntropyContribution],
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     make_annotations(&print_req),
+///     vec![vec!["3", "300", "0.311278"], vec!["5", "100", "0.500000"]]
+/// );
+/// ```
+pub fn make_annotations(print_req: &PrintRequest) -> Vec<Vec<String>> {
+    let (words, metadata) = split_words_and_metadata(
+        &print_req.tidied_list,
+        print_req.ignore_before_delimiter,
+        print_req.ignore_after_delimiter,
+    );
+    let frequencies = parse_frequencies(&metadata);
+    let total_frequency: f64 = frequencies.iter().flatten().sum();
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| {
+            let mut annotations: Vec<String> = print_req
+                .annotations
+                .iter()
+                .map(|annotation| match annotation {
+                    Annotation::Length => count_characters(word).to_string(),
+                    Annotation::EntropyContribution => {
+                        let probability = match frequencies {
+                            Some(ref frequencies) => frequencies[i] / total_frequency,
+                            None => 1.0 / words.len() as f64,
+                        };
+                        let contribution = if probability > 0.0 {
+                            -probability * probability.log2()
+                        } else {
+                            0.0
+                        };
+                        format!("{:.6}", contribution)
+                    }
+                    Annotation::Frequency => match frequencies {
+                        Some(_) => metadata[i].trim().to_string(),
+                        None => String::new(),
+                    },
+                })
+                .collect();
+            if let Some(ref sources) = print_req.sources {
+                annotations.push(sources[i].clone().unwrap_or_default());
+            }
+            annotations
+        })
+        .collect()
+}
+
+/// Makes the dice roll, card code, or coin flips for the word at the given
+/// (zero-indexed) position of the list, if user asked for any of them. Dice
+/// with a number of sides that can't be printed (which `print_list` checks
+/// for first) get no roll.
+pub fn make_index_code(print_req: &PrintRequest, i: usize) -> Option<String> {
+    let list_length = print_req.tidied_list.len();
+    if let Some(dice_sides) = print_req.dice_sides {
+        format_dice_roll(
+            i,
+            dice_sides,
+            list_length,
+            print_req.print_dice_sides_as_their_base,
+            &print_req.dice_format,
+        )
+        .ok()
+    } else if print_req.cards {
+        Some(print_as_cards(i, list_length))
+    } else if print_req.coins {
+        Some(print_as_coins(i, list_length))
+    } else {
+        None
+    }
+}
+
+use crate::count_characters;
+/// Arranges lines of the list into the given number of columns, to be read
+/// from top to bottom and then left to right, like a printed diceware sheet.
+/// Every column is padded to the width of the longest line.
+///
+/// If given a page height, each page gets that many rows (filling every
+/// column before starting the next page), and pages are separated by a form
+/// feed character, which tells printers to start a new page. Otherwise, the
+/// whole list is one page.
+/// ```
+/// use tidy::file_writer::arrange_in_columns;
+/// let lines: Vec<String> = ["11 a", "12 bb", "13 c", "14 d", "15 e"]
+///     .iter()
+///     .map(|line| line.to_string())
+///     .collect();
+/// assert_eq!(
+///     arrange_in_columns(&lines, 2, None),
+///     vec!["11 a    14 d", "12 bb   15 e", "13 c"]
+/// );
+/// assert_eq!(
+///     arrange_in_columns(&lines, 2, Some(1)),
+///     vec!["11 a    12 bb", "\u{c}", "13 c    14 d", "\u{c}", "15 e"]
+/// );
+/// ```
+pub fn arrange_in_columns(
+    lines: &[String],
+    columns: usize,
+    page_height: Option<usize>,
+) -> Vec<String> {
+    let column_width = lines
+        .iter()
+        .map(|line| count_characters(line))
+        .max()
+        .unwrap_or(0);
+    let mut arranged = vec![];
+    for (page_number, page) in paginate(lines, columns, page_height)
+        .into_iter()
+        .enumerate()
+    {
+        if page_number > 0 {
+            arranged.push("\u{c}".to_string());
+        }
+        for row in page {
+            let mut line = String::new();
+            for cell in row {
+                line += cell;
+                for _i in count_characters(cell)..column_width + 3 {
+                    line.push(' ');
+                }
+            }
+            arranged.push(line.trim_end().to_string());
+        }
+    }
+    arranged
+}
+
+/// Splits items into pages of rows, where each row has (at most) the given
+/// number of columns and columns are filled from top to bottom. If no page
+/// height is given, all items go on one page.
+pub fn paginate<T>(items: &[T], columns: usize, page_height: Option<usize>) -> Vec<Vec<Vec<&T>>> {
+    let columns = columns.max(1);
+    let rows_per_page = page_height
+        .filter(|&height| height > 0)
+        .unwrap_or_else(|| items.len().div_ceil(columns))
+        .max(1);
+    items
+        .chunks(rows_per_page * columns)
+        .map(|page| {
+            // Only the last page can be partly empty, so make its columns
+            // as even as possible
+            let rows = page.len().div_ceil(columns).min(rows_per_page);
+            (0..rows)
+                .map(|row| page.iter().skip(row).step_by(rows).collect())
+                .collect()
+        })
+        .collect()
+}
+
+/// Line endings to use when printing the list
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::Crlf => "\r\n",
+        }
+    }
+}
+
+impl FromStr for LineEnding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lf" => Ok(LineEnding::Lf),
+            "crlf" => Ok(LineEnding::Crlf),
+            _ => Err(format!("Unknown line ending {}. Use lf or crlf.", s)),
+        }
+    }
+}
+
+/// Formats to print the list in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputFormat {
+    /// One word per line
+    #[default]
+    Text,
+    /// A standalone HTML page, for printing
+    Html,
+    /// A JSON object with the words, for programs (see `json_writer`)
+    Json,
+}
+
+/// The format to write an output file in, going by its extension: html or
+/// htm for HTML, json for JSON, and txt for text. Files with any other
+/// extension, or none, are written in the `default` format.
+/// ```
+/// use std::path::Path;
+/// use tidy::file_writer::{infer_output_format, OutputFormat};
+/// assert_eq!(infer_output_format(Path::new("list.HTML"), OutputFormat::Text), OutputFormat::Html);
+/// assert_eq!(infer_output_format(Path::new("list.txt"), OutputFormat::Html), OutputFormat::Text);
+/// assert_eq!(infer_output_format(Path::new("list"), OutputFormat::Json), OutputFormat::Json);
+/// ```
+pub fn infer_output_format(output: &Path, default: OutputFormat) -> OutputFormat {
+    let extension = output
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    match extension.as_deref() {
+        Some("html") | Some("htm") => OutputFormat::Html,
+        Some("json") => OutputFormat::Json,
+        Some("txt") => OutputFormat::Text,
+        _ => default,
     }
 }
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(OutputFormat::Text),
+            "html" => Ok(OutputFormat::Html),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(format!(
+                "Unknown output format {}. Use text, html, or json.",
+                s
+            )),
+        }
+    }
+}
+
+use crate::file_readers::file_checksum;
+use std::time::{SystemTime, UNIX_EPOCH};
+/// Assembles the lines of a metadata header describing how a list was made:
+/// the date, the version of Tidy, the full command used, and the SHA-256 hash
+/// of every inputted file. `print_list` prints these lines as comments at the
+/// top of the list, followed by the hash of the list itself.
+pub fn make_metadata_header(command: &str, inputted_files: &[PathBuf]) -> Vec<String> {
+    let seconds_since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System time is before 1970")
+        .as_secs();
+    let mut header = vec![
+        format!(
+            "Generated by Tidy version {} on {}",
+            env!("CARGO_PKG_VERSION"),
+            format_date(seconds_since_epoch)
+        ),
+        format!("Command: {}", command),
+    ];
+    for file in inputted_files {
+        match file_checksum(file) {
+            Ok(checksum) => header.push(format!("Input {:?} SHA-256: {}", file, checksum)),
+            Err(e) => eprintln!("Unable to calculate checksum of {:?}: {}", file, e),
+        }
+    }
+    header
+}
+
+/// Formats a number of seconds since the Unix epoch as a UTC date
+/// (YYYY-MM-DD), using Howard Hinnant's `civil_from_days` algorithm.
+/// ```
+/// use tidy::file_writer::format_date;
+/// assert_eq!(format_date(0), "1970-01-01");
+/// assert_eq!(format_date(951_782_400), "2000-02-29");
+/// ```
+pub fn format_date(seconds_since_epoch: u64) -> String {
+    let days = (seconds_since_epoch / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
@@ -0,0 +1,58 @@
+//! Run a list through another program, for filters Tidy doesn't have
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// Runs `command` in the shell (`sh -c`, or `cmd /C` on Windows), with the
+/// list on its standard input, one word per line, and returns the lines it
+/// prints, leaving out blank ones. Returns an error message if the command
+/// can't be run or exits unsuccessfully.
+/// ```
+/// use tidy::filter_command::run_filter_command;
+/// let list = vec!["apple".to_string(), "quince".to_string()];
+/// # #[cfg(unix)]
+/// assert_eq!(run_filter_command("grep -v q", &list).unwrap(), vec!["apple"]);
+/// ```
+pub fn run_filter_command(command: &str, list: &[String]) -> Result<Vec<String>, String> {
+    let mut child = shell_command(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Couldn't run filter command {}: {}", command, e))?;
+    // Write from another thread, so that a command that prints as it reads
+    // can't fill up its output while we're still writing to it
+    let mut stdin = child.stdin.take().unwrap();
+    let input = list.join("\n") + "\n";
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Couldn't run filter command {}: {}", command, e))?;
+    // A command that stops reading early, like head, closes the pipe on
+    // us, which is fine
+    let _ = writer.join();
+    if !output.status.success() {
+        return Err(format!(
+            "Filter command {} failed ({})",
+            command, output.status
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
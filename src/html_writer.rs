@@ -0,0 +1,99 @@
+//! Render a finished list as an HTML page, ready to print and store offline
+use crate::file_writer::{make_annotations, make_index_code, paginate, PrintRequest};
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; }
+h1 { margin-bottom: 0.2em; }
+ul.metadata { list-style: none; padding: 0; color: #444; font-size: 0.85em; }
+table.page { border-collapse: collapse; margin-bottom: 2em; }
+table.page + table.page { break-before: page; page-break-before: always; }
+td { padding: 0.1em 0.8em 0.1em 0; }
+td.roll { font-family: monospace; color: #444; }
+td.word { padding-right: 2.5em; }
+td.annotation { color: #444; font-size: 0.85em; }";
+
+/// Renders the list as a standalone HTML page: a title, lines describing
+/// how the list was made (any metadata header, the number of words, and the
+/// list's SHA-256 hash), then the words in a table next to their dice rolls,
+/// card codes, or coin flips, and any annotations.
+///
+/// The table follows the `print_columns` and `page_height` options of the
+/// `PrintRequest`: each page of rows is its own table, and printing starts
+/// each table on a new page. Most browsers can also save the page as a PDF.
+pub fn render_html(print_req: &PrintRequest, checksum: &str) -> Vec<String> {
+    let title = escape_html(print_req.title.as_deref().unwrap_or("Word list"));
+    let mut lines = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        format!("<title>{}</title>", title),
+        format!("<style>\n{}\n</style>", STYLE),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        format!("<h1>{}</h1>", title),
+        "<ul class=\"metadata\">".to_string(),
+    ];
+    let metadata_lines = print_req
+        .preserved_comments
+        .iter()
+        .chain(print_req.metadata_header.iter().flatten());
+    for line in metadata_lines {
+        lines.push(format!("<li>{}</li>", escape_html(line)));
+    }
+    lines.push(format!("<li>{} words</li>", print_req.tidied_list.len()));
+    lines.push(format!("<li>SHA-256: <code>{}</code></li>", checksum));
+    lines.push("</ul>".to_string());
+
+    let cells: Vec<String> = print_req
+        .tidied_list
+        .iter()
+        .enumerate()
+        .zip(make_annotations(print_req))
+        .map(|((i, word), annotations)| {
+            let mut cell = match make_index_code(print_req, i) {
+                Some(code) => format!(
+                    "<td class=\"roll\">{}</td><td class=\"word\">{}</td>",
+                    escape_html(&code),
+                    escape_html(word)
+                ),
+                None => format!("<td class=\"word\">{}</td>", escape_html(word)),
+            };
+            for annotation in annotations {
+                cell += &format!("<td class=\"annotation\">{}</td>", escape_html(&annotation));
+            }
+            cell
+        })
+        .collect();
+    let columns = print_req.print_columns.unwrap_or(1);
+    for page in paginate(&cells, columns, print_req.page_height) {
+        lines.push("<table class=\"page\">".to_string());
+        for row in page {
+            let row: Vec<&str> = row.iter().map(|cell| cell.as_str()).collect();
+            lines.push(format!("<tr>{}</tr>", row.concat()));
+        }
+        lines.push("</table>".to_string());
+    }
+    lines.push("</body>".to_string());
+    lines.push("</html>".to_string());
+    lines
+}
+
+/// Escapes characters that have a special meaning in HTML.
+/// ```
+/// use tidy::html_writer::escape_html;
+/// assert_eq!(escape_html("<b>\"rock & roll\"</b>"), "&lt;b&gt;&quot;rock &amp; roll&quot;&lt;/b&gt;");
+/// ```
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::new();
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,43 @@
+//! Render a finished list as JSON, for programs that read word lists
+use crate::file_writer::{make_index_code, PrintRequest};
+use crate::logging::escape_json;
+
+/// Renders the list as a JSON object on one line: the words, in order, then
+/// the dice roll, card code, or coin flips of each word, if the user asked
+/// for them, then the list's SHA-256 hash. Annotations and columns are left
+/// out, since programs can work them out from the words.
+/// ```
+/// use tidy::file_writer::PrintRequest;
+/// use tidy::json_writer::render_json;
+/// let print_req = PrintRequest {
+///     tidied_list: vec!["abacus".to_string(), "say \"hi\"".to_string()],
+///     coins: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     render_json(&print_req, "abc123"),
+///     r#"{"words":["abacus","say \"hi\""],"codes":["H","T"],"sha256":"abc123"}"#
+/// );
+/// ```
+pub fn render_json(print_req: &PrintRequest, checksum: &str) -> String {
+    let words: Vec<String> = print_req
+        .tidied_list
+        .iter()
+        .map(|word| format!("\"{}\"", escape_json(word)))
+        .collect();
+    let codes: Vec<String> = (0..print_req.tidied_list.len())
+        .filter_map(|i| make_index_code(print_req, i))
+        .map(|code| format!("\"{}\"", escape_json(&code)))
+        .collect();
+    let codes = if codes.is_empty() {
+        String::new()
+    } else {
+        format!(",\"codes\":[{}]", codes.join(","))
+    };
+    format!(
+        "{{\"words\":[{}]{},\"sha256\":\"{}\"}}",
+        words.join(","),
+        codes,
+        checksum
+    )
+}
@@ -0,0 +1,147 @@
+//! Print Tidy's log messages to the terminal, at the verbosity the user chose
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use std::fs;
+use std::str::FromStr;
+use std::time::Instant;
+
+/// How to format log messages
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LogFormat {
+    /// One human-readable line per message
+    #[default]
+    Text,
+    /// One JSON object per line, for other programs to read
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("Unknown log format {}. Use text or json.", s)),
+        }
+    }
+}
+
+/// Converts the number of times the user gave -v to a log level. With none,
+/// only warnings and errors are logged. Once (-v) logs what each step did,
+/// twice (-vv) adds how long each step took and memory usage, and three
+/// times (-vvv) logs everything.
+pub fn verbosity_to_level(verbosity: u8) -> LevelFilter {
+    match verbosity {
+        0 => LevelFilter::Warn,
+        1 => LevelFilter::Info,
+        2 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+struct TerminalLogger {
+    format: LogFormat,
+    started: Instant,
+}
+
+impl Log for TerminalLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        // Skip messages from dependencies
+        metadata.target().starts_with("tidy")
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let elapsed = self.started.elapsed().as_secs_f64();
+        // Only look up memory usage when it's asked for
+        let memory = if record.level() >= Level::Debug {
+            memory_usage()
+        } else {
+            None
+        };
+        match self.format {
+            LogFormat::Text => eprintln!(
+                "[{:>8.3}s {:<5}] {}{}",
+                elapsed,
+                record.level(),
+                record.args(),
+                match memory {
+                    Some(bytes) => format!(" (memory: {:.1} MB)", bytes as f64 / 1_000_000.0),
+                    None => "".to_string(),
+                }
+            ),
+            LogFormat::Json => eprintln!(
+                "{{\"elapsed_seconds\":{:.3},\"level\":\"{}\",\"target\":\"{}\",\"message\":\"{}\"{}}}",
+                elapsed,
+                record.level(),
+                escape_json(record.target()),
+                escape_json(&record.args().to_string()),
+                match memory {
+                    Some(bytes) => format!(",\"memory_bytes\":{}", bytes),
+                    None => "".to_string(),
+                }
+            ),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Sets up logging to stderr. Should only be called once.
+pub fn init_logging(verbosity: u8, format: LogFormat) {
+    let logger = TerminalLogger {
+        format,
+        started: Instant::now(),
+    };
+    log::set_logger(Box::leak(Box::new(logger))).expect("Error: Logging was already set up");
+    log::set_max_level(verbosity_to_level(verbosity));
+}
+
+/// How much memory this process is using (its resident set size), in bytes.
+/// Only available on Linux.
+pub fn memory_usage() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// The most memory this process has used (its peak resident set size), in
+/// bytes, since it started or since `reset_peak_memory_usage` was last
+/// called. Only available on Linux.
+pub fn peak_memory_usage() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kilobytes: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Resets the peak memory usage reported by `peak_memory_usage` to the
+/// current usage, so the peak of a single task can be measured. Only
+/// available on Linux (4.0 or later); elsewhere, does nothing.
+pub fn reset_peak_memory_usage() {
+    let _ = fs::write("/proc/self/clear_refs", "5");
+}
+
+/// Escapes a string to be put between quotes in JSON
+/// ```
+/// use tidy::logging::escape_json;
+/// assert_eq!(escape_json("say \"hi\"\n"), "say \\\"hi\\\"\\n");
+/// ```
+pub fn escape_json(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,21 +1,71 @@
-use clap::Parser;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::Shell;
 use std::env;
+use std::fs;
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tidy::*;
 pub mod display_information;
 pub mod input_validations;
+use crate::assertions::{find_assertion_failures, ListAssertions};
+use crate::bench::{bench_stages, BenchSettings};
+use crate::bip39::apply_bip39_constraints;
+use crate::cache::{cache_key, read_cached_list, write_cached_list};
+use crate::checkpoint::{CheckpointCallback, WhittleCheckpoint};
+use crate::color::{init_color, ColorChoice};
+use crate::dice::DiceFormat;
+use crate::display_information::{
+    display_cross_list_report, display_list_information, display_relaxation_suggestions,
+    display_tidy_report, format_ambiguity_examples, split_words_and_metadata,
+};
+use crate::encodings::InputEncoding;
+use crate::encodings::OutputEncoding;
+use crate::error::TidyError;
 use crate::file_readers::*;
 use crate::file_writer::*;
+use crate::filter_command::run_filter_command;
+use crate::filters::CustomStep;
 use crate::input_validations::*;
+use crate::list_manipulations::{
+    merge_weighted, pin_words, tokenize_prose_into_ngrams, CanonicalCase, Capitalization,
+    CutStrategy, DedupKeep, DelimiterOccurrence, LengthQuota, QuotaPriority, SortOrder,
+    DEFAULT_CONFUSABLE_CHARS, DEFAULT_CONSONANT_CLUSTERS, DEFAULT_GIBBERISH_THRESHOLD,
+    DEFAULT_LEET_SUBSTITUTIONS,
+};
+use crate::logging::{init_logging, LogFormat};
+use crate::option_conflicts::{validate_and_parse_ignore_options, OutputOptions};
 use crate::parsers::*;
+use crate::pipeline::{complete_pipeline, PipelineStep, DEFAULT_PIPELINE};
+use crate::plan::{find_planned_steps, Plan};
+use crate::progress_bars::make_progress_bars;
+use crate::registry::{fetch_registry_list, fetched_list_path, find_registry_list, REGISTRY};
+use crate::sampling::RandStrategy;
+use crate::sardinas_patterson_pruning::SchlinkertStrategy;
+use crate::script::load_script;
+use crate::stemming::STEMMER_LANGUAGES;
+use crate::suggestions::suggest_relaxations;
+use crate::uniquely_decodable::find_ambiguous_concatenations;
+use crate::words::count_tidied_words;
 
 /// Combine and clean word lists
 #[derive(Parser, Debug)]
-#[clap(version, about, name = "tidy")]
+#[clap(
+    version,
+    about,
+    name = "tidy",
+    args_conflicts_with_subcommands = true,
+    subcommand_negates_reqs = true
+)]
 struct Args {
-    /// Do not print any extra information
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Do not print any extra information, including progress bars
     #[clap(long = "quiet")]
     quiet: bool,
 
@@ -23,61 +73,362 @@ struct Args {
     #[clap(long = "dry-run")]
     dry_run: bool,
 
-    /// Debug mode
+    /// With --dry-run, don't read inputted word lists at all. Instead, print a plan of what Tidy
+    /// would do as JSON: every option (including defaults), the steps it would run in order, and
+    /// any problems with the given options.
+    #[clap(long = "plan", requires = "dry_run")]
+    plan: bool,
+
+    /// Debug mode. Same as -vv, and also prints a summary table of how many words each step
+    /// removed and how long it took
     #[clap(long = "debug")]
     debug: bool,
 
+    /// Log what Tidy is doing to stderr. Use once (-v) to log what each step did to the list,
+    /// twice (-vv) to also log how long each step took and memory usage, or three times (-vvv)
+    /// for everything.
+    #[clap(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Format of log messages: text (the default) or json (one object per line).
+    #[clap(
+        long = "log-format",
+        default_value = "text",
+        value_parser = NamedValueParser::<LogFormat>::new(["text", "json"])
+    )]
+    log_format: LogFormat,
+
+    /// When to color attributes and warnings: auto (the default) colors them when printing to a
+    /// terminal, unless the NO_COLOR environment variable is set; always; or never.
+    #[clap(
+        long = "color",
+        default_value = "auto",
+        global = true,
+        value_parser = NamedValueParser::<ColorChoice>::new(["auto", "always", "never"])
+    )]
+    color: ColorChoice,
+
+    /// Print the order Tidy runs its steps in, and how many words are left after each one.
+    /// Helpful for finding which option removed more words than expected.
+    #[clap(long = "explain")]
+    explain: bool,
+
+    /// Print a one-line audit trail of how many words survived each step Tidy ran, from the
+    /// inputted words to the final list. Printed even with --quiet.
+    #[clap(long = "audit")]
+    audit: bool,
+
+    /// Change the order Tidy runs its steps in, given as a comma-separated list of step names
+    /// (for example, "reject,lowercase" to reject words before lowercasing them). Steps left out
+    /// keep their default order, after the named ones. Steps done to each word, like lowercase,
+    /// reject, and minimum-length, must come before steps that depend on the whole list, like
+    /// remove-prefix-words, dedup, and sort. Use --explain to see the order used.
+    #[clap(
+        long = "pipeline",
+        value_delimiter = ',',
+        value_parser = NamedValueParser::<PipelineStep>::new(DEFAULT_PIPELINE.iter().map(|step| step.name()))
+    )]
+    pipeline: Option<Vec<PipelineStep>>,
+
+    /// Pipe the list through this shell command, one word per line, and keep the lines it
+    /// prints, for filters Tidy doesn't have (for example, "grep -v xyz" or a script of your
+    /// own). Runs after the step named by --filter-cmd-after.
+    #[clap(long = "filter-cmd", value_name = "COMMAND")]
+    filter_cmd: Option<String>,
+
+    /// Run each word, and the metadata of its line, through the check(word, metadata) function
+    /// of this Rhai script, which returns true to keep the word, false to remove it, or a string
+    /// to replace it with. Runs after the step named by --script-after. Needs Tidy built with
+    /// the scripting feature.
+    #[clap(long = "script", value_name = "FILE")]
+    script: Option<PathBuf>,
+
+    /// The step of the pipeline to run --script right after. Defaults to maximum-length, the last
+    /// of the steps done to each word.
+    #[clap(
+        long = "script-after",
+        value_name = "STEP",
+        default_value = "maximum-length",
+        requires = "script",
+        value_parser = NamedValueParser::<PipelineStep>::new(DEFAULT_PIPELINE.iter().map(|step| step.name()))
+    )]
+    script_after: PipelineStep,
+
+    /// The step of the pipeline to run --filter-cmd right after. Must be a step that depends on
+    /// the whole list, like dedup (the default), remove-prefix-words, or sort, so that the
+    /// command is only run once.
+    #[clap(
+        long = "filter-cmd-after",
+        value_name = "STEP",
+        default_value = "dedup",
+        requires = "filter_cmd",
+        value_parser = NamedValueParser::<PipelineStep>::new(DEFAULT_PIPELINE.iter().map(|step| step.name()))
+    )]
+    filter_cmd_after: PipelineStep,
+
+    /// Make a list that follows the BIP39 standard: words are NFKD-normalized,
+    /// unique within their first 4 characters, and no longer than 8 characters.
+    /// Also reports any ways the new list violates BIP39, including if it is
+    /// not exactly 2,048 words long.
+    #[clap(long = "bip39")]
+    bip39: bool,
+
+    /// Exit with an error if the new list doesn't have exactly this many words. The list is
+    /// still printed, so it can be looked over.
+    #[clap(long = "assert-length")]
+    assert_length: Option<usize>,
+
+    /// Exit with an error if any two words on the new list share their first this many
+    /// characters.
+    #[clap(long = "assert-unique-prefix")]
+    assert_unique_prefix: Option<usize>,
+
+    /// Exit with an error if any word on the new list is in this file (or files).
+    #[clap(long = "assert-free-of")]
+    assert_free_of: Option<Vec<PathBuf>>,
+
+    /// Check whether new list is uniquely decodable and, if it isn't, print an
+    /// example of words from the list that can be combined in two ways to make
+    /// the same string. Doesn't remove any words; use --schlinkert-prune for
+    /// that. Pair with --dry-run to only check the list.
+    #[clap(long = "check-decodable")]
+    check_decodable: bool,
+
+    /// Write examples of strings that can be split into words in two different ways to this
+    /// file: from the list just before its Schlinkert prune, noting which of their words the
+    /// prune removed, and, with --check-decodable, from the new list. Useful for showing why a
+    /// Schlinkert prune removed the words it did.
+    #[clap(long = "ambiguity-report", value_name = "FILE")]
+    ambiguity_report: Option<PathBuf>,
+
+    /// Most examples of each kind to write to the --ambiguity-report
+    #[clap(
+        long = "ambiguity-examples",
+        value_name = "N",
+        default_value = "25",
+        requires = "ambiguity_report"
+    )]
+    ambiguity_examples: usize,
+
+    /// Cache the new list in this directory, keyed by the contents of the inputted files and the
+    /// options that change which words are on the list. Running Tidy again with the same inputs
+    /// and options, changing only how the list is printed (like --format or --dice), then skips
+    /// straight to printing the cached list. Lists cut at random without a --seed aren't cached.
+    /// Can't be used with options that report on how the list was made, like --explain.
+    #[clap(
+        long = "cache",
+        value_name = "DIR",
+        conflicts_with_all = ["ambiguity_report", "update_from", "explain", "audit"]
+    )]
+    cache: Option<PathBuf>,
+
+    /// Print each pair of words on the new list where one is probably an
+    /// inflection of the other (like "cat" and "cats"). Uses simple English
+    /// suffix rules, so not every pair will be right.
+    #[clap(long = "find-inflections")]
+    find_inflections: bool,
+
+    /// Print every word, or pair of words, on the new list that spells the same thing as
+    /// another pair of words when typed without a separator (like "input" and "in" + "put",
+    /// or "to" + "pin" and "top" + "in"). Doesn't remove any words; use
+    /// --remove-compound-collisions for that.
+    #[clap(long = "find-compound-collisions")]
+    find_compound_collisions: bool,
+
+    /// Warn if more than the given percent of words on the new list start with the same
+    /// character or pair of characters (for example, a corpus artifact like thousands of
+    /// words starting with "un"). Defaults to 15 percent if no percent is given.
+    #[clap(long = "warn-skew", num_args = 0..=1, default_missing_value = "15")]
+    warn_skew: Option<f64>,
+
+    /// Don't point out when the new list is just short of a clean power of 6 or 2 (like 7,740
+    /// words, 36 short of 6^5)
+    #[clap(long = "no-length-advice")]
+    no_length_advice: bool,
+
+    /// If the new list has fewer than this many words, suggest which single filter to relax
+    /// (like a maximum word length one higher, or no minimum edit distance) to get closer to
+    /// it. Found by tidying the inputted lists again with each filter relaxed, so may be slow.
+    #[clap(long = "suggest-to", value_name = "N", conflicts_with = "whittle_to")]
+    suggest_to: Option<usize>,
+
     /// Print attributes about new list to terminal. Can be used more than once
-    /// to print more attributes. Some attributes may take a nontrivial amount
-    /// of time to calculate.
+    /// to print more attributes (up to 6 times, which adds letter and bigram
+    /// distributions). Some attributes may take a nontrivial amount of time to
+    /// calculate.
     #[clap(short = 'A', long = "attributes", action = clap::ArgAction::Count)]
     attributes: u8,
 
+    /// Separator to assume between the words of a passphrase when calculating attributes
+    /// like bits per typed character. Defaults to a space.
+    #[clap(long = "separator", default_value = " ")]
+    separator: String,
+
+    /// Guesses per second to assume an attacker can make, when estimating how long passphrases
+    /// from the new list would take to crack. Shown with attributes. Defaults to a trillion.
+    #[clap(long = "guesses-per-second", default_value = "1e12", value_parser = parse_guesses_per_second)]
+    guesses_per_second: f64,
+
+    /// Compare new list to a baseline list: either a built-in list (eff-long or eff-short)
+    /// or a path to a file with one word per line. Prints how many words the lists share,
+    /// words unique to each, and differences in entropy, mean word length, and whether
+    /// each list is free of prefix words.
+    #[clap(long = "compare-to")]
+    compare_to: Option<String>,
+
     /// Print a handful of pseudorandomly selected words from the created list
     /// to the terminal. Should NOT be used as secure passphrases.
     #[clap(short = 's', long = "samples")]
     samples: bool,
 
+    #[clap(flatten)]
+    sample_args: SampleArgs,
+
     /// Ignore characters after the first instance of the specified delimiter until the end of line, treating
     /// anything before the delimiter as a word. Delimiter must be a single character (e.g., ','). Use 't'
     /// for tab and 's' for space. Helpful for ignoring metadata like word frequencies.
-    /// Works with attribute analysis and most word removal options, but not with word modifications
-    /// (like to lowercase). May not be used together with -d, -D or -G options.
-    #[clap(short = 'g', long = "ignore-after")]
+    /// Word modifications and removals only see the word, and each word keeps the metadata of its
+    /// first line. May not be used together with -d, -D, -G, --dice or --sides-as-base options.
+    #[clap(
+        short = 'g',
+        long = "ignore-after",
+        conflicts_with = "ignore_before_delimiter"
+    )]
     ignore_after_delimiter: Option<char>,
 
     /// Ignore characters before and including the first instance of the specified delimiter, treating
     /// anything after the delimiter as a word. Delimiter must be a single character (e.g., ','). Use 't'
     /// for tab and 's' for space. Helpful for ignoring metadata like word frequencies.
-    /// Works with attribute analysis and most word removal options, but not with word modifications
-    /// (like to lowercase). May not be used together with -d, -D or -g options.
+    /// Word modifications and removals only see the word, and each word keeps the metadata of its
+    /// first line. May not be used together with -d, -D, -g, --dice or --sides-as-base options.
     #[clap(short = 'G', long = "ignore-before")]
     ignore_before_delimiter: Option<char>,
 
+    /// Keep every column of metadata after each word in the new list, even if the metadata has more
+    /// delimiters in it. Without this, only the metadata up to the next delimiter is kept, so a
+    /// frequency count would be kept but any columns after it would be dropped.
+    #[clap(long = "keep-metadata", requires = "ignore_after_delimiter")]
+    keep_metadata: bool,
+
+    /// Print the SHA-256 hash of the new list, calculated with one word per line and
+    /// a line feed after each word. Helpful for confirming that two lists are identical.
+    #[clap(long = "checksum")]
+    checksum: bool,
+
+    /// Add the SHA-256 hash of the new list as a comment on the first line of the
+    /// outputted list (e.g. "# SHA-256: 9f86d0...").
+    #[clap(long = "checksum-header")]
+    checksum_header: bool,
+
+    /// Add a header of comments to the top of the outputted list recording the date, the
+    /// version of Tidy, the full command used, the SHA-256 hash of each inputted file, and
+    /// the SHA-256 hash of the new list. Helpful for making published lists reproducible.
+    #[clap(long = "metadata-header")]
+    metadata_header: bool,
+
     /// Do NOT sort outputted list alphabetically. Preserves original list order.
     /// Note that duplicate lines and blank lines will still be removed.
-    #[clap(short = 'O', long = "no-sort")]
+    /// Same as --sort-by none.
+    #[clap(short = 'O', long = "no-sort", conflicts_with = "sort_by")]
     no_alpha_sort: bool,
 
+    /// Order to sort outputted list in: alpha (alphabetically, the default),
+    /// length (shortest words first), length-then-alpha, frequency (most
+    /// frequent first, reading frequencies given with --ignore-after), random,
+    /// or none (keep original list order).
+    #[clap(
+        long = "sort-by",
+        value_parser = NamedValueParser::<SortOrder>::new([
+            "alpha",
+            "length",
+            "length-then-alpha",
+            "frequency",
+            "random",
+            "none"
+        ])
+    )]
+    sort_by: Option<SortOrder>,
+
+    /// Reverse the order of outputted list, after any sorting. For example,
+    /// --sort-by length --reverse puts the longest words first.
+    #[clap(long = "reverse")]
+    reverse_sort: bool,
+
     /// Normalize Unicode of all characters of all words. Accepts nfc, nfd, nfkc, or nfkd (case
     /// insensitive).
-    #[clap(short = 'z', long = "normalization-form")]
+    #[clap(
+        short = 'z',
+        long = "normalization-form",
+        value_parser = NamedValueParser::<String>::new(["nfc", "nfd", "nfkc", "nfkd"])
+    )]
     normalization_form: Option<String>,
 
-    /// Specify a locale for words on the list. Aids with sorting. Examples: en-US, es-ES. Defaults
-    /// to system LANG. If LANG environmental variable is not set, uses en-US.
-    #[clap(long = "locale")]
+    /// Specify a locale for words on the list. Words are sorted (collated) according to the
+    /// conventions of this locale, so accented letters sort next to unaccented ones. Examples:
+    /// en-US, es-ES, fr. Defaults to system LANG. If LANG environmental variable is not set, uses
+    /// en-US.
+    #[clap(long = "locale", visible_alias = "collate")]
     locale: Option<String>,
 
     /// Lowercase all words on new list
     #[clap(short = 'l', long = "lowercase")]
     to_lowercase: bool,
 
+    /// Capitalize the words of the new list: first (the first letter of each entry, like "Ice
+    /// cream"), all (the first letter of every word of each entry, like "Ice Cream"), or none (the
+    /// default). Done after removing duplicates, so combine with --lowercase for a list with no
+    /// other capital letters.
+    #[clap(
+        long = "capitalize",
+        default_value = "none",
+        value_parser = NamedValueParser::<Capitalization>::new(["first", "all", "none"])
+    )]
+    capitalization: Capitalization,
+
+    /// Treat words that only differ in case, like "Apple" and "apple", as
+    /// duplicates, without lowercasing the rest of the list. By default, keeps
+    /// whichever comes first on the list (see --keep-case).
+    #[clap(long = "dedup-ignore-case")]
+    dedup_ignore_case: bool,
+
+    /// When using --dedup-ignore-case, which of the words that only differ in
+    /// case to keep: first (the default, whichever comes first on the list),
+    /// lowercase, or capitalized.
+    #[clap(
+        long = "keep-case",
+        requires = "dedup_ignore_case",
+        value_parser = NamedValueParser::<CanonicalCase>::new(["first", "lowercase", "capitalized"])
+    )]
+    keep_case: Option<CanonicalCase>,
+
+    /// When the same word comes up more than once, like in two inputted lists with different
+    /// frequency counts, which of its lines to keep: first (the default), last,
+    /// highest-frequency (the line whose metadata starts with the highest number), or shortest
+    /// (counting both the word and its metadata). The kept line's metadata, and, with
+    /// --dedup-ignore-case, its spelling, go at the position of the word's first line. Also
+    /// breaks ties between words --keep-case doesn't choose between.
+    #[clap(
+        long = "dedup-keep",
+        default_value = "first",
+        value_parser = NamedValueParser::<DedupKeep>::new(["first", "last", "highest-frequency", "shortest"])
+    )]
+    dedup_keep: DedupKeep,
+
     /// Replace “smart” quotation marks, both “double” and ‘single’,
     /// with their "straight" versions
     #[clap(short = 'q', long = "straighten")]
     straighten_quotes: bool,
 
+    /// Delete apostrophes, straight or curly, from words, so "don't" becomes
+    /// "dont". Unlike -n, leaves other punctuation for other options to reject.
+    #[clap(long = "delete-apostrophes")]
+    delete_apostrophes: bool,
+
+    /// Replace hyphens and dashes of other kinds, like the Unicode hyphen (‐)
+    /// and the en dash (–), with ASCII hyphens (-), and delete soft hyphens
+    #[clap(long = "normalize-hyphens")]
+    normalize_hyphens: bool,
+
     /// Remove prefix words from new list
     #[clap(short = 'P', long = "remove-prefix")]
     remove_prefix_words: bool,
@@ -86,11 +437,51 @@ struct Args {
     #[clap(short = 'S', long = "remove-suffix")]
     remove_suffix_words: bool,
 
+    /// Remove words that appear anywhere inside another word (like "and" in "candy"), which
+    /// includes prefix and suffix words
+    #[clap(long = "remove-infix-words")]
+    remove_infix_words: bool,
+
+    /// Remove words until no word, or pair of words, spells the same thing as another pair of
+    /// words (see --find-compound-collisions). Removes the longest word of each collision, or,
+    /// of those, the one furthest down the list.
+    #[clap(long = "remove-compound-collisions")]
+    remove_compound_collisions: bool,
+
     /// Use Sardinas-Patterson algorithm to remove words to make list
     /// uniquely decodable. Experimental!
     #[clap(short = 'K', long = "schlinkert-prune")]
     schlinkert_prune: bool,
 
+    /// How to choose which words to remove when doing a Schlinkert prune: all (remove every
+    /// flagged word at once; the default), longest (remove longer words first), lowest-frequency
+    /// (remove words closest to the end of the inputted list first), or fewest (best-effort
+    /// attempt to remove as few words as possible). All but the default may be slow on long lists.
+    /// Used by --schlinkert-prune and --best-prune.
+    #[clap(
+        long = "schlinkert-strategy",
+        default_value = "all",
+        value_parser = NamedValueParser::<SchlinkertStrategy>::new(["all", "longest", "lowest-frequency", "fewest"])
+    )]
+    schlinkert_strategy: SchlinkertStrategy,
+
+    /// Give up on a Schlinkert prune that takes longer than this many seconds, and remove prefix
+    /// words instead, printing a warning.
+    #[clap(
+        long = "timeout",
+        value_name = "SECONDS",
+        requires = "schlinkert_prune"
+    )]
+    timeout: Option<u64>,
+
+    /// Try removing prefix words, removing suffix words, and doing a Schlinkert prune, then keep
+    /// whichever makes the longest list. Prints the number of words each method kept.
+    #[clap(
+        long = "best-prune",
+        conflicts_with_all = ["remove_prefix_words", "remove_suffix_words", "schlinkert_prune"]
+    )]
+    best_prune: bool,
+
     /// Remove all words with non-alphanumeric characters from new list. Words with diacritics will
     /// remain
     #[clap(short = 'N', long = "remove-nonalphanumeric")]
@@ -116,6 +507,72 @@ struct Args {
     #[clap(short = 'C', long = "remove-nonascii")]
     remove_nonascii: bool,
 
+    /// Remove all words that mix letters from more than one script, like a Latin
+    /// word with a Cyrillic letter that looks like a Latin one in it
+    #[clap(long = "remove-mixed-script")]
+    remove_mixed_script: bool,
+
+    /// Remove all entries with whitespace in them, like "ice cream"
+    #[clap(long = "remove-multiword", conflicts_with = "join_multiword_separator")]
+    remove_multiword: bool,
+
+    /// Join the words of entries with whitespace in them with the given separator,
+    /// so "ice cream" becomes "ice-cream" with "-", or "icecream" with ""
+    #[clap(long = "join-multiword", value_name = "SEPARATOR")]
+    join_multiword_separator: Option<String>,
+
+    /// Remove all words with characters that are easy to confuse when handwritten
+    /// or printed: l, 1, and I; O and 0; and rn (which looks like m). Use
+    /// --confusable-chars to choose other characters.
+    #[clap(long = "remove-confusable-chars")]
+    remove_confusable_chars: bool,
+
+    /// Characters, or sequences of characters, for --remove-confusable-chars to
+    /// remove words with, separated by commas, like "l,1,I,O,0,rn,vv"
+    #[clap(
+        long = "confusable-chars",
+        value_delimiter = ',',
+        requires = "remove_confusable_chars"
+    )]
+    confusable_chars: Option<Vec<String>>,
+
+    /// Remove all words with the same letter twice in a row, like "balloon", which are easy to
+    /// misspell when heard rather than read
+    #[clap(long = "remove-doubled-letters")]
+    remove_doubled_letters: bool,
+
+    /// Remove all words with clusters of consonants that are hard to say or make out when
+    /// spoken, especially for non-native speakers, like the "ths" of "months" or the "sts" of
+    /// "tests". Use --consonant-clusters to choose other clusters.
+    #[clap(long = "remove-consonant-clusters")]
+    remove_consonant_clusters: bool,
+
+    /// Clusters of consonants for --remove-consonant-clusters to remove words with, separated by
+    /// commas, like "sts,ths,rlds"
+    #[clap(
+        long = "consonant-clusters",
+        value_delimiter = ',',
+        requires = "remove_consonant_clusters"
+    )]
+    consonant_clusters: Option<Vec<String>>,
+
+    /// Remove words that look like random junk rather than words, like base64 blobs and hex
+    /// strings from scraped web pages. Each word is scored by how varied its characters are (their
+    /// entropy) and how often it switches between lowercase letters, uppercase letters, and
+    /// digits. Use --gibberish-threshold to choose how sensitive this is.
+    #[clap(long = "remove-gibberish")]
+    remove_gibberish: bool,
+
+    /// Score at or above which --remove-gibberish removes a word. Lower scores remove more words.
+    /// Real words rarely score above 5
+    #[clap(
+        long = "gibberish-threshold",
+        value_name = "SCORE",
+        default_value_t = DEFAULT_GIBBERISH_THRESHOLD,
+        requires = "remove_gibberish"
+    )]
+    gibberish_threshold: f64,
+
     /// Remove all words with integers in them from list
     #[clap(short = 'I', long = "remove-integers")]
     remove_integers: bool,
@@ -124,6 +581,17 @@ struct Args {
     #[clap(short = 'i', long = "delete-integers")]
     delete_integers: bool,
 
+    /// Take only the Nth column of each line as the word, counting from 1, like awk's $N. Columns are
+    /// separated by --field-separator, or by runs of whitespace. -d X works like
+    /// --field-separator X --field 1, and -D X --delimiter-occurrence all like
+    /// --field-separator X --field 2.
+    #[clap(long = "field", value_parser = parse_field_number)]
+    field_number: Option<usize>,
+
+    /// Character that separates the columns for --field. Use 't' for tab and 's' for space.
+    #[clap(long = "field-separator", requires = "field_number")]
+    field_separator: Option<char>,
+
     /// Delete all characters after the first instance of the specified delimiter until the end of line
     /// (including the delimiter). Delimiter must be a single character (e.g., ','). Use 't' for tab and
     /// 's' for space. May not be used together with -g or -G options.
@@ -136,6 +604,16 @@ struct Args {
     #[clap(short = 'D', long = "delete-before")]
     delete_before_delimiter: Option<char>,
 
+    /// Which instance of the delimiter -d and -D act on, for lines with more than one, like
+    /// "123<TAB>word<TAB>tag". "all" splits the line into columns at every instance, and keeps only
+    /// the column next to the deleted part, so -D t makes that line "word".
+    #[clap(
+        long = "delimiter-occurrence",
+        value_parser = NamedValueParser::<DelimiterOccurrence>::new(["first", "last", "all"]),
+        default_value = "first"
+    )]
+    delimiter_occurrence: DelimiterOccurrence,
+
     /// Only take first N words from inputted word list.
     /// If two or more word lists are inputted, it will combine arbitrarily and then take first N words.
     #[clap(long = "take-first", value_parser=eval_list_length)]
@@ -148,6 +626,24 @@ struct Args {
     #[clap(long = "take-rand", value_parser=eval_list_length)]
     take_rand: Option<usize>,
 
+    /// How --take-rand, --print-rand, and --cut-to-power pick random words. "shuffle" shuffles
+    /// every word and keeps the first ones. "reservoir" uses reservoir sampling, a single pass
+    /// that only holds on to the words picked so far. "stride" takes evenly spaced words from a
+    /// random starting point, keeping their order. Words from weighted inputs (--input-weight)
+    /// are always picked by weight.
+    #[clap(
+        long = "rand-strategy",
+        value_parser = NamedValueParser::<RandStrategy>::new(["shuffle", "reservoir", "stride"]),
+        default_value = "shuffle"
+    )]
+    rand_strategy: RandStrategy,
+
+    /// Seed for random choices, like those of --take-rand and --print-rand, so that running
+    /// Tidy again with the same seed, inputs, and options picks the same words. Seeds may pick
+    /// different words in other versions of Tidy.
+    #[clap(long = "seed")]
+    rand_seed: Option<u64>,
+
     /// Whittle list exactly to a specified length, only taking minimum number of words
     /// from the beginning of inputted list(s).
     /// If the outputted list is not exactly the specified length, it will try again by taking a
@@ -167,10 +663,32 @@ struct Args {
     #[clap(short = 'W', long = "whittle-to")]
     whittle_to: Option<String>,
 
+    /// While whittling, save how far the whittle has got to this file after each guess, so that
+    /// an interrupted whittle can be picked up again with --resume. The file is deleted once the
+    /// whittle is done.
+    #[clap(long = "checkpoint", value_name = "FILE", requires = "whittle_to")]
+    checkpoint: Option<PathBuf>,
+
+    /// When no number of words to take makes a list of exactly the --whittle-to length, take the
+    /// fewest words that make a list that's too long, then cut it down to length: either by
+    /// removing words at random (rand), so the words cut aren't all the least common ones, or by
+    /// keeping the words taken first (first). Without this, Tidy stops with an error.
+    #[clap(
+        long = "whittle-finish",
+        value_parser = NamedValueParser::<CutStrategy>::new(["rand", "first"]),
+        requires = "whittle_to"
+    )]
+    whittle_finish: Option<CutStrategy>,
+
+    /// Pick up an interrupted whittle where it left off, from its --checkpoint file. Starts over
+    /// if there's no checkpoint yet, or if it's from a whittle of different inputs or options.
+    #[clap(long = "resume", requires = "checkpoint")]
+    resume: bool,
+
     /// Just before printing generated list, cut list down
     /// to a set number of words. Can accept expressions in the
     /// form of base**exponent (helpful for generating diceware lists).
-    /// Cuts are done randomly.
+    /// Cuts are done randomly (see --rand-strategy).
     #[clap(long = "print-rand", value_parser=eval_list_length)]
     print_rand: Option<usize>,
 
@@ -181,6 +699,54 @@ struct Args {
     #[clap(long = "print-first", value_parser=eval_list_length)]
     print_first: Option<usize>,
 
+    /// Just before printing generated list, cut list down to the largest power of the given
+    /// base that it has enough words for, like 7,776 (6^5) or 1,296 (6^4) for base 6, ready to
+    /// be assigned dice rolls. Words are chosen by --cut-strategy.
+    #[clap(
+        long = "cut-to-power",
+        value_name = "BASE",
+        value_parser = parse_power_base,
+        conflicts_with = "whittle_to"
+    )]
+    cut_to_power: Option<usize>,
+
+    /// Which words --cut-to-power keeps: a random selection (like --print-rand) or the words at
+    /// the beginning of the processed list (like --print-first)
+    #[clap(
+        long = "cut-strategy",
+        value_parser = NamedValueParser::<CutStrategy>::new(["random", "first"]),
+        default_value = "random",
+        requires = "cut_to_power"
+    )]
+    cut_strategy: CutStrategy,
+
+    /// Keep at most this many words that start with each letter. Helps
+    /// with lists made from corpora where many words share a first letter.
+    #[clap(long = "max-per-first-letter", value_name = "N")]
+    max_per_first_letter: Option<usize>,
+
+    /// Limit the share of the list taken up by words of some lengths, removing words over the
+    /// limit. Given as lengths and a maximum percent, separated by a colon: 8+:20 allows at most
+    /// 20 percent of words to have 8 or more characters, 3-4:10 at most 10 percent to have 3 or 4,
+    /// and 5:30 at most 30 percent to have 5. Can be given more than once.
+    #[clap(long = "length-quota", value_name = "LENGTHS:PERCENT")]
+    length_quotas: Vec<LengthQuota>,
+
+    /// Which words --length-quota removes first: the ones closest to the end of the list
+    /// (lowest-frequency, for lists sorted by word frequency), the longest, or random ones
+    #[clap(
+        long = "quota-priority",
+        value_parser = NamedValueParser::<QuotaPriority>::new(["lowest-frequency", "longest", "random"]),
+        default_value = "lowest-frequency",
+        requires = "length_quotas"
+    )]
+    quota_priority: QuotaPriority,
+
+    /// When cutting the list down with --print-rand, --print-first, or --cut-to-power,
+    /// give each first letter as even a share of the words as possible.
+    #[clap(long = "balance-first-letters")]
+    balance_first_letters: bool,
+
     /// Set minimum word length
     #[clap(short = 'm', long = "minimum-word-length")]
     minimum_length: Option<usize>,
@@ -202,13 +768,114 @@ struct Args {
     #[clap(short = 'x', long = "shared-prefix-length")]
     maximum_shared_prefix_length: Option<usize>,
 
-    /// Skip first number of lines from inputted files. Useful for dealing with headers like from
-    /// PGP signatures
+    /// Make words unique in their first N characters, like -x does, then
+    /// cut every word down to those N characters. Makes compact lists like
+    /// the 4-character prefixes of the BIP39 list.
+    #[clap(long = "truncate-to-unique", value_name = "N")]
+    truncate_to_unique_length: Option<usize>,
+
+    /// Do NOT automatically detect and remove PGP clearsign headers and signatures
+    /// from inputted files.
+    #[clap(long = "no-auto-pgp")]
+    no_auto_pgp: bool,
+
+    /// Remove comment lines, and comments at the end of lines, from inputted files. Optionally
+    /// takes the string that starts a comment, which defaults to '#'. Trailing comments must
+    /// be preceded by whitespace.
+    #[clap(long = "ignore-comments", num_args = 0..=1, default_missing_value = "#")]
+    ignore_comments: Option<String>,
+
+    /// Carry the comment lines at the top of inputted files into the outputted list.
+    /// Requires --ignore-comments.
+    #[clap(long = "preserve-comments", requires = "ignore_comments")]
+    preserve_comments: bool,
+
+    /// Specify the text encoding of inputted files: utf-8, latin1, windows-1252, utf-16, or
+    /// auto. Words are converted to UTF-8. If not set, inputted files are read as UTF-8 and
+    /// lines that are not valid UTF-8 are skipped.
+    #[clap(
+        long = "input-encoding",
+        value_parser = NamedValueParser::<InputEncoding>::new(["utf-8", "latin1", "windows-1252", "utf-16", "auto"])
+    )]
+    input_encoding: Option<InputEncoding>,
+
+    /// When using --input-encoding, exit with an error if an inputted file contains bytes that
+    /// are invalid in that encoding, rather than replacing them with �.
+    #[clap(long = "strict-encoding", requires = "input_encoding")]
+    strict_encoding: bool,
+
+    /// Memory-map inputted files rather than reading them line by line, and only copy the words
+    /// that make it through word modifications and removals. Uses much less memory on corpora
+    /// with tens of millions of lines. Inputted files must be local UTF-8 files. Needs Tidy
+    /// built with the mmap feature.
+    #[clap(
+        long = "mmap",
+        conflicts_with_all = [
+            "input_encoding",
+            "skip_rows_start",
+            "skip_rows_end",
+            "ignore_comments",
+            "input_weights",
+            "tag_source",
+            "whittle_to",
+        ]
+    )]
+    mmap: bool,
+
+    /// Treat inputted files as running text, like books or articles, rather than lists of one
+    /// word per line: split them into words, dropping punctuation, and tidy each word once, most
+    /// common first. With --ignore-after, each word is followed by the delimiter and the number
+    /// of times it appeared, for options that use word frequencies.
+    #[clap(
+        long = "tokenize",
+        conflicts_with_all = ["mmap", "input_weights", "tag_source"]
+    )]
+    tokenize: bool,
+
+    /// Count how many times each word appears in the inputted files, and print each word followed
+    /// by a tab and its count. Implies --tokenize, so use --no-sort to print the most common words
+    /// first. Counts of words that are tidied into the same word, like "The" and "the" with
+    /// --lowercase, are added together.
+    #[clap(
+        long = "count",
+        conflicts_with_all = [
+            "mmap",
+            "input_weights",
+            "tag_source",
+            "ignore_after_delimiter",
+            "ignore_before_delimiter",
+            "dice_sides",
+        ]
+    )]
+    count: bool,
+
+    /// Split inputted running text into phrases of this many words in a row, like "ice cream",
+    /// rather than single words, so each phrase is an entry on the list. Implies --tokenize.
+    /// Phrases don't run across punctuation or line breaks. Words of a phrase are joined with a
+    /// space, or with the separator given to --join-multiword.
+    #[clap(
+        long = "ngrams",
+        value_name = "N",
+        value_parser = parse_ngram_length,
+        conflicts_with_all = ["mmap", "input_weights", "tag_source", "remove_multiword", "field_number"]
+    )]
+    ngrams: Option<usize>,
+
+    /// Before tidying, drop repeated inputted lines, finding them with a Bloom filter. Uses much
+    /// less memory than keeping every line in a set, so it helps with huge inputs with many
+    /// repeated lines, like corpus dumps. Works best with --mmap.
+    #[clap(long = "bloom-dedup", conflicts_with = "whittle_to")]
+    bloom_dedup: bool,
+
+    /// Skip first number of lines from inputted files. Useful for dealing with headers.
+    /// PGP clearsign headers are removed automatically, and no lines are skipped from files
+    /// they were removed from (see --no-auto-pgp).
     #[clap(long = "skip-rows-start")]
     skip_rows_start: Option<usize>,
 
-    /// Skip last number of lines from inputted files. Useful for dealing with footers like from
-    /// PGP signatures.
+    /// Skip last number of lines from inputted files. Useful for dealing with footers.
+    /// PGP signatures are removed automatically, and no lines are skipped from files they
+    /// were removed from (see --no-auto-pgp).
     #[clap(long = "skip-rows-end")]
     skip_rows_end: Option<usize>,
 
@@ -222,11 +889,68 @@ struct Args {
     #[clap(short = 'a', long = "approve")]
     approved_list: Option<Vec<PathBuf>>,
 
+    /// Path(s) for optional list of words to put on the new list no matter
+    /// what other options would do with them. Warns if these words break
+    /// other requested constraints, like having no prefix words. Can accept
+    /// multiple files.
+    #[clap(long = "always-include")]
+    always_include: Option<Vec<PathBuf>>,
+
     /// Path(s) to file(s) containing homophone pairs. There must be one pair
     /// of homophones per line, separated by a comma (sun,son).
     #[clap(long = "homophones")]
     homophones_list: Option<Vec<PathBuf>>,
 
+    /// Keep only the first of each set of words that are anagrams of each other, like "lemon"
+    /// and "melon", which are easy to mix up when dictated or written down.
+    #[clap(long = "remove-anagrams")]
+    should_remove_anagrams: bool,
+
+    /// Only remove anagrams that are within this edit distance of an anagram kept earlier in
+    /// the list, like "melon" (2 edits from "lemon") but not "thing" (5 edits from "night").
+    #[clap(
+        long = "anagram-edit-distance",
+        value_name = "N",
+        requires = "should_remove_anagrams"
+    )]
+    anagram_edit_distance: Option<usize>,
+
+    /// Remove words that are leetspeak variants of other words on the list, or of words on a
+    /// reject list, like "p4ss" when "pass" is on the list. Of variants of a word that isn't on
+    /// either list, like "p4ss" and "pa55", keeps the first. Use --leet-substitutions to choose
+    /// which substitutions to undo.
+    #[clap(long = "remove-leet-variants")]
+    remove_leet_variants: bool,
+
+    /// Substitutions for --remove-leet-variants to undo, each a character, =, and the letter it
+    /// stands for, separated by commas. Defaults to "4=a,@=a,8=b,3=e,1=i,!=i,0=o,5=s,$=s,7=t".
+    #[clap(
+        long = "leet-substitutions",
+        value_delimiter = ',',
+        value_parser = parse_leet_substitution,
+        requires = "remove_leet_variants"
+    )]
+    leet_substitutions: Option<Vec<(char, char)>>,
+
+    /// Remove words that are probably inflections of another word on the list,
+    /// keeping the base form. For example, removes "cats" if "cat" is on the
+    /// list, and "running" if "run" is. Uses simple English suffix rules for
+    /// plurals and -ed and -ing forms, so check results with --find-inflections.
+    #[clap(long = "remove-inflections")]
+    should_remove_inflections: bool,
+
+    /// Of the words that share a stem in the given language, like "connect",
+    /// "connected", and "connection", keep only the first. Uses Snowball
+    /// stemmers, and needs Tidy to be built with the stemming feature.
+    /// Languages: arabic, danish, dutch, english, finnish, french, german,
+    /// greek, hungarian, italian, norwegian, portuguese, romanian, russian,
+    /// spanish, swedish, tamil, turkish.
+    #[clap(
+        long = "lemmatize",
+        value_parser = NamedValueParser::<String>::new(STEMMER_LANGUAGES)
+    )]
+    lemmatize_language: Option<String>,
+
     /// Print dice roll before word in output. Set number of sides
     /// of dice. Must be between 2 and 36. Use 6 for normal dice.
     #[clap(long = "dice")]
@@ -241,33 +965,432 @@ struct Args {
     #[clap(long = "sides-as-base")]
     print_dice_sides_as_their_base: bool,
 
+    /// Path to a file of words to keep at particular places on the new list,
+    /// like when updating a published list. Each line is a dice roll (or,
+    /// without --dice, a line number) and a word, separated by a tab. Other
+    /// words shift down to make room. Pinned words must
+    /// make it on to the new list; see --always-include.
+    #[clap(long = "pin-words")]
+    pin_words_file: Option<PathBuf>,
+
+    /// Path to an earlier version of the list to update. Words still on the new
+    /// list keep their places (and dice rolls), new words fill the places of
+    /// words that are gone, and Tidy prints a list of the changes. Anything up
+    /// to and including the first tab of each line, like a dice roll, is
+    /// ignored. --print-rand and --print-first keep earlier words where they can.
+    #[clap(long = "update-from")]
+    update_from: Option<PathBuf>,
+
+    /// Print the name of the inputted file each word came from after it,
+    /// separated by a tab. With --attributes, also print how many words came
+    /// from each file.
+    #[clap(long = "tag-source")]
+    tag_source: bool,
+
+    /// Give an inputted file a weight, like corpus.txt=0.5, to prefer words from
+    /// files with higher weights when cutting the list down with --take-first,
+    /// --take-rand, or --whittle-to. Files have a weight of 1 unless given one.
+    /// Can be used more than once.
+    #[clap(long = "input-weight", value_name = "FILE=WEIGHT", value_parser = parse_input_weight)]
+    input_weights: Vec<(PathBuf, f64)>,
+
+    /// When printing dice roll before word in output, also print a table
+    /// of the dice rolls that don't correspond to any word, which happens
+    /// when the list length isn't a power of the number of dice sides.
+    #[clap(long = "reroll-table", requires = "dice_sides")]
+    reroll_table: bool,
+
+    /// Separator to print between each dice roll (or card code or coin flips)
+    /// and its word. Use 't' for tab and 's' for space. Defaults to a tab.
+    #[clap(long = "dice-separator", value_parser = parse_separator)]
+    dice_separator: Option<String>,
+
+    /// Separator to print between each die of a dice roll. Use 't' for tab
+    /// and 's' for space. Defaults to a dash for dice with more than 9 sides
+    /// (03-11-01) and nothing for other dice (26555).
+    #[clap(long = "dice-digit-separator", value_parser = parse_separator, requires = "dice_sides")]
+    dice_digit_separator: Option<String>,
+
+    /// Don't left-pad faces of dice with more than 9 sides with a zero,
+    /// printing 3-11-1 rather than 03-11-01.
+    #[clap(long = "no-dice-digit-padding", requires = "dice_sides")]
+    unpadded_dice_digits: bool,
+
+    /// Print the new list in the given number of columns, to be read from
+    /// top to bottom, which is helpful for printing a physical diceware sheet.
+    /// Dice rolls are separated from words with a space rather than a tab.
+    #[clap(long = "print-columns")]
+    print_columns: Option<usize>,
+
+    /// Number of rows per page, for printing. In text, pages are separated by
+    /// a form feed character. If not given, the whole list is one page.
+    #[clap(long = "page-height")]
+    page_height: Option<usize>,
+
+    /// Format of outputted list: text (the default, one word per line),
+    /// html (a page with the list in a table, along with how it was made,
+    /// ready to print), or json (an object with the words and any dice
+    /// rolls, for programs). Most browsers can save the html page as a PDF.
+    /// Output files ending in .txt, .html, or .json are always written in
+    /// that format.
+    #[clap(
+        long = "format",
+        default_value = "text",
+        value_parser = NamedValueParser::<OutputFormat>::new(["text", "html", "json"])
+    )]
+    format: OutputFormat,
+
+    /// Print information about each word after it, separated by tabs. Choose
+    /// any of: length (number of characters), frequency (read from metadata
+    /// given with --ignore-after), and entropy-contribution (bits the word
+    /// contributes to the list's entropy per word). Separate choices with
+    /// commas, like length,frequency.
+    #[clap(
+        long = "annotate",
+        value_delimiter = ',',
+        value_parser = NamedValueParser::<Annotation>::new(["length", "entropy-contribution", "frequency"])
+    )]
+    annotations: Vec<Annotation>,
+
+    /// Also write a companion file for printing study sheets: a line for each word with its
+    /// dice roll (or line number), the word, and a memorization hint, separated by tabs.
+    #[clap(long = "companion")]
+    companion: Option<PathBuf>,
+
+    /// Fill in each hint of the companion file from this template, rather than leaving them
+    /// blank. {word}, {roll}, {first}, and {length} are replaced with the word, its dice roll,
+    /// its first character, and its number of characters.
+    #[clap(long = "companion-hint", requires = "companion")]
+    companion_hint: Option<String>,
+
+    /// Title of the page, when outputting html. Defaults to "Word list".
+    #[clap(long = "title")]
+    title: Option<String>,
+
     /// Print playing card abbreviation next to each word.
     /// Strongly recommend only use on lists with lengths that are powers
     /// of 26 (26^1, 26^2, 26^3, etc.)
     #[clap(long = "cards")]
     cards: bool,
 
+    /// Print coin flips next to each word, with H for heads and T for tails.
+    /// Strongly recommend only use on lists with lengths that are powers
+    /// of 2 (2^10, 2^13, etc.)
+    #[clap(long = "coins")]
+    coins: bool,
+
     /// Path for outputted list file. If none given, generated word list
-    /// will be printed to terminal.
+    /// will be printed to terminal. Can be given more than once to write the
+    /// list in more than one format: files ending in .txt, .html, or .json
+    /// are written in that format, and others in the --format.
     #[clap(short = 'o', long = "output")]
-    output: Option<PathBuf>,
+    output: Vec<PathBuf>,
+
+    /// Line endings to use in outputted list: lf (the default) or crlf (Windows-style).
+    #[clap(
+        long = "line-ending",
+        default_value = "lf",
+        value_parser = NamedValueParser::<LineEnding>::new(["lf", "crlf"])
+    )]
+    line_ending: LineEnding,
+
+    /// Text encoding of outputted list: utf-8 (the default), utf-16le, utf-16be, latin1, or
+    /// windows-1252. UTF-16 output starts with a byte order mark. Exits with an error if a word
+    /// contains a character that can not be represented in the chosen encoding.
+    #[clap(
+        long = "output-encoding",
+        default_value = "utf-8",
+        value_parser = NamedValueParser::<OutputEncoding>::new(["utf-8", "utf-16le", "utf-16be", "latin1", "windows-1252"])
+    )]
+    output_encoding: OutputEncoding,
 
     /// Force overwrite of output file if it exists.
     #[clap(short = 'f', long = "force")]
     force_overwrite: bool,
 
+    /// Instead of writing the new list to the output file, compare it to what's in the file now,
+    /// printing the lines that would be added, removed, or moved. Useful for checking that a new
+    /// version of Tidy doesn't change a published list.
+    #[clap(long = "diff", requires = "output", conflicts_with = "dry_run")]
+    diff: bool,
+
     /// Word list input files. Can be more than one, in which case
     /// they'll be combined and de-duplicated. Requires at least
-    /// one file.
+    /// one file. A directory reads every (non-hidden) file within it,
+    /// recursively. A quoted glob pattern, like "chapters/**/*.txt", reads
+    /// every matching file. If Tidy was built with the net feature, an
+    /// http:// or https:// URL downloads the word list at that address.
     #[clap(name = "Inputted Word Lists", required = true)]
     inputted_word_lists: Vec<PathBuf>,
 }
 
+/// Options for the sample passphrases printed by --samples
+#[derive(clap::Args, Debug)]
+struct SampleArgs {
+    /// Number of sample passphrases to print [default: 5]
+    #[clap(long = "sample-count", requires = "samples")]
+    sample_count: Option<usize>,
+
+    /// Number of words in each sample passphrase [default: 6]
+    #[clap(long = "sample-words", requires = "samples")]
+    sample_words: Option<usize>,
+
+    /// Separator between the words of each sample passphrase. Use 't' for tab and 's' for space
+    /// [default: space]
+    #[clap(long = "sample-separator", value_parser = parse_separator, requires = "samples")]
+    sample_separator: Option<String>,
+
+    /// How to capitalize the words of each sample passphrase [default: unchanged]
+    #[clap(
+        long = "sample-case",
+        value_parser = NamedValueParser::<SampleCase>::new(["unchanged", "lowercase", "uppercase", "capitalized"]),
+        requires = "samples"
+    )]
+    sample_case: Option<SampleCase>,
+
+    /// Write the sample passphrases, one per line, to this file rather than the terminal. Use -
+    /// to print them to stdout, so they can be piped to another program.
+    #[clap(long = "samples-output", requires = "samples")]
+    samples_output: Option<PathBuf>,
+
+    /// Also print zxcvbn's estimate of how strong each sample passphrase is, and, to compare,
+    /// the strength of a passphrase of as many words by its entropy. Needs Tidy to be built with
+    /// the strength feature.
+    #[clap(long = "sample-strength", requires = "samples")]
+    sample_strength: bool,
+}
+
+impl SampleArgs {
+    fn to_sample_options(&self) -> SampleOptions {
+        let defaults = SampleOptions::default();
+        SampleOptions {
+            passphrases: self.sample_count.unwrap_or(defaults.passphrases),
+            words_per_passphrase: self.sample_words.unwrap_or(defaults.words_per_passphrase),
+            separator: self.sample_separator.clone().unwrap_or(defaults.separator),
+            case: self.sample_case.unwrap_or(defaults.case),
+            output: self.samples_output.clone(),
+            estimate_strength: self.sample_strength,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a script that sets up tab completion of Tidy's options for the given shell
+    ///
+    /// For example, in bash: tidy completions bash > ~/.local/share/bash-completion/completions/tidy
+    Completions {
+        /// Shell to print completions for
+        shell: Shell,
+    },
+    /// Time each step Tidy can run on the inputted word lists, and measure the peak memory it
+    /// uses, to find which steps are slow on a big list before choosing options
+    ///
+    /// Each step is run on its own, on the de-duplicated inputted words. Steps that need a list
+    /// of words or delimiters from the user, like reject, are skipped.
+    Bench {
+        /// Minimum edit distance to time enforcing
+        #[clap(long = "minimum-edit-distance", default_value = "3")]
+        minimum_edit_distance: usize,
+
+        /// Shared prefix length to time enforcing, and truncating words to
+        #[clap(long = "shared-prefix-length", default_value = "4")]
+        shared_prefix_length: usize,
+
+        /// Only time these steps, given as a comma-separated list of step names
+        #[clap(
+            long = "steps",
+            value_delimiter = ',',
+            value_parser = NamedValueParser::<PipelineStep>::new(DEFAULT_PIPELINE.iter().map(|step| step.name()))
+        )]
+        steps: Option<Vec<PipelineStep>>,
+
+        /// Word list input files
+        #[clap(name = "Inputted Word Lists", required = true)]
+        inputted_word_lists: Vec<PathBuf>,
+    },
+    /// Print attributes of an existing word list, without changing it or making a new one
+    ///
+    /// The list is read as it is: its lines aren't de-duplicated, sorted, or tidied in any other
+    /// way, and blank lines are skipped. Prints the basic attributes unless told what to print.
+    Analyze {
+        /// Print attributes about the list. Can be used more than once to print more attributes
+        /// (up to 6 times, which adds letter and bigram distributions).
+        #[clap(short = 'A', long = "attributes", action = clap::ArgAction::Count)]
+        attributes: u8,
+
+        /// Separator to assume between the words of a passphrase when calculating attributes
+        #[clap(long = "separator", default_value = " ")]
+        separator: String,
+
+        /// Guesses per second to assume an attacker can make, when estimating how long passphrases
+        /// would take to crack. Defaults to a trillion.
+        #[clap(long = "guesses-per-second", default_value = "1e12", value_parser = parse_guesses_per_second)]
+        guesses_per_second: f64,
+
+        /// Print a handful of pseudorandomly selected words from the list. Should NOT be used as
+        /// secure passphrases.
+        #[clap(short = 's', long = "samples")]
+        samples: bool,
+
+        #[clap(flatten)]
+        sample_args: SampleArgs,
+
+        /// Ignore characters after the first instance of the specified delimiter on each line,
+        /// like word frequencies. Use 't' for tab and 's' for space.
+        #[clap(
+            short = 'g',
+            long = "ignore-after",
+            conflicts_with = "ignore_before_delimiter"
+        )]
+        ignore_after_delimiter: Option<char>,
+
+        /// Ignore characters before and including the first instance of the specified delimiter on
+        /// each line, like dice rolls. Use 't' for tab and 's' for space.
+        #[clap(short = 'G', long = "ignore-before")]
+        ignore_before_delimiter: Option<char>,
+
+        /// Word list files to analyze, as one list
+        #[clap(name = "Word Lists", required = true)]
+        word_lists: Vec<PathBuf>,
+    },
+    /// Download well-known word lists, so they can be given by name wherever Tidy takes a list
+    ///
+    /// The EFF's lists are checked against the copies built into Tidy. Other lists are checked
+    /// against the SHA-256 hash published for them. Lists are kept in tidy/lists in the user's
+    /// cache directory, or in TIDY_LIST_DIR if it's set. Needs Tidy to be built with the net
+    /// feature.
+    Fetch {
+        /// Names of the lists to download
+        #[clap(
+            name = "List Names",
+            required = true,
+            value_parser = NamedValueParser::<String>::new(REGISTRY.iter().map(|list| list.name))
+        )]
+        names: Vec<String>,
+
+        /// Download lists again, even if they've already been fetched
+        #[clap(long = "refresh")]
+        refresh: bool,
+    },
+    /// Check two finished lists for passphrases made of a word from the first followed by a
+    /// word from the second, like an adjective and a noun
+    ///
+    /// Reports words on both lists, pairs that spell the same thing as another pair when typed
+    /// without a separator, and the entropy of a pair. Exits with an error if the lists share
+    /// any words or have any such pairs.
+    CrossCheck {
+        /// Ignore characters after the first instance of the specified delimiter on each line,
+        /// like word frequencies. Use 't' for tab and 's' for space.
+        #[clap(
+            short = 'g',
+            long = "ignore-after",
+            conflicts_with = "ignore_before_delimiter"
+        )]
+        ignore_after_delimiter: Option<char>,
+
+        /// Ignore characters before and including the first instance of the specified delimiter on
+        /// each line, like dice rolls. Use 't' for tab and 's' for space.
+        #[clap(short = 'G', long = "ignore-before")]
+        ignore_before_delimiter: Option<char>,
+
+        /// List the first word of each passphrase is picked from
+        #[clap(name = "First List")]
+        first_list: PathBuf,
+
+        /// List the second word of each passphrase is picked from
+        #[clap(name = "Second List")]
+        second_list: PathBuf,
+    },
+}
+
 fn main() {
-    let opt = Args::parse();
-    if opt.debug {
-        eprintln!("Received args: {:?}", opt);
+    let matches = Args::command().get_matches();
+    let opt = match Args::from_arg_matches(&matches) {
+        Ok(opt) => opt,
+        Err(e) => e.exit(),
+    };
+    init_color(opt.color);
+    match opt.command {
+        Some(Command::Completions { shell }) => {
+            clap_complete::generate(shell, &mut Args::command(), "tidy", &mut io::stdout());
+            return;
+        }
+        Some(Command::Bench {
+            minimum_edit_distance,
+            shared_prefix_length,
+            ref steps,
+            ref inputted_word_lists,
+        }) => {
+            run_bench(
+                inputted_word_lists,
+                steps.as_deref().unwrap_or(&DEFAULT_PIPELINE),
+                &BenchSettings {
+                    minimum_edit_distance,
+                    shared_prefix_length,
+                },
+            );
+            return;
+        }
+        Some(Command::Analyze {
+            attributes,
+            ref separator,
+            guesses_per_second,
+            samples,
+            ref sample_args,
+            ignore_after_delimiter,
+            ignore_before_delimiter,
+            ref word_lists,
+        }) => {
+            run_analyze(
+                word_lists,
+                // With nothing else asked for, print the basic attributes
+                if attributes == 0 && !samples {
+                    1
+                } else {
+                    attributes
+                },
+                separator,
+                guesses_per_second,
+                if samples {
+                    Some(sample_args.to_sample_options())
+                } else {
+                    None
+                },
+                ignore_before_delimiter,
+                ignore_after_delimiter,
+            );
+            return;
+        }
+        Some(Command::Fetch { ref names, refresh }) => {
+            run_fetch(names, refresh);
+            return;
+        }
+        Some(Command::CrossCheck {
+            ignore_after_delimiter,
+            ignore_before_delimiter,
+            ref first_list,
+            ref second_list,
+        }) => {
+            run_cross_check(
+                first_list,
+                second_list,
+                ignore_before_delimiter,
+                ignore_after_delimiter,
+            );
+            return;
+        }
+        None => (),
     }
+    init_logging(
+        if opt.debug {
+            opt.verbose.max(2)
+        } else {
+            opt.verbose
+        },
+        opt.log_format,
+    );
+    log::debug!("Received args: {:?}", opt);
 
     // Some initial validations
     // Check given number of dice sides
@@ -279,17 +1402,45 @@ fn main() {
         }
     }
 
-    // Check if any of inputted_word_lists are directories
-    for file in &opt.inputted_word_lists {
-        if file.is_dir() {
-            eprintln!("Given file {:?} is a directory", file);
-            eprintln!("Exiting");
+    // Expand any directories or glob patterns into the files to read
+    let inputted_word_lists = match expand_input_paths(&opt.inputted_word_lists) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("{}", e);
             process::exit(1);
         }
+    };
+
+    if [opt.cards, opt.coins, opt.dice_sides.is_some()]
+        .iter()
+        .filter(|&&chosen| chosen)
+        .count()
+        > 1
+    {
+        eprintln!("Error: Cannot use more than one of dice, cards, and coins. Must be one of them or none.");
+        process::exit(1);
     }
 
-    if opt.cards && opt.dice_sides.is_some() {
-        eprintln!("Error: Cannot use dice and cards. Must be either cards or dice or neither.");
+    if opt.filter_cmd.is_some() && opt.filter_cmd_after.is_word_step() {
+        eprintln!(
+            "Error: --filter-cmd-after must name a step that depends on the whole list, like dedup, not {}.",
+            opt.filter_cmd_after.name()
+        );
+        process::exit(1);
+    }
+    if opt.ambiguity_report.is_some() && !opt.schlinkert_prune && !opt.check_decodable {
+        eprintln!(
+            "Error: --ambiguity-report only works with --schlinkert-prune or --check-decodable."
+        );
+        process::exit(1);
+    }
+
+    if opt.balance_first_letters
+        && opt.print_rand.is_none()
+        && opt.print_first.is_none()
+        && opt.cut_to_power.is_none()
+    {
+        eprintln!("Error: --balance-first-letters only works when cutting the list down with --print-rand, --print-first, or --cut-to-power.");
         process::exit(1);
     }
 
@@ -306,9 +1457,21 @@ fn main() {
         }
     }
 
-    // Check if output file exists
-    if let Some(ref output_file_name) = opt.output {
-        if !opt.force_overwrite && Path::new(output_file_name).exists() {
+    // Check if output files exist
+    for output_file_name in &opt.output {
+        if opt.diff && !Path::new(output_file_name).exists() {
+            eprintln!("Error: --diff needs an existing output file to compare the new list to.");
+            process::exit(1);
+        } else if !opt.diff && !opt.force_overwrite && Path::new(output_file_name).exists() {
+            eprintln!(
+                "Specified output file already exists. Use --force flag to force an overwrite."
+            );
+            return;
+        }
+    }
+    // The companion file isn't written when diffing
+    if let Some(ref companion) = opt.companion {
+        if !opt.diff && !opt.force_overwrite && companion.exists() {
             eprintln!(
                 "Specified output file already exists. Use --force flag to force an overwrite."
             );
@@ -316,11 +1479,25 @@ fn main() {
         }
     }
 
+    if opt.bip39
+        && opt.whittle_to.is_none()
+        && opt.print_rand.is_none()
+        && opt.print_first.is_none()
+        && opt.cut_to_power.is_none()
+        && !opt.quiet
+    {
+        eprintln!("RECOMMENDATION: BIP39 lists must be exactly 2,048 words long. Consider using --whittle-to 2048 or --print-rand 2048.\n");
+    }
+
     // Determine if this is a niche case in which whittle_to would be a smarter choice
     // than (either) print_first or print_rand.
     if (opt.print_first.is_some() || opt.print_rand.is_some())
         && opt.whittle_to.is_none()
-        && (opt.remove_prefix_words || opt.remove_suffix_words || opt.schlinkert_prune)
+        && (opt.remove_prefix_words
+            || opt.remove_suffix_words
+            || opt.remove_infix_words
+            || opt.schlinkert_prune
+            || opt.best_prune)
         && !opt.quiet
     {
         if opt.print_first.is_some() {
@@ -331,33 +1508,299 @@ fn main() {
         }
     }
 
+    // Phrases have spaces between their words, so a space can't also mark
+    // where a phrase's metadata starts
+    if opt.ngrams.is_some_and(|n| n > 1)
+        && [opt.ignore_after_delimiter, opt.ignore_before_delimiter]
+            .iter()
+            .any(|delimiter| delimiter.and_then(parse_delimiter) == Some(' '))
+    {
+        eprintln!("Error: --ngrams can't be used with a space as the metadata delimiter, since phrases have spaces in them. Try a tab (t) instead.");
+        process::exit(1);
+    }
+
+    // If mapping inputted files into memory, borrow their lines rather than
+    // reading them into Strings
+    let mapped_files: Vec<MappedFile> = if opt.mmap && !opt.plan {
+        inputted_word_lists
+            .iter()
+            .map(|filename| match map_file(filename) {
+                Ok(mapped_file) => mapped_file,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            })
+            .collect()
+    } else {
+        vec![]
+    };
+    let mapped_lines: Vec<&str> = mapped_files
+        .iter()
+        .zip(&inputted_word_lists)
+        .flat_map(|(mapped_file, filename)| {
+            let lines = mapped_file.lines(filename);
+            if opt.no_auto_pgp {
+                lines
+            } else {
+                strip_pgp_armor_from_slices(&lines)
+            }
+        })
+        .collect();
+
+    // Read each inputted file separately, so that words can be traced back
+    // to the file they came from (unless they've been mapped into memory)
+    let mut header_comments = vec![];
+    let lists_of_files: Vec<Vec<String>> = if opt.mmap {
+        vec![]
+    } else if opt.plan {
+        vec![vec![]; inputted_word_lists.len()]
+    } else {
+        inputted_word_lists
+            .iter()
+            .map(|filename| {
+                let lines = make_vec_from_filenames(
+                    &[filename.to_path_buf()],
+                    opt.skip_rows_start,
+                    opt.skip_rows_end,
+                    !opt.no_auto_pgp,
+                    None,
+                    opt.input_encoding,
+                    opt.strict_encoding,
+                );
+                match opt.ignore_comments {
+                    Some(ref comment_prefix) => {
+                        // Keep the comments at the top of the file before
+                        // they're removed, if asked to
+                        if opt.preserve_comments {
+                            header_comments.extend(find_header_comments(&lines, comment_prefix));
+                        }
+                        strip_comments(lines, comment_prefix)
+                    }
+                    None => lines,
+                }
+            })
+            .collect()
+    };
+
+    // If given weights for inputted files, combine the files so the ones with
+    // higher weights come first, and give each line its file's weight
+    let (list, line_weights) = if opt.input_weights.is_empty() {
+        (lists_of_files.concat(), None)
+    } else {
+        for (weighted_file, _weight) in &opt.input_weights {
+            if !inputted_word_lists.contains(weighted_file) {
+                eprintln!(
+                    "Error: Gave a weight for {}, which isn't one of the inputted files.",
+                    weighted_file.display()
+                );
+                process::exit(1);
+            }
+        }
+        let weights: Vec<f64> = inputted_word_lists
+            .iter()
+            .map(|filename| {
+                opt.input_weights
+                    .iter()
+                    .find(|(weighted_file, _weight)| weighted_file == filename)
+                    .map_or(1.0, |(_weighted_file, weight)| *weight)
+            })
+            .collect();
+        let merged = merge_weighted(&lists_of_files, &weights);
+        let line_weights = merged.iter().map(|(file, _line)| weights[*file]).collect();
+        (
+            merged.into_iter().map(|(_file, line)| line).collect(),
+            Some(line_weights),
+        )
+    };
+
+    // Split prose into its words, most common first. If the user is ignoring
+    // metadata after a delimiter, put each word's count there, so it can be
+    // used like a word frequency.
+    let word_counts = if opt.tokenize || opt.count || opt.ngrams.is_some() {
+        Some(tokenize_prose_into_ngrams(&list, opt.ngrams.unwrap_or(1)))
+    } else {
+        None
+    };
+    let list: Vec<String> = match word_counts {
+        Some(ref word_counts) => word_counts
+            .iter()
+            .map(
+                |(word, count)| match opt.ignore_after_delimiter.and_then(parse_delimiter) {
+                    Some(delimiter) => format!("{}{}{}", word, delimiter, count),
+                    None => word.to_string(),
+                },
+            )
+            .collect(),
+        None => list,
+    };
+
+    // On a first Ctrl-C, ask Tidy to stop, so it can print how far it got.
+    // On a second, quit right away.
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let handler_interrupted = interrupted.clone();
+    if let Err(e) = ctrlc::set_handler(move || {
+        if handler_interrupted.swap(true, Ordering::SeqCst) {
+            process::exit(130);
+        }
+        eprintln!("\nStopping... Press Ctrl-C again to quit right away.");
+    }) {
+        eprintln!("WARNING: Couldn't set a Ctrl-C handler: {}", e);
+    }
+
+    // Without --locale, words are sorted by the system's locale, so it goes
+    // in the cache key too
+    let locale = match opt.locale {
+        Some(ref lang) => lang.to_string(),
+        None => match get_system_lang() {
+            Some(lang) => lang,
+            None => "en-US".to_string(),
+        },
+    };
+    let mut key_options = given_options(&matches);
+    key_options.push(("resolved-locale".to_string(), vec![locale.clone()]));
+
+    // With --cache, look for a list an earlier run made from the same inputs
+    // and options. Lists picked at random without a seed, or read from URLs,
+    // aren't cached, since running again could make a different list.
+    let list_cache_key = match opt.cache {
+        Some(_) if picks_at_random(&opt) || inputted_word_lists.iter().any(|path| is_url(path)) => {
+            eprintln!("WARNING: Not caching this list, since it's read from a URL or cut at random without a --seed.\n");
+            None
+        }
+        Some(_) => match cache_key(&key_options, &inputted_word_lists) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                eprintln!("WARNING: Couldn't read a file to make a cache key, so not caching this list: {}\n", e);
+                None
+            }
+        },
+        None => None,
+    };
+    let cached_list = match (&opt.cache, &list_cache_key) {
+        (Some(cache_dir), Some(key)) => read_cached_list(cache_dir, key),
+        _ => None,
+    };
+    let from_cache = cached_list.is_some();
+
+    // Key a whittle's checkpoint to its inputs and options, so that a
+    // checkpoint from another whittle isn't resumed
+    let checkpoint_key = match opt.checkpoint {
+        Some(_) => match cache_key(&key_options, &inputted_word_lists) {
+            Ok(key) => Some(key),
+            Err(e) => {
+                eprintln!(
+                    "Error: Couldn't read a file to key the whittle checkpoint: {}",
+                    e
+                );
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let (whittle_resume_from, whittle_checkpoint) = match (&opt.checkpoint, &checkpoint_key) {
+        (Some(path), Some(key)) => {
+            let resume_from = if opt.resume {
+                read_whittle_checkpoint(path, key)
+            } else {
+                None
+            };
+            let (path, key) = (path.to_path_buf(), key.to_string());
+            let save = CheckpointCallback::new(move |checkpoint| {
+                if let Err(e) = fs::write(&path, format!("inputs {}\n{}", key, checkpoint)) {
+                    eprintln!(
+                        "WARNING: Couldn't save whittle checkpoint to {}: {}",
+                        path.display(),
+                        e
+                    );
+                }
+            });
+            (resume_from, Some(save))
+        }
+        _ => (None, None),
+    };
+
+    // Steps of the user's own, from --script and --filter-cmd
+    let mut custom_steps = vec![];
+    if let Some(ref script) = opt.script {
+        match load_script(script) {
+            Ok(script) => custom_steps.push((
+                opt.script_after,
+                CustomStep::transform_with_metadata("script", move |word, metadata| {
+                    match script.check(word, metadata) {
+                        Ok(new_word) => new_word,
+                        Err(e) => {
+                            eprintln!("Error: {}", e);
+                            process::exit(1);
+                        }
+                    }
+                }),
+            )),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+    if let Some(ref command) = opt.filter_cmd {
+        let command = command.to_string();
+        custom_steps.push((
+            opt.filter_cmd_after,
+            CustomStep::list("filter-cmd", move |list| {
+                match run_filter_command(&command, list) {
+                    Ok(new_list) => new_list,
+                    Err(e) => {
+                        eprintln!("Error: {}", e);
+                        process::exit(1);
+                    }
+                }
+            }),
+        ));
+    }
+
     // OK let's do this. Make a Tidy request.
     // While it's not declared as mutable here, we will reassign it
     // it later, unfortunately.
     let this_tidy_request = TidyRequest {
-        list: make_vec_from_filenames(
-            &opt.inputted_word_lists,
-            opt.skip_rows_start,
-            opt.skip_rows_end,
-        ),
+        list,
         take_first: opt.take_first,
         take_rand: opt.take_rand,
-        sort_alphabetically: !opt.no_alpha_sort,
+        line_weights,
+        sort_by: if opt.no_alpha_sort {
+            SortOrder::Unsorted
+        } else {
+            opt.sort_by.unwrap_or(SortOrder::Alphabetical)
+        },
+        reverse_sort: opt.reverse_sort,
+        should_bloom_dedup: opt.bloom_dedup,
+        dedup_ignore_case: if opt.dedup_ignore_case {
+            Some(opt.keep_case.unwrap_or_default())
+        } else {
+            None
+        },
+        dedup_keep: opt.dedup_keep,
         ignore_after_delimiter: opt.ignore_after_delimiter,
         ignore_before_delimiter: opt.ignore_before_delimiter,
+        keep_metadata: opt.keep_metadata,
         to_lowercase: opt.to_lowercase,
+        capitalization: opt.capitalization,
         normalization_form: opt.normalization_form,
-        locale: match opt.locale {
-            Some(lang) => lang,
-            None => match get_system_lang() {
-                Some(lang) => lang,
-                None => "en-US".to_string(),
-            },
-        },
+        locale,
         should_straighten_quotes: opt.straighten_quotes,
+        should_delete_apostrophes: opt.delete_apostrophes,
+        should_normalize_hyphens: opt.normalize_hyphens,
         should_remove_prefix_words: opt.remove_prefix_words,
         should_remove_suffix_words: opt.remove_suffix_words,
+        should_remove_infix_words: opt.remove_infix_words,
+        should_remove_compound_collisions: opt.remove_compound_collisions,
         should_schlinkert_prune: opt.schlinkert_prune,
+        schlinkert_strategy: opt.schlinkert_strategy,
+        schlinkert_timeout: opt.timeout.map(Duration::from_secs),
+        ambiguity_examples: opt
+            .ambiguity_report
+            .as_ref()
+            .map(|_path| opt.ambiguity_examples),
+        should_best_prune: opt.best_prune,
         should_remove_integers: opt.remove_integers,
         should_delete_integers: opt.delete_integers,
         should_remove_nonalphanumeric: opt.remove_nonalphanumeric,
@@ -365,37 +1808,181 @@ fn main() {
         should_remove_nonalphabetic: opt.remove_nonalphabetic,
         should_remove_non_latin_alphabetic: opt.remove_non_latin_alphabetic,
         should_remove_nonascii: opt.remove_nonascii,
+        should_remove_mixed_script: opt.remove_mixed_script,
+        should_remove_multiword: opt.remove_multiword,
+        join_multiword_separator: opt.join_multiword_separator,
+        confusable_chars: if opt.remove_confusable_chars {
+            Some(opt.confusable_chars.unwrap_or_else(|| {
+                DEFAULT_CONFUSABLE_CHARS
+                    .iter()
+                    .map(|chars| chars.to_string())
+                    .collect()
+            }))
+        } else {
+            None
+        },
+        should_remove_doubled_letters: opt.remove_doubled_letters,
+        consonant_clusters: if opt.remove_consonant_clusters {
+            Some(opt.consonant_clusters.unwrap_or_else(|| {
+                DEFAULT_CONSONANT_CLUSTERS
+                    .iter()
+                    .map(|cluster| cluster.to_string())
+                    .collect()
+            }))
+        } else {
+            None
+        },
+        gibberish_threshold: if opt.remove_gibberish {
+            Some(opt.gibberish_threshold)
+        } else {
+            None
+        },
         should_delete_after_first_delimiter: opt.delete_after_delimiter,
         should_delete_before_first_delimiter: opt.delete_before_delimiter,
+        field_number: opt.field_number,
+        field_separator: opt.field_separator,
+        delimiter_occurrence: opt.delimiter_occurrence,
 
         // If given more than one file of reject words, combine them
         // right here.
-        reject_list: opt
-            .reject_list
-            .map(|list_of_files| make_vec_from_filenames(&list_of_files, None, None)),
+        reject_list: opt.reject_list.map(|list_of_files| {
+            make_vec_from_filenames(
+                &list_of_files,
+                None,
+                None,
+                !opt.no_auto_pgp,
+                opt.ignore_comments.as_deref(),
+                opt.input_encoding,
+                opt.strict_encoding,
+            )
+            .into_iter()
+            .collect()
+        }),
+        always_include: opt.always_include.map(|list_of_files| {
+            make_vec_from_filenames(
+                &list_of_files,
+                None,
+                None,
+                !opt.no_auto_pgp,
+                opt.ignore_comments.as_deref(),
+                opt.input_encoding,
+                opt.strict_encoding,
+            )
+        }),
+        previous_list: opt.update_from.as_ref().map(|previous_list_file| {
+            make_vec_from_filenames(
+                &[previous_list_file.to_path_buf()],
+                None,
+                None,
+                !opt.no_auto_pgp,
+                opt.ignore_comments.as_deref(),
+                opt.input_encoding,
+                opt.strict_encoding,
+            )
+            .iter()
+            .map(|line| match line.split_once('\t') {
+                Some((_roll, word)) => word.trim().to_string(),
+                None => line.trim().to_string(),
+            })
+            .filter(|word| !word.is_empty())
+            .collect()
+        }),
         // Likewise with approved word lists
-        approved_list: opt
-            .approved_list
-            .map(|list_of_files| make_vec_from_filenames(&list_of_files, None, None)),
+        approved_list: opt.approved_list.map(|list_of_files| {
+            make_vec_from_filenames(
+                &list_of_files,
+                None,
+                None,
+                !opt.no_auto_pgp,
+                opt.ignore_comments.as_deref(),
+                opt.input_encoding,
+                opt.strict_encoding,
+            )
+            .into_iter()
+            .collect()
+        }),
         // And homophones
         homophones_list: opt
             .homophones_list
             .map(|list_of_files| read_homophones_list_from_filenames(&list_of_files)),
+        should_remove_anagrams: opt.should_remove_anagrams,
+        anagram_edit_distance: opt.anagram_edit_distance,
+        leet_substitutions: if opt.remove_leet_variants {
+            Some(
+                opt.leet_substitutions
+                    .unwrap_or_else(|| DEFAULT_LEET_SUBSTITUTIONS.to_vec()),
+            )
+        } else {
+            None
+        },
+        should_remove_inflections: opt.should_remove_inflections,
+        lemmatize_language: opt.lemmatize_language,
         minimum_length: opt.minimum_length,
         maximum_length: opt.maximum_length,
         maximum_shared_prefix_length: opt.maximum_shared_prefix_length,
+        truncate_to_unique_length: opt.truncate_to_unique_length,
         minimum_edit_distance: opt.minimum_edit_distance,
         print_rand: opt.print_rand,
+        max_per_first_letter: opt.max_per_first_letter,
+        length_quotas: opt.length_quotas,
+        quota_priority: opt.quota_priority,
+        balance_first_letters: opt.balance_first_letters,
         print_first: opt.print_first,
+        cut_to_power: opt.cut_to_power,
+        cut_strategy: opt.cut_strategy,
+        rand_strategy: opt.rand_strategy,
+        rand_seed: opt.rand_seed,
+        // Show progress bars for slow operations, unless user wants quiet
+        progress: if opt.quiet {
+            None
+        } else {
+            Some(make_progress_bars())
+        },
+        interrupted: Some(interrupted.clone()),
+        custom_steps,
+        whittle_resume_from,
+        whittle_checkpoint,
+        whittle_finish: opt.whittle_finish,
+        pipeline: match opt.pipeline {
+            Some(ref given_steps) => match complete_pipeline(given_steps) {
+                Ok(pipeline) => Some(pipeline),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            },
+            None => None,
+        },
     };
 
-    let (ignore_before_delimiter, ignore_after_delimiter) = match validate_and_parse_ignore_options(
+    if opt.plan {
+        println!("{}", make_plan(&matches, &opt, this_tidy_request).to_json());
+        return;
+    }
+
+    // If user wants a BIP39 list, tighten a few options to match
+    // the standard
+    let this_tidy_request = if opt.bip39 {
+        match apply_bip39_constraints(this_tidy_request) {
+            Ok(this_tidy_request) => this_tidy_request,
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    } else {
+        this_tidy_request
+    };
+
+    let (ignore_after_delimiter, ignore_before_delimiter) = match validate_and_parse_ignore_options(
         &this_tidy_request,
-        opt.dice_sides,
-        opt.print_dice_sides_as_their_base,
+        &OutputOptions {
+            dice_sides: opt.dice_sides,
+            print_dice_sides_as_their_base: opt.print_dice_sides_as_their_base,
+        },
     ) {
-        Ok((ignore_before_delimiter, ignore_after_delimiter)) => {
-            (ignore_before_delimiter, ignore_after_delimiter)
+        Ok((ignore_after_delimiter, ignore_before_delimiter)) => {
+            (ignore_after_delimiter, ignore_before_delimiter)
         }
         Err(e) => {
             eprintln!("{}", e);
@@ -405,7 +1992,7 @@ fn main() {
 
     // Parse provided "whittle string" for a length_to_whittle_to and an
     // optional starting point.
-    let (mut this_tidy_request, length_to_whittle_to, starting_point) =
+    let (this_tidy_request, length_to_whittle_to, starting_point) =
         match parse_whittle_options(this_tidy_request, opt.whittle_to) {
             Ok((this_tidy_request, length_to_whittle_to, starting_point)) => {
                 (this_tidy_request, length_to_whittle_to, starting_point)
@@ -418,60 +2005,548 @@ fn main() {
 
     // Finally get to actually tidy the inputted_word_list
     // If we have a length_to_whittle_to and a starting_point, we know we're
-    // whittling, which is (still) a bit too complicated for my tastes. But we
-    // need a while loop here.
-    let mut this_list_length = 0;
-    let tidied_list = match (length_to_whittle_to, starting_point) {
-        (Some(our_length_to_whittle_to), Some(mut our_starting_point)) => {
-            let mut this_tidied_list = vec![];
-            while this_list_length != our_length_to_whittle_to {
-                // Edit this_tidy_request to have our new starting point
-                this_tidy_request.take_first = Some(our_starting_point);
-
-                // This clone might be too expensice. maybe tidy_list can take a
-                // reference?
-                this_tidied_list = tidy_list(this_tidy_request.clone());
-
-                this_list_length = this_tidied_list.len();
-                our_starting_point = get_new_starting_point_guess(
-                    our_starting_point,
-                    this_list_length,
-                    our_length_to_whittle_to,
-                );
-                if opt.debug {
-                    eprintln!(
-                        "Whittled list to {}. Will try again, taking {} words.",
-                        this_list_length, our_starting_point
-                    );
-                }
+    // whittling.
+    let tidy_result = match (cached_list, length_to_whittle_to, starting_point) {
+        (Some(cached_list), _, _) => {
+            if !opt.quiet {
+                eprintln!("Using the list cached by an earlier run with the same inputs and options, so skipping tidying.\n");
             }
-            // Out of the loop, which means the list is the user-specified
-            // length. return this verison of the list.
-            this_tidied_list
+            Ok((cached_list, TidyReport::default()))
         }
-        (_, _) => {
+        (None, Some(our_length_to_whittle_to), Some(our_starting_point)) => whittle_list(
+            &this_tidy_request,
+            our_length_to_whittle_to,
+            our_starting_point,
+        ),
+        (None, _, _) => {
             // In all other cases, `whittle_to` option not specified, so
             // proceed as normal, sending all parameters in this_tidied_list
             // as they are just once.
-            tidy_list(this_tidy_request)
+            if opt.mmap {
+                tidy_lines_explained(&mapped_lines, &this_tidy_request)
+            } else {
+                tidy_list_explained(&this_tidy_request)
+            }
+        }
+    };
+    let (tidied_list, previous_list_changes, ambiguities) = match tidy_result {
+        Ok((tidied_list, report)) => {
+            if !from_cache {
+                display_tidy_report(&report, opt.explain, opt.audit, opt.debug);
+            }
+            (
+                tidied_list,
+                report.previous_list_changes,
+                report.ambiguities,
+            )
+        }
+        Err(TidyError::Interrupted(report)) => {
+            // Show how far Tidy got, so a long run isn't a total loss
+            display_tidy_report(&report, true, opt.audit, opt.debug);
+            eprintln!("{}", TidyError::Interrupted(report));
+            process::exit(130);
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+
+    // The whittle is done, so there's nothing to resume
+    if let Some(ref checkpoint) = opt.checkpoint {
+        if let Err(e) = fs::remove_file(checkpoint) {
+            if e.kind() != io::ErrorKind::NotFound {
+                eprintln!(
+                    "WARNING: Couldn't delete whittle checkpoint {}: {}\n",
+                    checkpoint.display(),
+                    e
+                );
+            }
+        }
+    }
+
+    if let (Some(cache_dir), Some(key), false) = (&opt.cache, &list_cache_key, from_cache) {
+        if let Err(e) = write_cached_list(cache_dir, key, &tidied_list) {
+            eprintln!(
+                "WARNING: Couldn't cache the list in {}: {}\n",
+                cache_dir.display(),
+                e
+            );
+        }
+    }
+
+    if let Some(ref report_path) = opt.ambiguity_report {
+        let mut report = String::new();
+        if opt.schlinkert_prune {
+            report += &format_ambiguity_examples(
+                "Ambiguous strings before Schlinkert prune",
+                &ambiguities,
+                &tidied_list,
+            );
+        }
+        if opt.check_decodable {
+            report += &format_ambiguity_examples(
+                "Ambiguous strings in new list",
+                &find_ambiguous_concatenations(&tidied_list, opt.ambiguity_examples),
+                &tidied_list,
+            );
+        }
+        if let Err(e) = fs::write(report_path, report) {
+            eprintln!(
+                "Error: Unable to write ambiguity report to {}: {}",
+                report_path.display(),
+                e
+            );
+            process::exit(1);
+        }
+    }
+
+    // If the list came up short, see which filter to relax to get closer to
+    // the length the user is aiming for
+    if let Some(target) = opt.suggest_to {
+        if tidied_list.len() < target {
+            let lines: Vec<&str> = if opt.mmap {
+                mapped_lines.to_vec()
+            } else {
+                this_tidy_request.list.iter().map(String::as_str).collect()
+            };
+            display_relaxation_suggestions(
+                &suggest_relaxations(&lines, &this_tidy_request, tidied_list.len()),
+                tidied_list.len(),
+                target,
+            );
+        }
+    }
+
+    // Move any pinned words to their rolls
+    let tidied_list = match opt.pin_words_file {
+        Some(ref pin_words_file) => {
+            let pin_lines = make_vec_from_filenames(
+                &[pin_words_file.to_path_buf()],
+                None,
+                None,
+                !opt.no_auto_pgp,
+                opt.ignore_comments.as_deref(),
+                opt.input_encoding,
+                opt.strict_encoding,
+            );
+            match parse_pinned_words(
+                &pin_lines,
+                opt.dice_sides,
+                opt.print_dice_sides_as_their_base,
+            )
+            .and_then(|pins| pin_words(tidied_list, &pins))
+            {
+                Ok(pinned_list) => pinned_list,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            }
         }
+        None => tidied_list,
     };
 
+    // Follow each word with its count, and treat the count as metadata from
+    // here on, so it's left out of checks of the words
+    let (tidied_list, ignore_after_delimiter) = match word_counts {
+        Some(ref word_counts) if opt.count => {
+            let counts = match count_tidied_words(&tidied_list, word_counts, &this_tidy_request) {
+                Ok(counts) => counts,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            };
+            (
+                tidied_list
+                    .into_iter()
+                    .zip(counts)
+                    .map(|(word, count)| format!("{}\t{}", word, count))
+                    .collect(),
+                Some('t'),
+            )
+        }
+        _ => (tidied_list, ignore_after_delimiter),
+    };
+
+    let sources = if opt.tag_source {
+        let source_names: Vec<String> = inputted_word_lists
+            .iter()
+            .map(|filename| filename.display().to_string())
+            .collect();
+        match find_word_sources(&tidied_list, &lists_of_files, &this_tidy_request) {
+            Ok(sources) => Some(
+                sources
+                    .into_iter()
+                    .map(|source| source.map(|source| source_names[source].clone()))
+                    .collect(),
+            ),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    // Check the list against anything the user asserted about it now, but
+    // only fail once it's printed, so that a failing list can be looked over
+    let list_assertions = ListAssertions {
+        length: opt.assert_length,
+        unique_prefix: opt.assert_unique_prefix,
+        free_of: opt.assert_free_of.as_ref().map(|list_of_files| {
+            make_vec_from_filenames(
+                list_of_files,
+                None,
+                None,
+                !opt.no_auto_pgp,
+                opt.ignore_comments.as_deref(),
+                opt.input_encoding,
+                opt.strict_encoding,
+            )
+            .into_iter()
+            .collect()
+        }),
+    };
+    let (words, _metadata) = split_words_and_metadata(
+        &tidied_list,
+        ignore_before_delimiter,
+        ignore_after_delimiter,
+    );
+    let assertion_failures = find_assertion_failures(&words, &list_assertions);
+
     // Next, we figure out what to print where
+    // HTML pages always show how the list was made
+    let writes_html = if opt.output.is_empty() {
+        opt.format == OutputFormat::Html
+    } else {
+        opt.output
+            .iter()
+            .any(|output| infer_output_format(output, opt.format) == OutputFormat::Html)
+    };
     let this_print_request = PrintRequest {
         tidied_list,
         dry_run: opt.dry_run,
+        diff: opt.diff,
         quiet: opt.quiet,
-        output: opt.output,
+        outputs: opt.output,
         cards: opt.cards,
+        coins: opt.coins,
         dice_sides: opt.dice_sides,
         print_dice_sides_as_their_base: opt.print_dice_sides_as_their_base,
+        dice_separator: opt.dice_separator,
+        dice_format: DiceFormat {
+            digit_separator: opt.dice_digit_separator,
+            unpadded_digits: opt.unpadded_dice_digits,
+        },
+        print_columns: opt.print_columns,
+        page_height: opt.page_height,
+        annotations: opt.annotations,
+        format: opt.format,
+        title: opt.title,
+        reroll_table: opt.reroll_table,
         attributes: opt.attributes,
+        guesses_per_second: opt.guesses_per_second,
         samples: opt.samples,
+        sample_options: opt.sample_args.to_sample_options(),
+        bip39: opt.bip39,
+        check_decodable: opt.check_decodable,
+        find_inflections: opt.find_inflections,
+        find_compound_collisions: opt.find_compound_collisions,
+        previous_list_changes,
+        sources,
+        warn_skew: opt.warn_skew,
+        length_advice: !opt.no_length_advice,
+        separator: opt.separator,
+        compare_to: match opt.compare_to {
+            Some(ref name_or_path) => match load_baseline_list(name_or_path) {
+                Ok(baseline) => Some((name_or_path.to_string(), baseline)),
+                Err(e) => {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                }
+            },
+            None => None,
+        },
+        checksum: opt.checksum,
+        checksum_header: opt.checksum_header,
+        preserved_comments: header_comments,
+        line_ending: opt.line_ending,
+        output_encoding: opt.output_encoding,
+        text_metadata_header: opt.metadata_header,
+        metadata_header: if opt.metadata_header || writes_html {
+            Some(make_metadata_header(
+                &env::args()
+                    .map(|arg| shell_quote(&arg))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                &inputted_word_lists,
+            ))
+        } else {
+            None
+        },
         ignore_before_delimiter,
         ignore_after_delimiter,
+        companion: opt.companion,
+        companion_hint: opt.companion_hint,
+    };
+    if let Err(e) = print_list(this_print_request) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+    if !assertion_failures.is_empty() {
+        for failure in &assertion_failures {
+            eprintln!("Error: {}", failure);
+        }
+        process::exit(1);
+    }
+}
+
+/// Reads the inputted word lists, then times each step on them, printing a
+/// line for each step as it finishes
+fn run_bench(inputted_word_lists: &[PathBuf], steps: &[PipelineStep], settings: &BenchSettings) {
+    let inputted_word_lists = match expand_input_paths(inputted_word_lists) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
     };
-    print_list(this_print_request);
+    let list = make_vec_from_filenames(&inputted_word_lists, None, None, true, None, None, false);
+    println!("Inputted lines: {}", list.len());
+    println!(
+        "{:<28} {:>12} {:>14} {:>12}",
+        "Step", "Time", "Peak memory", "Words left"
+    );
+    bench_stages(&list, steps, settings, &|benchmark| {
+        println!(
+            "{:<28} {:>12} {:>14} {:>12}",
+            benchmark.step.name(),
+            format!("{:.2?}", benchmark.elapsed),
+            match benchmark.peak_memory {
+                Some(bytes) => format!("{:.1} MB", bytes as f64 / 1_000_000.0),
+                None => "unknown".to_string(),
+            },
+            benchmark.words_left
+        );
+    });
+}
+
+/// Reads a finished list and prints the requested attributes and samples of
+/// it, as they'd be printed after making it
+fn run_analyze(
+    word_lists: &[PathBuf],
+    attributes: u8,
+    separator: &str,
+    guesses_per_second: f64,
+    samples: Option<SampleOptions>,
+    ignore_before_delimiter: Option<char>,
+    ignore_after_delimiter: Option<char>,
+) {
+    let word_lists = match expand_input_paths(word_lists) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    };
+    let list: Vec<String> =
+        make_vec_from_filenames(&word_lists, None, None, true, None, None, false)
+            .into_iter()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+    if list.is_empty() {
+        eprintln!("Error: No words to analyze.");
+        process::exit(1);
+    }
+    if attributes > 0 {
+        display_list_information(
+            &list,
+            attributes,
+            ignore_before_delimiter,
+            ignore_after_delimiter,
+            separator,
+            guesses_per_second,
+        );
+    }
+    if let Some(ref sample_options) = samples {
+        print_samples(
+            &list,
+            ignore_before_delimiter,
+            ignore_after_delimiter,
+            sample_options,
+            guesses_per_second,
+        );
+    }
+}
+
+/// Downloads each of the named lists Tidy knows of (see `registry`) that
+/// hasn't been fetched yet, or all of them if refreshing
+fn run_fetch(names: &[String], refresh: bool) {
+    for name in names {
+        let list = match find_registry_list(name) {
+            Some(list) => list,
+            None => {
+                eprintln!(
+                    "Error: Tidy doesn't know of a list called {}. Choose from: {}",
+                    name,
+                    REGISTRY
+                        .iter()
+                        .map(|list| list.name)
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                );
+                process::exit(1);
+            }
+        };
+        let path = fetched_list_path(list.name);
+        if path.exists() && !refresh {
+            eprintln!(
+                "Already fetched {} to {}. Use --refresh to download it again.",
+                list.name,
+                path.display()
+            );
+            continue;
+        }
+        match fetch_registry_list(list) {
+            Ok(path) => eprintln!(
+                "Fetched {}, {}, to {}",
+                list.name,
+                list.description,
+                path.display()
+            ),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// Reads two finished lists and checks that passphrases of a word from the
+/// first followed by a word from the second are unambiguous, exiting with an
+/// error if they aren't
+fn run_cross_check(
+    first_list: &Path,
+    second_list: &Path,
+    ignore_before_delimiter: Option<char>,
+    ignore_after_delimiter: Option<char>,
+) {
+    let read_words = |path: &Path| {
+        let lines: Vec<String> =
+            make_vec_from_filenames(&[path.to_path_buf()], None, None, true, None, None, false)
+                .into_iter()
+                .filter(|line| !line.trim().is_empty())
+                .collect();
+        if lines.is_empty() {
+            eprintln!("Error: No words in {}.", path.display());
+            process::exit(1);
+        }
+        split_words_and_metadata(&lines, ignore_before_delimiter, ignore_after_delimiter).0
+    };
+    let passed = display_cross_list_report(
+        &first_list.display().to_string(),
+        &read_words(first_list),
+        &second_list.display().to_string(),
+        &read_words(second_list),
+    );
+    if !passed {
+        process::exit(1);
+    }
+}
+
+/// Every option the user gave, or that has a default, named by its long
+/// name, with its values as given
+fn given_options(matches: &ArgMatches) -> Vec<(String, Vec<String>)> {
+    Args::command()
+        .get_arguments()
+        .filter_map(|arg| {
+            let id = arg.get_id().as_str();
+            let values = matches.get_raw(id)?;
+            let name = arg.get_long().unwrap_or(id).to_string();
+            Some((
+                name,
+                values.map(|v| v.to_string_lossy().to_string()).collect(),
+            ))
+        })
+        .collect()
+}
+
+/// Reads the whittle checkpoint saved to `path` by --checkpoint, if there is
+/// one and it's from a whittle of the inputs and options with the given key
+fn read_whittle_checkpoint(path: &Path, key: &str) -> Option<WhittleCheckpoint> {
+    let contents = fs::read_to_string(path).ok()?;
+    let (inputs, checkpoint) = contents.split_once('\n')?;
+    if inputs != format!("inputs {}", key) {
+        eprintln!(
+            "WARNING: Checkpoint {} is from a whittle of different inputs or options, so starting the whittle over.\n",
+            path.display()
+        );
+        return None;
+    }
+    match checkpoint.parse() {
+        Ok(checkpoint) => Some(checkpoint),
+        Err(e) => {
+            eprintln!("WARNING: {}, so starting the whittle over.\n", e);
+            None
+        }
+    }
+}
+
+/// Whether the options pick words at random without a seed, so that running
+/// Tidy again could make a different list
+fn picks_at_random(opt: &Args) -> bool {
+    opt.rand_seed.is_none()
+        && (opt.take_rand.is_some()
+            || opt.print_rand.is_some()
+            || (opt.cut_to_power.is_some() && opt.cut_strategy == CutStrategy::Random)
+            || opt.quota_priority == QuotaPriority::Random)
+}
+
+/// Works out what Tidy would do with the given options, and what problems
+/// with them would stop it, without reading any inputted word lists
+fn make_plan(matches: &ArgMatches, opt: &Args, mut req: TidyRequest) -> Plan {
+    let options = given_options(matches);
+    let mut problems = vec![];
+    if opt.bip39 {
+        match apply_bip39_constraints(req.clone()) {
+            Ok(bip39_req) => req = bip39_req,
+            Err(e) => problems.push(e),
+        }
+    }
+    if let Err(e) = validate_and_parse_ignore_options(
+        &req,
+        &OutputOptions {
+            dice_sides: opt.dice_sides,
+            print_dice_sides_as_their_base: opt.print_dice_sides_as_their_base,
+        },
+    ) {
+        problems.push(e);
+    }
+    if let Err(e) = validate_tidy_request(&req) {
+        problems.push(e.to_string());
+    }
+    let mut steps = find_planned_steps(&req);
+    if opt.whittle_to.is_some() {
+        steps.insert(0, "whittle");
+    }
+    Plan {
+        options,
+        steps,
+        problems,
+    }
+}
+
+/// Quote an argument the way a shell would need it, if it has whitespace or
+/// quotes in it, so the command in the metadata header can be run again
+fn shell_quote(arg: &str) -> String {
+    if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '\'' || c == '"') {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    } else {
+        arg.to_string()
+    }
 }
 
 /// Read LANG environmental variable, if possible
@@ -0,0 +1,107 @@
+//! Options that can't be used together. Each clash is reported by naming
+//! both of the options involved.
+use crate::TidyRequest;
+
+/// Options of the `tidy` command-line tool that aren't part of a
+/// `TidyRequest`, but can still clash with other options
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OutputOptions {
+    pub dice_sides: Option<u8>,
+    pub print_dice_sides_as_their_base: bool,
+}
+
+/// Whether a request, or the output options that go with it, uses an option
+pub type UsesOption = fn(&TidyRequest, &OutputOptions) -> bool;
+
+/// Options that can't be used with `--ignore-after` or `--ignore-before`,
+/// named as they're given on the command line, with a check for whether
+/// each is used. Word modifications and whole-list steps only see the word
+/// part of each line, but these options deal with the rest of the line too:
+/// deleting before or after a delimiter would be ambiguous with the
+/// metadata's own delimiter, and dice rolls would be printed next to the
+/// metadata.
+pub const IGNORE_DELIMITER_CONFLICTS: [(&str, UsesOption); 4] = [
+    ("--delete-before", |req, _| {
+        req.should_delete_before_first_delimiter.is_some()
+    }),
+    ("--delete-after", |req, _| {
+        req.should_delete_after_first_delimiter.is_some()
+    }),
+    ("--dice", |_, output| output.dice_sides.is_some()),
+    ("--sides-as-base", |_, output| {
+        output.print_dice_sides_as_their_base
+    }),
+];
+
+/// Finds the first option in `IGNORE_DELIMITER_CONFLICTS` that the request
+/// uses, if any.
+/// ```
+/// use tidy::option_conflicts::{find_ignore_delimiter_conflict, OutputOptions};
+/// use tidy::TidyRequest;
+/// let req = TidyRequest {
+///     ignore_after_delimiter: Some(','),
+///     should_delete_before_first_delimiter: Some(':'),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     find_ignore_delimiter_conflict(&req, &OutputOptions::default()),
+///     Some("--delete-before")
+/// );
+/// ```
+pub fn find_ignore_delimiter_conflict(
+    req: &TidyRequest,
+    output: &OutputOptions,
+) -> Option<&'static str> {
+    IGNORE_DELIMITER_CONFLICTS
+        .iter()
+        .find(|(_name, uses_option)| uses_option(req, output))
+        .map(|(name, _uses_option)| *name)
+}
+
+/// Checks that the request's ignore delimiters, if any, can be used with the
+/// rest of its options, returning the delimiters (after, before) if so. If
+/// not, the error names the two options that clash.
+/// ```
+/// use tidy::option_conflicts::{validate_and_parse_ignore_options, OutputOptions};
+/// use tidy::TidyRequest;
+/// let req = TidyRequest {
+///     ignore_before_delimiter: Some(','),
+///     to_lowercase: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     validate_and_parse_ignore_options(&req, &OutputOptions::default()),
+///     Ok((None, Some(',')))
+/// );
+/// let output = OutputOptions {
+///     dice_sides: Some(6),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     validate_and_parse_ignore_options(&req, &output),
+///     Err("Error: --ignore-before can't be used with --dice.".to_string())
+/// );
+/// ```
+pub fn validate_and_parse_ignore_options(
+    req: &TidyRequest,
+    output: &OutputOptions,
+) -> Result<(Option<char>, Option<char>), String> {
+    let ignore_option = match (req.ignore_after_delimiter, req.ignore_before_delimiter) {
+        (None, None) => return Ok((None, None)),
+        (Some(_), Some(_)) => {
+            return Err(
+                "Error: --ignore-after can't be used with --ignore-before, since metadata can only be ignored on one side of each word."
+                    .to_string(),
+            )
+        }
+        (Some(_), None) => "--ignore-after",
+        (None, Some(_)) => "--ignore-before",
+    };
+    match find_ignore_delimiter_conflict(req, output) {
+        Some(conflicting_option) => Err(format!(
+            "Error: {} can't be used with {}.",
+            ignore_option, conflicting_option
+        )),
+        None => Ok((req.ignore_after_delimiter, req.ignore_before_delimiter)),
+    }
+}
@@ -27,6 +27,171 @@ pub fn eval_list_length(input: &str) -> Result<usize, String> {
     }
 }
 
+use crate::dice::parse_dice_roll;
+use std::path::PathBuf;
+/// Parse the lines of a file of words to pin to dice rolls, each a roll
+/// and a word separated by a tab (or other whitespace), like `11111\tabbey`.
+/// Rolls are read as rolls of `dice_sides`-sided dice, as Tidy would print
+/// them (see `parse_dice_roll`), or, with no dice, as line numbers starting
+/// from 1. Returns zero-indexed positions and words.
+/// ```
+/// use tidy::parsers::parse_pinned_words;
+/// let lines = vec!["11112\tabbey".to_string(), "".to_string()];
+/// assert_eq!(parse_pinned_words(&lines, Some(6), false), Ok(vec![(1, "abbey".to_string())]));
+/// assert_eq!(parse_pinned_words(&lines, None, false), Ok(vec![(11111, "abbey".to_string())]));
+/// ```
+pub fn parse_pinned_words(
+    lines: &[String],
+    dice_sides: Option<u8>,
+    use_letters: bool,
+) -> Result<Vec<(usize, String)>, String> {
+    lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (roll, word) = line
+                .trim()
+                .split_once(char::is_whitespace)
+                .ok_or_else(|| format!("Pinned word line {:?} needs a roll and a word", line))?;
+            let position = match dice_sides {
+                Some(dice_sides) => parse_dice_roll(roll, dice_sides, use_letters)?,
+                None => match roll.parse::<usize>() {
+                    Ok(line_number) if line_number > 0 => line_number - 1,
+                    _ => return Err(format!("Can't read {:?} as a line number", roll)),
+                },
+            };
+            Ok((position, word.trim().to_string()))
+        })
+        .collect()
+}
+
+/// Parse an inputted file's weight, given as the file's path and a
+/// non-negative number separated by an equals sign.
+/// ```
+/// use std::path::PathBuf;
+/// use tidy::parsers::parse_input_weight;
+/// assert_eq!(parse_input_weight("curated.txt=2"), Ok((PathBuf::from("curated.txt"), 2.0)));
+/// assert!(parse_input_weight("corpus.txt").is_err());
+/// assert!(parse_input_weight("corpus.txt=-1").is_err());
+/// ```
+pub fn parse_input_weight(input: &str) -> Result<(PathBuf, f64), String> {
+    let (path, weight) = input.rsplit_once('=').ok_or_else(|| {
+        format!(
+            "Give a weight as FILE=WEIGHT, like corpus.txt=0.5, not {}",
+            input
+        )
+    })?;
+    match weight.trim().parse::<f64>() {
+        Ok(weight) if weight >= 0.0 && weight.is_finite() => Ok((PathBuf::from(path), weight)),
+        _ => Err(format!(
+            "Unable to parse weight {}. Weights must be numbers of 0 or more.",
+            weight
+        )),
+    }
+}
+
+/// Parse a separator to print in the outputted list. Like the delimiter
+/// options, a lone 't' stands for a tab and a lone 's' for a space. Any
+/// other input is used as it is.
+/// ```
+/// use tidy::parsers::parse_separator;
+/// assert_eq!(parse_separator("t"), Ok("\t".to_string()));
+/// assert_eq!(parse_separator(" - "), Ok(" - ".to_string()));
+/// ```
+pub fn parse_separator(input: &str) -> Result<String, String> {
+    match input {
+        "t" => Ok("\t".to_string()),
+        "s" => Ok(" ".to_string()),
+        _ => Ok(input.to_string()),
+    }
+}
+
+/// Parses the column number given to `--field`. Columns are counted from 1,
+/// like in awk.
+/// ```
+/// use tidy::parsers::parse_field_number;
+/// assert_eq!(parse_field_number("2"), Ok(2));
+/// assert!(parse_field_number("0").is_err());
+/// ```
+pub fn parse_field_number(input: &str) -> Result<usize, String> {
+    match input.parse::<usize>() {
+        Ok(0) | Err(_) => Err(format!(
+            "Unable to parse field {}. Fields are numbered from 1.",
+            input
+        )),
+        Ok(number) => Ok(number),
+    }
+}
+
+/// Parses the number of words in each phrase given to `--ngrams`, which
+/// must be at least 1.
+/// ```
+/// use tidy::parsers::parse_ngram_length;
+/// assert_eq!(parse_ngram_length("2"), Ok(2));
+/// assert!(parse_ngram_length("0").is_err());
+/// ```
+pub fn parse_ngram_length(input: &str) -> Result<usize, String> {
+    match input.parse::<usize>() {
+        Ok(0) | Err(_) => Err(format!(
+            "Unable to parse n-gram length {}. Phrases must have at least 1 word.",
+            input
+        )),
+        Ok(number) => Ok(number),
+    }
+}
+
+/// Parses one of the substitutions given to `--leet-substitutions`: a
+/// character, "=", and the letter it stands for.
+/// ```
+/// use tidy::parsers::parse_leet_substitution;
+/// assert_eq!(parse_leet_substitution("4=a"), Ok(('4', 'a')));
+/// assert!(parse_leet_substitution("4a").is_err());
+/// ```
+pub fn parse_leet_substitution(input: &str) -> Result<(char, char), String> {
+    let chars: Vec<char> = input.chars().collect();
+    match chars[..] {
+        [leet, '=', letter] => Ok((leet, letter)),
+        _ => Err(format!(
+            "Unable to parse leet substitution {}. Write it as a character, =, and the letter it stands for, like 4=a.",
+            input
+        )),
+    }
+}
+
+/// Parses the guessing rate given to `--guesses-per-second`, which can be
+/// written in scientific notation, like `1e12`.
+/// ```
+/// use tidy::parsers::parse_guesses_per_second;
+/// assert_eq!(parse_guesses_per_second("1e9"), Ok(1_000_000_000.0));
+/// assert!(parse_guesses_per_second("0").is_err());
+/// ```
+pub fn parse_guesses_per_second(input: &str) -> Result<f64, String> {
+    match input.parse::<f64>() {
+        Ok(rate) if rate > 0.0 && rate.is_finite() => Ok(rate),
+        _ => Err(format!(
+            "Unable to parse guessing rate {}. It must be a number above 0, like 1e12.",
+            input
+        )),
+    }
+}
+
+/// Parses the base given to `--cut-to-power`. Only bases of 2 or more have
+/// powers other than 1 to cut a list to.
+/// ```
+/// use tidy::parsers::parse_power_base;
+/// assert_eq!(parse_power_base("6"), Ok(6));
+/// assert!(parse_power_base("1").is_err());
+/// ```
+pub fn parse_power_base(input: &str) -> Result<usize, String> {
+    match input.parse::<usize>() {
+        Ok(base) if base >= 2 => Ok(base),
+        _ => Err(format!(
+            "Unable to parse base {}. It must be a whole number of 2 or more.",
+            input
+        )),
+    }
+}
+
 use crate::split_and_vectorize;
 use crate::TidyRequest;
 pub fn parse_whittle_options(
@@ -92,3 +257,53 @@ pub fn parse_whittle_options(
         None => Ok((this_tidy_request, None, None)),
     }
 }
+
+use clap::builder::{PossibleValue, TypedValueParser};
+use std::ffi::OsStr;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+/// A clap value parser for options that take one of a set of named values,
+/// like `--line-ending`. Values are parsed with the type's `FromStr`, which
+/// may accept more than the given names (like aliases or other casings). The
+/// names are listed in `--help` and offered by shell completions.
+#[derive(Clone)]
+pub struct NamedValueParser<T> {
+    names: Vec<&'static str>,
+    value_type: PhantomData<T>,
+}
+
+impl<T> NamedValueParser<T> {
+    pub fn new(names: impl IntoIterator<Item = &'static str>) -> Self {
+        NamedValueParser {
+            names: names.into_iter().collect(),
+            value_type: PhantomData,
+        }
+    }
+}
+
+impl<T> TypedValueParser for NamedValueParser<T>
+where
+    T: FromStr + Clone + Send + Sync + 'static,
+    T::Err: Display,
+{
+    type Value = T;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        _arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<T, clap::Error> {
+        let value = value.to_string_lossy();
+        value.parse::<T>().map_err(|e| {
+            clap::Error::raw(clap::error::ErrorKind::InvalidValue, format!("{}\n", e)).with_cmd(cmd)
+        })
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            self.names.iter().map(|name| PossibleValue::new(*name)),
+        ))
+    }
+}
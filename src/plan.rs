@@ -0,0 +1,88 @@
+//! Describe what Tidy would do with the given options, without reading any
+//! inputted word lists, for `--dry-run --plan`
+use crate::logging::escape_json;
+use crate::pipeline::DEFAULT_PIPELINE;
+use crate::{is_requested, TidyRequest};
+
+/// What Tidy would do: the options it was given (including defaults), the
+/// steps it would run, in order, and any problems with the options that
+/// would stop it from making a list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Plan {
+    pub options: Vec<(String, Vec<String>)>,
+    pub steps: Vec<&'static str>,
+    pub problems: Vec<String>,
+}
+
+impl Plan {
+    /// Formats the plan as a JSON object.
+    /// ```
+    /// use tidy::plan::Plan;
+    /// let plan = Plan {
+    ///     options: vec![("minimum-word-length".to_string(), vec!["3".to_string()])],
+    ///     steps: vec!["minimum-length", "dedup"],
+    ///     problems: vec![],
+    /// };
+    /// assert_eq!(
+    ///     plan.to_json(),
+    ///     r#"{"options":{"minimum-word-length":["3"]},"steps":["minimum-length","dedup"],"problems":[]}"#
+    /// );
+    /// ```
+    pub fn to_json(&self) -> String {
+        let options: Vec<String> = self
+            .options
+            .iter()
+            .map(|(name, values)| format!("\"{}\":{}", escape_json(name), json_array(values)))
+            .collect();
+        format!(
+            "{{\"options\":{{{}}},\"steps\":{},\"problems\":{}}}",
+            options.join(","),
+            json_array(&self.steps),
+            json_array(&self.problems)
+        )
+    }
+}
+
+fn json_array<S: AsRef<str>>(strings: &[S]) -> String {
+    let quoted: Vec<String> = strings
+        .iter()
+        .map(|s| format!("\"{}\"", escape_json(s.as_ref())))
+        .collect();
+    format!("[{}]", quoted.join(","))
+}
+
+/// The names of the steps Tidy would run for the request, in order,
+/// starting with any truncation of the inputted lists.
+/// ```
+/// use tidy::plan::find_planned_steps;
+/// use tidy::TidyRequest;
+/// let req = TidyRequest {
+///     take_first: Some(100),
+///     to_lowercase: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(find_planned_steps(&req), vec!["take-first", "lowercase", "dedup"]);
+/// ```
+pub fn find_planned_steps(req: &TidyRequest) -> Vec<&'static str> {
+    let mut steps = vec![];
+    if req.take_first.is_some() {
+        steps.push("take-first");
+    }
+    if req.take_rand.is_some() {
+        steps.push("take-rand");
+    }
+    if req.should_bloom_dedup {
+        steps.push("bloom-dedup");
+    }
+    let pipeline = match req.pipeline {
+        Some(ref pipeline) => pipeline.as_slice(),
+        None => &DEFAULT_PIPELINE,
+    };
+    steps.extend(
+        pipeline
+            .iter()
+            .filter(|step| is_requested(**step, req))
+            .map(|step| step.name()),
+    );
+    steps
+}
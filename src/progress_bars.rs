@@ -0,0 +1,37 @@
+//! Show progress through Tidy's slower operations as progress bars
+use crate::progress::ProgressCallback;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::sync::Mutex;
+
+/// Makes a callback that draws a progress bar, with an estimate of the time
+/// left, for each slow task Tidy reports progress on. Bars are drawn to
+/// stderr, only if it's a terminal, and cleared once their task is done.
+pub fn make_progress_bars() -> ProgressCallback {
+    let current_bar: Mutex<Option<(String, ProgressBar)>> = Mutex::new(None);
+    ProgressCallback::new(move |task, done, total| {
+        let mut current_bar = current_bar.lock().unwrap();
+        let is_new_task = match *current_bar {
+            Some((ref current_task, _)) => current_task != task,
+            None => true,
+        };
+        if is_new_task {
+            let bar = ProgressBar::new(total as u64);
+            bar.set_style(
+                ProgressStyle::with_template(
+                    "{msg:<32} [{bar:40}] {human_pos}/{human_len} (ETA {eta})",
+                )
+                .unwrap()
+                .progress_chars("=> "),
+            );
+            bar.set_message(task.to_string());
+            *current_bar = Some((task.to_string(), bar));
+        }
+        if let Some((_, ref bar)) = *current_bar {
+            bar.set_length(total as u64);
+            bar.set_position(done as u64);
+            if done >= total {
+                bar.finish_and_clear();
+            }
+        }
+    })
+}
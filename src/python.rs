@@ -0,0 +1,173 @@
+//! Python bindings, as a `tidy_wordlists` module, so that lists can be
+//! tidied, and their attributes calculated, in-process from Python. Built
+//! with the `python` feature, usually by maturin (see `pyproject.toml`).
+//!
+//! ```python
+//! import tidy_wordlists
+//! words = tidy_wordlists.tidy_list(["Apple", "apple", "b"], to_lowercase=True, minimum_length=2)
+//! tidy_wordlists.entropy_per_word(len(words))
+//! tidy_wordlists.dice_roll(0, 7776)  # "11111"
+//! ```
+use crate::dice::print_as_dice;
+use crate::display_information::uniquely_decodable::check_decodability;
+use crate::display_information::{
+    assumed_entropy_per_character, calc_entropy_per_word, efficiency_per_character,
+    find_longest_shared_prefix, find_mean_edit_distance, get_shortest_word_length,
+    mean_word_length,
+};
+use crate::list_manipulations::SortOrder;
+use crate::{tidy_list_explained, TidyRequest};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Tidies a list of words, the way the `tidy` command-line tool would with
+/// the matching options. Raises a `ValueError` if the options are invalid.
+#[pyfunction]
+#[pyo3(
+    name = "tidy_list",
+    signature = (
+        words,
+        *,
+        to_lowercase = false,
+        straighten_quotes = false,
+        remove_prefix_words = false,
+        remove_suffix_words = false,
+        schlinkert_prune = false,
+        remove_nonalphanumeric = false,
+        delete_nonalphanumeric = false,
+        remove_nonalphabetic = false,
+        remove_nonascii = false,
+        remove_integers = false,
+        delete_integers = false,
+        minimum_length = None,
+        maximum_length = None,
+        shared_prefix_length = None,
+        minimum_edit_distance = None,
+        print_first = None,
+        print_rand = None,
+        sort_alphabetically = false,
+        locale = "en-US".to_string(),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+fn py_tidy_list(
+    words: Vec<String>,
+    to_lowercase: bool,
+    straighten_quotes: bool,
+    remove_prefix_words: bool,
+    remove_suffix_words: bool,
+    schlinkert_prune: bool,
+    remove_nonalphanumeric: bool,
+    delete_nonalphanumeric: bool,
+    remove_nonalphabetic: bool,
+    remove_nonascii: bool,
+    remove_integers: bool,
+    delete_integers: bool,
+    minimum_length: Option<usize>,
+    maximum_length: Option<usize>,
+    shared_prefix_length: Option<usize>,
+    minimum_edit_distance: Option<usize>,
+    print_first: Option<usize>,
+    print_rand: Option<usize>,
+    sort_alphabetically: bool,
+    locale: String,
+) -> PyResult<Vec<String>> {
+    let req = TidyRequest {
+        list: words,
+        to_lowercase,
+        should_straighten_quotes: straighten_quotes,
+        should_remove_prefix_words: remove_prefix_words,
+        should_remove_suffix_words: remove_suffix_words,
+        should_schlinkert_prune: schlinkert_prune,
+        should_remove_nonalphanumeric: remove_nonalphanumeric,
+        should_delete_nonalphanumeric: delete_nonalphanumeric,
+        should_remove_nonalphabetic: remove_nonalphabetic,
+        should_remove_nonascii: remove_nonascii,
+        should_remove_integers: remove_integers,
+        should_delete_integers: delete_integers,
+        minimum_length,
+        maximum_length,
+        maximum_shared_prefix_length: shared_prefix_length,
+        minimum_edit_distance,
+        print_first,
+        print_rand,
+        sort_by: if sort_alphabetically {
+            SortOrder::Alphabetical
+        } else {
+            SortOrder::Unsorted
+        },
+        locale,
+        ..Default::default()
+    };
+    match tidy_list_explained(&req) {
+        Ok((list, _report)) => Ok(list),
+        Err(e) => Err(PyValueError::new_err(e.to_string())),
+    }
+}
+
+/// Bits of entropy each word of a list of the given length adds to a
+/// passphrase
+#[pyfunction]
+fn entropy_per_word(list_length: usize) -> f64 {
+    calc_entropy_per_word(list_length)
+}
+
+#[pyfunction]
+fn mean_length(words: Vec<String>) -> f32 {
+    mean_word_length(&words)
+}
+
+#[pyfunction]
+fn shortest_word_length(words: Vec<String>) -> usize {
+    get_shortest_word_length(&words)
+}
+
+#[pyfunction]
+fn mean_edit_distance(words: Vec<String>) -> f64 {
+    find_mean_edit_distance(&words)
+}
+
+#[pyfunction]
+fn longest_shared_prefix(words: Vec<String>) -> usize {
+    find_longest_shared_prefix(&words)
+}
+
+#[pyfunction]
+fn entropy_per_character(words: Vec<String>) -> f64 {
+    assumed_entropy_per_character(&words)
+}
+
+#[pyfunction]
+fn efficiency(words: Vec<String>) -> f64 {
+    efficiency_per_character(&words)
+}
+
+#[pyfunction]
+fn is_uniquely_decodable(words: Vec<String>) -> bool {
+    check_decodability(&words)
+}
+
+/// The dice roll for the word at `index` of a list of `list_length` words,
+/// like "11111" for the first word of a 7,776-word list. Raises a
+/// `ValueError` if dice can't have that many sides.
+#[pyfunction]
+#[pyo3(signature = (index, list_length, sides = 6, use_letters = false))]
+fn dice_roll(index: usize, list_length: usize, sides: u8, use_letters: bool) -> PyResult<String> {
+    print_as_dice(index, sides, list_length, use_letters)
+        .map_err(|e| PyValueError::new_err(e.to_string()))
+}
+
+#[pymodule]
+fn tidy_wordlists(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(py_tidy_list, m)?)?;
+    m.add_function(wrap_pyfunction!(entropy_per_word, m)?)?;
+    m.add_function(wrap_pyfunction!(mean_length, m)?)?;
+    m.add_function(wrap_pyfunction!(shortest_word_length, m)?)?;
+    m.add_function(wrap_pyfunction!(mean_edit_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(longest_shared_prefix, m)?)?;
+    m.add_function(wrap_pyfunction!(entropy_per_character, m)?)?;
+    m.add_function(wrap_pyfunction!(efficiency, m)?)?;
+    m.add_function(wrap_pyfunction!(is_uniquely_decodable, m)?)?;
+    m.add_function(wrap_pyfunction!(dice_roll, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,156 @@
+//! Well-known word lists that Tidy can download with `tidy fetch`, and where
+//! it keeps them, so they can be given by name wherever Tidy takes a list
+use crate::checksum::bytes_checksum;
+use crate::compare::builtin_list;
+use crate::file_readers::fetch_url;
+use std::env;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// How to check that a downloaded list is the list it should be
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListCheck {
+    /// Its words must match the copy of the list built into Tidy with this
+    /// name (see `compare::BUILTIN_LISTS`)
+    Builtin(&'static str),
+    /// Its SHA-256 hash, as lowercase hexadecimal, must be this one. If the
+    /// hash is empty, no download has been pinned yet, so none is trusted.
+    Sha256(&'static str),
+}
+
+/// The SHA-256 of Alan Beale's list, as published at its URL. Empty until a
+/// hash from a trusted download is pinned here.
+pub const BEALE_SHA256: &str = "";
+/// The SHA-256 of the Orchard Street Long list, as published at its URL.
+/// Empty until a hash from a trusted download is pinned here.
+pub const ORCHARD_SHA256: &str = "";
+
+/// A word list Tidy can download
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegistryList {
+    pub name: &'static str,
+    pub url: &'static str,
+    pub description: &'static str,
+    pub check: ListCheck,
+}
+
+/// The word lists Tidy can download, by name
+pub const REGISTRY: [RegistryList; 4] = [
+    RegistryList {
+        name: "eff-long",
+        url: "https://www.eff.org/files/2016/07/18/eff_large_wordlist.txt",
+        description: "EFF's long list (7,776 words, with dice rolls)",
+        check: ListCheck::Builtin("eff-long"),
+    },
+    RegistryList {
+        name: "eff-short",
+        url: "https://www.eff.org/files/2016/09/08/eff_short_wordlist_1.txt",
+        description: "EFF's first short list (1,296 words, with dice rolls)",
+        check: ListCheck::Builtin("eff-short"),
+    },
+    RegistryList {
+        name: "beale",
+        url: "https://theworld.com/~reinhold/beale.wordlist.asc",
+        description: "Alan Beale's diceware list (7,776 words, with dice rolls, PGP-signed)",
+        check: ListCheck::Sha256(BEALE_SHA256),
+    },
+    RegistryList {
+        name: "orchard",
+        url: "https://raw.githubusercontent.com/sts10/orchard-street-wordlists/main/lists/orchard-street-long.txt",
+        description: "Orchard Street Long list",
+        check: ListCheck::Sha256(ORCHARD_SHA256),
+    },
+];
+
+/// Gets one of the `REGISTRY` lists by name, if there is one by that name
+/// ```
+/// use tidy::registry::find_registry_list;
+/// assert!(find_registry_list("eff-short").unwrap().url.starts_with("https://www.eff.org/"));
+/// assert!(find_registry_list("no-such-list").is_none());
+/// ```
+pub fn find_registry_list(name: &str) -> Option<&'static RegistryList> {
+    REGISTRY.iter().find(|list| list.name == name)
+}
+
+/// The directory fetched lists are kept in: `tidy/lists` in the
+/// `XDG_CACHE_HOME` directory, or `~/.cache` (or, on Windows,
+/// `LOCALAPPDATA`) if that isn't set. `TIDY_LIST_DIR` overrides it.
+pub fn registry_dir() -> PathBuf {
+    if let Some(dir) = env::var_os("TIDY_LIST_DIR") {
+        return PathBuf::from(dir);
+    }
+    env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .unwrap_or_else(env::temp_dir)
+        .join("tidy")
+        .join("lists")
+}
+
+/// Where the list with the given name is kept once fetched
+pub fn fetched_list_path(name: &str) -> PathBuf {
+    fetched_list_path_in(&registry_dir(), name)
+}
+
+/// Where the list with the given name is kept once fetched into `dir`
+pub fn fetched_list_path_in(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.txt", name))
+}
+
+/// Downloads a list, checks it (see `ListCheck`), and keeps it in the
+/// `registry_dir`. Returns where it was kept. Returns an error message if
+/// it can't be downloaded, fails its check, or can't be saved.
+pub fn fetch_registry_list(list: &RegistryList) -> Result<PathBuf, String> {
+    let mut bytes = vec![];
+    fetch_url(list.url)?
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Couldn't download {}: {}", list.url, e))?;
+    check_fetched_list(list, &bytes)?;
+    let path = fetched_list_path(list.name);
+    let save_error =
+        |e: io::Error| format!("Couldn't save {} to {}: {}", list.name, path.display(), e);
+    fs::create_dir_all(registry_dir()).map_err(save_error)?;
+    fs::write(&path, &bytes).map_err(save_error)?;
+    Ok(path)
+}
+
+/// Checks that the downloaded bytes of a list are the list they should be
+/// (see `ListCheck`)
+fn check_fetched_list(list: &RegistryList, bytes: &[u8]) -> Result<(), String> {
+    match list.check {
+        ListCheck::Builtin(builtin_name) => {
+            // Downloaded lines have a dice roll before each word
+            let words: Vec<String> = String::from_utf8_lossy(bytes)
+                .lines()
+                .filter_map(|line| line.split_whitespace().last())
+                .map(|word| word.to_string())
+                .collect();
+            if Some(words) == builtin_list(builtin_name) {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Downloaded {} doesn't match the copy of it built into Tidy, so it wasn't saved",
+                    list.name
+                ))
+            }
+        }
+        ListCheck::Sha256("") => Err(format!(
+            "Tidy has no pinned SHA-256 hash for {} yet, so it can't check the download, and it wasn't saved",
+            list.name
+        )),
+        ListCheck::Sha256(pinned_hash) => {
+            let hash = bytes_checksum(bytes);
+            if hash == pinned_hash {
+                Ok(())
+            } else {
+                Err(format!(
+                    "Downloaded {} has SHA-256 hash {}, not the published {}, so it wasn't saved",
+                    list.name, hash, pinned_hash
+                ))
+            }
+        }
+    }
+}
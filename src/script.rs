@@ -0,0 +1,107 @@
+//! Custom per-word rules, written as a [Rhai](https://rhai.rs) script
+//! (needs Tidy built with the `scripting` feature)
+use std::path::Path;
+
+/// A compiled script of per-word rules (see `load_script`)
+#[cfg(feature = "scripting")]
+pub struct Script {
+    name: String,
+    engine: rhai::Engine,
+    ast: rhai::AST,
+}
+
+/// A compiled script of per-word rules. Without the `scripting` feature,
+/// there can't be any.
+#[cfg(not(feature = "scripting"))]
+pub enum Script {}
+
+/// Loads a Rhai script of per-word rules. The script must define a function
+/// `check(word, metadata)`, which is given each word and the metadata of its
+/// line (or an empty string, if it has none), and returns `true` to keep the
+/// word, `false` to remove it, or a string to replace it with. Returns an
+/// error message if the script can't be read or compiled.
+/// ```
+/// # #[cfg(feature = "scripting")]
+/// # {
+/// use tidy::script::load_script;
+/// let path = std::env::temp_dir().join(format!("tidy_doc_test_rules_{}.rhai", std::process::id()));
+/// std::fs::write(
+///     &path,
+///     r#"
+///     fn check(word, metadata) {
+///         if word.contains("q") { return false; }
+///         if word == "colour" { return "color"; }
+///         true
+///     }
+///     "#,
+/// )
+/// .unwrap();
+/// let script = load_script(&path).unwrap();
+/// assert_eq!(script.check("queen", None), Ok(String::new()));
+/// assert_eq!(script.check("colour", None), Ok("color".to_string()));
+/// assert_eq!(script.check("apple", None), Ok("apple".to_string()));
+/// std::fs::remove_file(&path).unwrap();
+/// # }
+/// ```
+#[cfg(feature = "scripting")]
+pub fn load_script(path: &Path) -> Result<Script, String> {
+    let engine = rhai::Engine::new();
+    let ast = engine
+        .compile_file(path.to_path_buf())
+        .map_err(|e| format!("Couldn't load script {}: {}", path.display(), e))?;
+    if !ast
+        .iter_functions()
+        .any(|f| f.name == "check" && f.params.len() == 2)
+    {
+        return Err(format!(
+            "Script {} must define a function check(word, metadata)",
+            path.display()
+        ));
+    }
+    Ok(Script {
+        name: path.display().to_string(),
+        engine,
+        ast,
+    })
+}
+
+#[cfg(not(feature = "scripting"))]
+pub fn load_script(_path: &Path) -> Result<Script, String> {
+    Err("This copy of Tidy was built without the scripting feature, so it can't run scripts. Reinstall with `cargo install --features scripting`.".to_string())
+}
+
+impl Script {
+    /// Runs the script's `check` on a word and the metadata of its line.
+    /// Returns the word, changed or not, or an empty string if the word is
+    /// removed. Returns an error message if the script fails on the word or
+    /// returns something other than a boolean or a string.
+    #[cfg(feature = "scripting")]
+    pub fn check(&self, word: &str, metadata: Option<&str>) -> Result<String, String> {
+        let result = self
+            .engine
+            .call_fn::<rhai::Dynamic>(
+                &mut rhai::Scope::new(),
+                &self.ast,
+                "check",
+                (word.to_string(), metadata.unwrap_or("").to_string()),
+            )
+            .map_err(|e| format!("script {} failed on {}: {}", self.name, word, e))?;
+        // Removing a word is the same as changing it to nothing
+        if result.is_string() {
+            return Ok(result.into_string().unwrap());
+        }
+        match result.as_bool() {
+            Ok(true) => Ok(word.to_string()),
+            Ok(false) => Ok(String::new()),
+            Err(type_name) => Err(format!(
+                "check in script {} returned a {} for {}. Return true, false, or a string.",
+                self.name, type_name, word
+            )),
+        }
+    }
+
+    #[cfg(not(feature = "scripting"))]
+    pub fn check(&self, _word: &str, _metadata: Option<&str>) -> Result<String, String> {
+        match *self {}
+    }
+}
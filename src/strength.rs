@@ -0,0 +1,56 @@
+//! Estimates of how strong sample passphrases are, from
+//! [zxcvbn](https://github.com/shssoichiro/zxcvbn-rs) (needs Tidy built with
+//! the `strength` feature)
+
+/// How strong zxcvbn thinks a passphrase is
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StrengthEstimate {
+    /// From 0 (too guessable) to 4 (very unguessable)
+    pub score: u8,
+    /// How many guesses zxcvbn thinks an attacker would need to find the
+    /// passphrase, as a power of 10
+    pub guesses_log10: f64,
+}
+
+/// zxcvbn's score for a passphrase that takes the given number of guesses
+/// to find: 0 for fewer than a thousand, 1 for fewer than a million, 2 for
+/// fewer than a hundred million, 3 for fewer than ten billion, and 4 for any
+/// more. Used to score passphrases by their entropy, so that scores can be
+/// compared to zxcvbn's.
+/// ```
+/// use tidy::strength::score_for_guesses;
+/// assert_eq!(score_for_guesses(500.0), 0);
+/// assert_eq!(score_for_guesses(5e8), 3);
+/// assert_eq!(score_for_guesses(2f64.powf(77.5)), 4);
+/// ```
+pub fn score_for_guesses(guesses: f64) -> u8 {
+    [1e3, 1e6, 1e8, 1e10]
+        .iter()
+        .take_while(|threshold| guesses >= **threshold)
+        .count() as u8
+}
+
+/// Asks zxcvbn how strong a passphrase is. Returns an error message if it
+/// can't say, like for an empty passphrase.
+/// ```
+/// # #[cfg(feature = "strength")]
+/// # {
+/// use tidy::strength::estimate_strength;
+/// assert!(estimate_strength("password").unwrap().score <= 1);
+/// assert_eq!(estimate_strength("tibia reprimand snowfall tannery battalion sweep").unwrap().score, 4);
+/// # }
+/// ```
+#[cfg(feature = "strength")]
+pub fn estimate_strength(passphrase: &str) -> Result<StrengthEstimate, String> {
+    let entropy = zxcvbn::zxcvbn(passphrase, &[])
+        .map_err(|e| format!("Couldn't estimate the strength of {}: {}", passphrase, e))?;
+    Ok(StrengthEstimate {
+        score: entropy.score(),
+        guesses_log10: entropy.guesses_log10(),
+    })
+}
+
+#[cfg(not(feature = "strength"))]
+pub fn estimate_strength(_passphrase: &str) -> Result<StrengthEstimate, String> {
+    Err("This copy of Tidy was built without the strength feature, so it can't estimate the strength of sample passphrases. Reinstall with `cargo install --features strength`.".to_string())
+}
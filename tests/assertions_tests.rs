@@ -0,0 +1,39 @@
+mod assertions_tests {
+    use std::collections::HashSet;
+    use tidy::assertions::*;
+
+    fn make_list(words: &[&str]) -> Vec<String> {
+        words.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn list_meeting_every_assertion_has_no_failures() {
+        let list = make_list(&["apple", "banana", "cherry"]);
+        let assertions = ListAssertions {
+            length: Some(3),
+            unique_prefix: Some(1),
+            free_of: Some(HashSet::from(["damn".to_string()])),
+        };
+        assert!(find_assertion_failures(&list, &assertions).is_empty());
+        // With no assertions, nothing is checked
+        assert!(find_assertion_failures(&list, &ListAssertions::default()).is_empty());
+    }
+
+    #[test]
+    fn can_find_every_assertion_a_list_fails() {
+        let list = make_list(&["apple", "apply", "damn", "darn"]);
+        let assertions = ListAssertions {
+            length: Some(7776),
+            unique_prefix: Some(3),
+            free_of: Some(HashSet::from(["damn".to_string(), "heck".to_string()])),
+        };
+        assert_eq!(
+            find_assertion_failures(&list, &assertions),
+            vec![
+                AssertionFailure::WrongLength(4, 7776),
+                AssertionFailure::SharedPrefix("apple".to_string(), "apply".to_string(), 3),
+                AssertionFailure::ForbiddenWord("damn".to_string()),
+            ]
+        );
+    }
+}
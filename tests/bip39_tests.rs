@@ -0,0 +1,47 @@
+mod bip39_tests {
+    use tidy::bip39::*;
+    use tidy::*;
+
+    #[test]
+    fn can_find_words_sharing_first_four_characters() {
+        let list: Vec<String> = ["abandon", "ability", "abandons"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let violations = find_bip39_violations(&list);
+        assert!(violations.contains(&Bip39Violation::SharedPrefix(
+            "abandon".to_string(),
+            "abandons".to_string()
+        )));
+        assert!(!violations.contains(&Bip39Violation::WordTooLong("abandons".to_string())));
+    }
+
+    #[test]
+    fn can_find_words_not_nfkd_normalized() {
+        let list: Vec<String> = ["sécréter".to_string()].to_vec();
+        let violations = find_bip39_violations(&list);
+        assert!(violations.contains(&Bip39Violation::NotNfkdNormalized("sécréter".to_string())));
+    }
+
+    #[test]
+    fn bip39_constraints_tighten_request() {
+        let this_tidy_request = TidyRequest {
+            maximum_length: Some(10),
+            maximum_shared_prefix_length: Some(3),
+            ..Default::default()
+        };
+        let this_tidy_request = apply_bip39_constraints(this_tidy_request).unwrap();
+        assert_eq!(this_tidy_request.maximum_length, Some(8));
+        assert_eq!(this_tidy_request.maximum_shared_prefix_length, Some(3));
+        assert_eq!(
+            this_tidy_request.normalization_form,
+            Some("nfkd".to_string())
+        );
+
+        let this_tidy_request = TidyRequest {
+            normalization_form: Some("nfc".to_string()),
+            ..Default::default()
+        };
+        assert!(apply_bip39_constraints(this_tidy_request).is_err());
+    }
+}
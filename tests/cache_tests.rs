@@ -0,0 +1,51 @@
+mod cache_tests {
+    use std::fs;
+    use tidy::cache::*;
+
+    #[test]
+    fn cached_list_can_be_read_back() {
+        let cache_dir =
+            std::env::temp_dir().join(format!("tidy_cache_test_{}", std::process::id()));
+        let list = vec!["abacus".to_string(), "abdomen".to_string()];
+        write_cached_list(&cache_dir, "key", &list).unwrap();
+        assert_eq!(read_cached_list(&cache_dir, "key"), Some(list));
+        assert_eq!(read_cached_list(&cache_dir, "other_key"), None);
+        // An empty list is still a cached list
+        write_cached_list(&cache_dir, "empty", &[]).unwrap();
+        assert_eq!(read_cached_list(&cache_dir, "empty"), Some(vec![]));
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn changing_an_inputted_file_changes_the_cache_key() {
+        let path =
+            std::env::temp_dir().join(format!("tidy_cache_key_test_{}.txt", std::process::id()));
+        let options = vec![(
+            "inputted_word_lists".to_string(),
+            vec![path.to_string_lossy().to_string()],
+        )];
+        fs::write(&path, "apple\n").unwrap();
+        let first_key = cache_key(&options, &[]).unwrap();
+        assert_eq!(cache_key(&options, &[]).unwrap(), first_key);
+        fs::write(&path, "pear\n").unwrap();
+        assert_ne!(cache_key(&options, &[]).unwrap(), first_key);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn changing_a_file_in_an_inputted_directory_changes_the_cache_key() {
+        let dir =
+            std::env::temp_dir().join(format!("tidy_cache_key_dir_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let options = vec![(
+            "inputted_word_lists".to_string(),
+            vec![dir.to_string_lossy().to_string()],
+        )];
+        let input_files = vec![dir.join("words.txt")];
+        fs::write(&input_files[0], "apple\n").unwrap();
+        let first_key = cache_key(&options, &input_files).unwrap();
+        fs::write(&input_files[0], "pear\n").unwrap();
+        assert_ne!(cache_key(&options, &input_files).unwrap(), first_key);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
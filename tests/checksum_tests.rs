@@ -0,0 +1,18 @@
+mod checksum_tests {
+    use tidy::checksum::list_checksum;
+
+    #[test]
+    fn checksum_of_empty_list_is_hash_of_empty_string() {
+        assert_eq!(
+            list_checksum(&[]),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn checksum_changes_with_list_order() {
+        let list: Vec<String> = ["abbey", "zoo"].iter().map(|x| x.to_string()).collect();
+        let reversed_list: Vec<String> = ["zoo", "abbey"].iter().map(|x| x.to_string()).collect();
+        assert_ne!(list_checksum(&list), list_checksum(&reversed_list));
+    }
+}
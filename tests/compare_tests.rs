@@ -0,0 +1,68 @@
+mod compare_tests {
+    use tidy::compare::*;
+    use tidy::file_readers::load_baseline_list;
+
+    #[test]
+    fn can_load_builtin_eff_lists() {
+        let long_list = load_baseline_list("eff-long").unwrap();
+        assert_eq!(long_list.len(), 7776);
+        assert_eq!(long_list[0], "abacus");
+        let short_list = load_baseline_list("eff-short").unwrap();
+        assert_eq!(short_list.len(), 1296);
+    }
+
+    #[test]
+    fn can_compare_a_list_to_a_baseline() {
+        let baseline = load_baseline_list("eff-short").unwrap();
+        let mut list: Vec<String> = baseline[0..100].to_vec();
+        list.push("zzyzx".to_string());
+        let comparison = compare_lists(&list, &baseline);
+        assert_eq!(comparison.overlap, 100);
+        assert_eq!(comparison.unique_to_list, vec!["zzyzx"]);
+        assert_eq!(comparison.unique_to_baseline.len(), 1196);
+    }
+
+    #[test]
+    fn can_diff_lines_with_repeats() {
+        let old: Vec<String> = ["# header", "abacus", "", "zoo", ""]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let new: Vec<String> = ["# header", "", "abacus", "zoo"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let diff = diff_lines(&old, &new);
+        assert!(diff.added.is_empty());
+        assert_eq!(diff.removed, vec![(5, "".to_string())]);
+        assert_eq!(
+            diff.moved,
+            vec![("".to_string(), 3, 2), ("abacus".to_string(), 2, 3)]
+        );
+        assert!(diff_lines(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn can_check_lists_of_first_and_second_words_against_each_other() {
+        use tidy::compound_collisions::find_cross_list_collisions;
+        let adjectives: Vec<String> = ["blue", "bluer", "light"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let nouns: Vec<String> = ["rink", "ink", "light", "cat"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(find_shared_words(&adjectives, &nouns), vec!["light"]);
+        // "blue" + "rink" and "bluer" + "ink" both spell "bluerink"
+        assert_eq!(
+            find_cross_list_collisions(&adjectives, &nouns),
+            vec![(
+                vec!["blue".to_string(), "rink".to_string()],
+                vec!["bluer".to_string(), "ink".to_string()]
+            )]
+        );
+        // Going the other way, no noun is the start of another
+        assert!(find_cross_list_collisions(&nouns, &adjectives).is_empty());
+    }
+}
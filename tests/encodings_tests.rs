@@ -0,0 +1,61 @@
+mod encodings_tests {
+    use tidy::encodings::*;
+
+    #[test]
+    fn can_decode_utf16_with_byte_order_marks() {
+        let little_endian = b"\xFF\xFEz\x00o\x00o\x00";
+        let big_endian = b"\xFE\xFF\x00z\x00o\x00o";
+        assert_eq!(
+            decode(little_endian, InputEncoding::Utf16, true).unwrap(),
+            "zoo"
+        );
+        assert_eq!(
+            decode(big_endian, InputEncoding::Utf16, true).unwrap(),
+            "zoo"
+        );
+    }
+
+    #[test]
+    fn can_guess_encoding() {
+        assert_eq!(guess_encoding("énigme".as_bytes()), InputEncoding::Utf8);
+        assert_eq!(guess_encoding(b"\xe9nigme"), InputEncoding::Windows1252);
+        assert_eq!(guess_encoding(b"\xFF\xFEz\x00"), InputEncoding::Utf16);
+        assert_eq!(
+            decode(b"\xe9nigme", InputEncoding::Auto, true).unwrap(),
+            "énigme"
+        );
+    }
+
+    #[test]
+    fn strict_mode_rejects_undefined_windows_1252_bytes() {
+        assert!(decode(b"\x81", InputEncoding::Windows1252, true).is_err());
+        assert_eq!(
+            decode(b"\x81", InputEncoding::Windows1252, false).unwrap(),
+            "�"
+        );
+    }
+
+    #[test]
+    fn can_parse_encoding_names() {
+        assert_eq!("Latin1".parse(), Ok(InputEncoding::Latin1));
+        assert_eq!("cp1252".parse(), Ok(InputEncoding::Windows1252));
+        assert!("ebcdic".parse::<InputEncoding>().is_err());
+    }
+
+    #[test]
+    fn can_encode_list_as_windows_1252_with_crlf_line_endings() {
+        use tidy::file_writer::LineEnding;
+        let list = ["café", "“hi”"];
+        let text = list.join(LineEnding::Crlf.as_str());
+        assert_eq!(
+            encode(&text, OutputEncoding::Windows1252).unwrap(),
+            b"caf\xe9\r\n\x93hi\x94"
+        );
+    }
+
+    #[test]
+    fn encoding_fails_on_unrepresentable_character() {
+        assert!(encode("京都", OutputEncoding::Windows1252).is_err());
+        assert!(encode("京都", OutputEncoding::Utf16Le).is_ok());
+    }
+}
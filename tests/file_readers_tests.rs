@@ -0,0 +1,159 @@
+mod file_readers_tests {
+    use tidy::file_readers::*;
+
+    fn make_clearsigned_lines() -> Vec<String> {
+        [
+            "-----BEGIN PGP SIGNED MESSAGE-----",
+            "Hash: SHA512",
+            "",
+            "11111\tabacus",
+            "11112\tabdomen",
+            "- -----dashes",
+            "-----BEGIN PGP SIGNATURE-----",
+            "",
+            "iQIzBAEBCgAdFiEEo3Wcmvm5P9pT",
+            "=mX2h",
+            "-----END PGP SIGNATURE-----",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect()
+    }
+
+    #[test]
+    fn can_strip_pgp_clearsign_armor() {
+        let lines = strip_pgp_armor(make_clearsigned_lines());
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "11111\tabacus");
+        assert_eq!(lines[2], "-----dashes");
+    }
+
+    #[test]
+    fn can_strip_pgp_clearsign_armor_from_borrowed_lines() {
+        let owned_lines = make_clearsigned_lines();
+        let lines: Vec<&str> = owned_lines.iter().map(String::as_str).collect();
+        assert_eq!(
+            strip_pgp_armor_from_slices(&lines),
+            vec!["11111\tabacus", "11112\tabdomen", "-----dashes"]
+        );
+    }
+
+    #[test]
+    fn leaves_unsigned_lines_alone() {
+        let lines: Vec<String> = ["- minus", "", "abacus"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(strip_pgp_armor(lines.clone()), lines);
+    }
+
+    #[test]
+    fn skips_no_rows_of_files_pgp_armor_was_stripped_from() {
+        let path = std::env::temp_dir().join(format!(
+            "tidy_pgp_skip_rows_test_{}.txt",
+            std::process::id()
+        ));
+        std::fs::write(&path, make_clearsigned_lines().join("\n")).unwrap();
+        let lines = make_vec_from_filenames(
+            std::slice::from_ref(&path),
+            Some(3),
+            Some(5),
+            true,
+            None,
+            None,
+            false,
+        );
+        assert_eq!(
+            lines,
+            vec!["11111\tabacus", "11112\tabdomen", "-----dashes"]
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn can_strip_comment_lines_and_trailing_comments() {
+        let lines: Vec<String> = [
+            "// Word list v2",
+            "abacus",
+            "  // indented",
+            "abdomen // noun",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        assert_eq!(strip_comments(lines, "//"), vec!["abacus", "abdomen"]);
+    }
+
+    #[test]
+    fn keeps_words_containing_comment_prefix() {
+        let lines: Vec<String> = ["C#", "F#", "#hashtag"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(strip_comments(lines, "#"), vec!["C#", "F#"]);
+    }
+
+    #[test]
+    fn can_expand_directories_and_glob_patterns() {
+        let dir = std::env::temp_dir().join(format!(
+            "tidy_expand_input_paths_test_{}",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("part two")).unwrap();
+        for file in [
+            "ch1.txt",
+            "ch2.txt",
+            "notes.md",
+            ".hidden",
+            "part two/ch3.txt",
+        ] {
+            std::fs::write(dir.join(file), "word\n").unwrap();
+        }
+
+        let files = expand_input_paths(std::slice::from_ref(&dir)).unwrap();
+        assert_eq!(files.len(), 4);
+        assert!(!files.contains(&dir.join(".hidden")));
+
+        let files = expand_input_paths(&[dir.join("*.txt")]).unwrap();
+        assert_eq!(files, vec![dir.join("ch1.txt"), dir.join("ch2.txt")]);
+
+        let files = expand_input_paths(&[dir.join("**").join("ch?.txt")]).unwrap();
+        assert_eq!(files.len(), 3);
+        assert!(files.contains(&dir.join("part two/ch3.txt")));
+
+        assert!(expand_input_paths(&[dir.join("*.csv")]).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn can_read_lines_of_memory_mapped_file() {
+        let path =
+            std::env::temp_dir().join(format!("tidy_map_file_test_{}.txt", std::process::id()));
+        std::fs::write(&path, b"abacus\r\nabdomen\n\xff\xfe\n\nzoo\n").unwrap();
+        let mapped_file = map_file(&path).unwrap();
+        assert_eq!(
+            mapped_file.lines(&path),
+            vec!["abacus", "abdomen", "", "zoo"]
+        );
+        drop(mapped_file);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn can_find_a_fetched_list_by_name() {
+        use std::path::Path;
+        let dir = std::env::temp_dir().join(format!("tidy_registry_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("eff-short.txt"), "abacus\nabdomen\n").unwrap();
+        let (list, fetched_path) = find_fetched_list(Path::new("eff-short"), &dir).unwrap();
+        assert_eq!(list.name, "eff-short");
+        assert_eq!(
+            read_lines_from_file(&fetched_path, None, false),
+            vec!["abacus", "abdomen"]
+        );
+        assert!(find_fetched_list(Path::new("no-such-list"), &dir).is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,39 @@
+mod html_writer_tests {
+    use tidy::file_writer::PrintRequest;
+    use tidy::html_writer::render_html;
+
+    #[test]
+    fn can_render_list_as_html_pages() {
+        let print_req = PrintRequest {
+            tidied_list: ["apple", "b&b", "cherry", "date", "elder"]
+                .iter()
+                .map(|word| word.to_string())
+                .collect(),
+            dice_sides: Some(6),
+            print_columns: Some(2),
+            page_height: Some(2),
+            metadata_header: Some(vec!["Command: tidy --format html".to_string()]),
+            ..Default::default()
+        };
+        let lines = render_html(&print_req, "abc123");
+        assert!(lines.contains(&"<h1>Word list</h1>".to_string()));
+        assert!(lines.contains(&"<li>Command: tidy --format html</li>".to_string()));
+        assert!(lines.contains(&"<li>5 words</li>".to_string()));
+        assert!(lines.contains(&"<li>SHA-256: <code>abc123</code></li>".to_string()));
+        // Two rows of two columns fit 4 words on the first page
+        assert_eq!(
+            lines
+                .iter()
+                .filter(|line| line.as_str() == "<table class=\"page\">")
+                .count(),
+            2
+        );
+        assert!(lines.contains(
+            &"<tr><td class=\"roll\">1</td><td class=\"word\">apple</td><td class=\"roll\">3</td><td class=\"word\">cherry</td></tr>".to_string()
+        ));
+        assert!(lines.contains(
+            &"<tr><td class=\"roll\">2</td><td class=\"word\">b&amp;b</td><td class=\"roll\">4</td><td class=\"word\">date</td></tr>".to_string()
+        ));
+        assert_eq!(lines.last().unwrap(), "</html>");
+    }
+}
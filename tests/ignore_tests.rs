@@ -1,4 +1,5 @@
 mod ignore_tests {
+    use tidy::list_manipulations::SortOrder;
     use tidy::*;
 
     fn make_list() -> Vec<String> {
@@ -16,7 +17,7 @@ mod ignore_tests {
             maximum_length: Some(10),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"mA1,word1 mB1".to_string()));
     }
     #[test]
@@ -27,8 +28,146 @@ mod ignore_tests {
             maximum_length: Some(10),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         println!("{:?}", new_list);
         assert!(new_list.contains(&"mA1,word1 mB1".to_string()));
     }
+
+    #[test]
+    fn word_modifications_leave_metadata_alone() {
+        let this_tidy_request = TidyRequest {
+            list: vec!["Apple2,300".to_string(), "ZEBRA,100".to_string()],
+            ignore_after_delimiter: Some(','),
+            to_lowercase: true,
+            should_delete_integers: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["apple,300", "zebra,100"]
+        );
+    }
+
+    #[test]
+    fn can_choose_which_line_of_a_duplicate_word_to_keep() {
+        use tidy::list_manipulations::{CanonicalCase, DedupKeep};
+        let list: Vec<String> = ["apple,300", "pear,20", "Apple,5000", "apple,12"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let request_keeping = |dedup_keep| TidyRequest {
+            list: list.clone(),
+            ignore_after_delimiter: Some(','),
+            dedup_ignore_case: Some(CanonicalCase::First),
+            dedup_keep,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&request_keeping(DedupKeep::First)).unwrap(),
+            ["apple,300", "pear,20"]
+        );
+        assert_eq!(
+            tidy_list(&request_keeping(DedupKeep::Last)).unwrap(),
+            ["apple,12", "pear,20"]
+        );
+        assert_eq!(
+            tidy_list(&request_keeping(DedupKeep::HighestFrequency)).unwrap(),
+            ["Apple,5000", "pear,20"]
+        );
+        assert_eq!(
+            tidy_list(&request_keeping(DedupKeep::Shortest)).unwrap(),
+            ["apple,12", "pear,20"]
+        );
+    }
+
+    #[test]
+    fn words_made_the_same_keep_the_metadata_of_their_first_line() {
+        let this_tidy_request = TidyRequest {
+            list: vec![
+                "Apple,300".to_string(),
+                "apple,5".to_string(),
+                "pear,20".to_string(),
+            ],
+            ignore_after_delimiter: Some(','),
+            to_lowercase: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["apple,300", "pear,20"]
+        );
+    }
+
+    #[test]
+    fn whole_list_steps_only_compare_words() {
+        let this_tidy_request = TidyRequest {
+            list: vec![
+                "11111\tnews".to_string(),
+                "11112\tnewspaper".to_string(),
+                "11113\tpaper".to_string(),
+            ],
+            ignore_before_delimiter: Some('t'),
+            should_remove_prefix_words: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["11112\tnewspaper", "11113\tpaper"]
+        );
+
+        let this_tidy_request = TidyRequest {
+            list: vec![
+                "cat,9".to_string(),
+                "bat,8".to_string(),
+                "dog,7".to_string(),
+            ],
+            ignore_after_delimiter: Some(','),
+            minimum_edit_distance: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), ["dog,7"]);
+    }
+
+    #[test]
+    fn metadata_follows_words_that_are_truncated() {
+        let this_tidy_request = TidyRequest {
+            list: vec!["abacus,10".to_string(), "zebra,20".to_string()],
+            ignore_after_delimiter: Some(','),
+            truncate_to_unique_length: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), ["aba,10", "zeb,20"]);
+    }
+
+    #[test]
+    fn can_keep_every_column_of_metadata() {
+        let list: Vec<String> = ["Apple\t300\tnoun", "Run\t200\tverb"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list: list.clone(),
+            ignore_after_delimiter: Some('t'),
+            to_lowercase: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["apple\t300", "run\t200"]
+        );
+
+        let this_tidy_request = TidyRequest {
+            list,
+            ignore_after_delimiter: Some('t'),
+            keep_metadata: true,
+            to_lowercase: true,
+            sort_by: SortOrder::Frequency,
+            reverse_sort: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["run\t200\tverb", "apple\t300\tnoun"]
+        );
+    }
 }
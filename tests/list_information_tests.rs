@@ -102,4 +102,112 @@ mod list_information_tests {
             .collect();
         assert_eq!(mean_word_length(&list), 5.3333335);
     }
+    #[test]
+    fn can_calculate_kraft_mcmillan_sum() {
+        let list: Vec<String> = ["a", "ba", "bb"].iter().map(|x| x.to_string()).collect();
+        assert_eq!(kraft_mcmillan_sum(&list), 1.0);
+        assert!(satisfies_mcmillan(&list));
+        assert_eq!(max_additional_words_by_length(&list), vec![(1, 0), (2, 0)]);
+
+        // Not uniquely decodable, so can't satisfy the inequality
+        let list: Vec<String> = ["a", "b", "ab"].iter().map(|x| x.to_string()).collect();
+        assert_eq!(kraft_mcmillan_sum(&list), 1.25);
+        assert!(!satisfies_mcmillan(&list));
+        assert_eq!(max_additional_words_by_length(&list), vec![(1, 0), (2, 0)]);
+    }
+    #[test]
+    fn can_calculate_shannon_entropy_of_frequencies() {
+        let metadata: Vec<String> = ["100", "100", "100", "100"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let frequencies = parse_frequencies(&metadata).unwrap();
+        assert_eq!(shannon_entropy(&frequencies), calc_entropy_per_word(4));
+
+        // Skewed frequencies mean less entropy than the uniform figure
+        assert!(shannon_entropy(&[1000.0, 10.0, 10.0, 1.0]) < calc_entropy_per_word(4));
+        assert_eq!(parse_frequencies(&[]), None);
+    }
+    #[test]
+    fn can_find_skewed_starting_letters() {
+        use tidy::display_information::distributions::*;
+        let list: Vec<String> = ["unable", "undo", "unmoved", "apple", "zebra"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(first_letter_frequencies(&list)[0], ("u".to_string(), 3));
+        let skewed = find_skewed_prefixes(&list, 50.0);
+        assert_eq!(skewed.len(), 2);
+        assert_eq!(skewed[1].0, "un");
+        assert!(find_skewed_prefixes(&list, 60.0).is_empty());
+    }
+    #[test]
+    fn can_get_median_word_length_and_histogram() {
+        let list: Vec<String> = ["canopy", "to", "cold", "seasons", "fire", "Christmas"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(median_word_length(&list), 5.0);
+        let histogram = word_length_histogram(&list);
+        assert_eq!(histogram.first(), Some(&(2, 1)));
+        assert_eq!(histogram.last(), Some(&(9, 1)));
+        assert_eq!(histogram.iter().map(|(_l, count)| count).sum::<usize>(), 6);
+    }
+    #[test]
+    fn can_calculate_bits_per_typed_character() {
+        let list: Vec<String> = ["canopy", "to", "cold", "seasons", "fire", "Christmas"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert_eq!(
+            bits_per_typed_character(&list, ""),
+            efficiency_per_character(&list)
+        );
+        assert!(bits_per_typed_character(&list, " ") < efficiency_per_character(&list));
+    }
+
+    #[test]
+    fn can_estimate_time_to_crack_passphrases_of_each_length() {
+        let table = passphrase_entropy_table(7_776, 1e12);
+        let numbers_of_words: Vec<usize> = table.iter().map(|(n, _bits, _secs)| *n).collect();
+        assert_eq!(numbers_of_words, vec![3, 4, 5, 6, 7]);
+        let (_n, bits, seconds) = table[1];
+        assert_eq!(bits, calc_entropy_per_word(7_776) * 4.0);
+        assert_eq!(format_crack_time(seconds), "30 minutes");
+        assert_eq!(format_crack_time(table[4].2), "27,237,033 years");
+
+        // A slower attacker takes longer
+        let (_n, _bits, slower_seconds) = passphrase_entropy_table(7_776, 1e9)[1];
+        assert_eq!(format_crack_time(slower_seconds), "21 days");
+    }
+
+    #[test]
+    fn can_find_clean_power_a_list_is_just_short_of() {
+        assert_eq!(find_power_just_above(7_740), Some((6, 5, 7_776)));
+        assert_eq!(find_power_just_above(1_290), Some((6, 4, 1_296)));
+        assert_eq!(find_power_just_above(8_100), Some((2, 13, 8_192)));
+        // Too far short of 7,776
+        assert_eq!(find_power_just_above(7_000), None);
+        // Already at or above the power
+        assert_eq!(find_power_just_above(2_048), None);
+        assert_eq!(find_power_just_above(7_800), None);
+    }
+
+    #[test]
+    fn attribute_values_line_up() {
+        let rows = [
+            format_attribute("List length", "7776 words"),
+            format_attribute("Mean 6-word passphrase", "47.9 characters"),
+            format_attribute("Uniquely decodable?", false),
+        ];
+        let colons: Vec<Option<usize>> = rows.iter().map(|row| row.find(':')).collect();
+        assert!(colons.iter().all(|colon| *colon == Some(26)));
+        // Names with characters wider than a byte line up too
+        assert_eq!(
+            format_attribute("Mots du début", 1)
+                .chars()
+                .position(|c| c == ':'),
+            Some(26)
+        );
+    }
 }
@@ -1,5 +1,7 @@
 mod list_manipulation_tests {
+    use std::collections::HashSet;
     use tidy::dice::print_as_dice; // not exactly sure why I need this here...
+    use tidy::list_manipulations::{CutStrategy, QuotaPriority, SortOrder};
     use tidy::*;
 
     fn make_lists() -> (Vec<String>, Vec<String>, Vec<String>, Vec<String>) {
@@ -86,7 +88,7 @@ mod list_manipulation_tests {
             list: make_lists().0,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"tea".to_string()));
         assert!(new_list.contains(&"apple".to_string()));
         assert!(new_list.len() == make_lists().0.len() - 2);
@@ -96,11 +98,11 @@ mod list_manipulation_tests {
     fn can_sort_words_alphabetically() {
         let this_tidy_request = TidyRequest {
             list: make_lists().0,
-            sort_alphabetically: true,
+            sort_by: SortOrder::Alphabetical,
             locale: "en-US".to_string(),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list[0] == "addiction".to_string());
         assert!(new_list.contains(&"station".to_string()));
         assert!(new_list[new_list.len() - 1] == "zookeeper".to_string());
@@ -110,22 +112,341 @@ mod list_manipulation_tests {
     fn respect_option_to_not_sort_alphabetically() {
         let this_tidy_request = TidyRequest {
             list: make_lists().0,
-            sort_alphabetically: false,
+            sort_by: SortOrder::Unsorted,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list[0] == "zookeeper".to_string());
         assert!(new_list.contains(&"apple".to_string()));
         assert!(new_list[new_list.len() - 1] == "station".to_string());
     }
 
+    #[test]
+    fn can_sort_by_length_and_frequency() {
+        let list: Vec<String> = ["kiwi,5", "fig,20", "apple,300", "date,40", "plum"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list: list.clone(),
+            ignore_after_delimiter: Some(','),
+            sort_by: SortOrder::LengthThenAlphabetical,
+            locale: "en-US".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["fig,20", "date,40", "kiwi,5", "plum", "apple,300"]
+        );
+
+        let this_tidy_request = TidyRequest {
+            list,
+            ignore_after_delimiter: Some(','),
+            sort_by: SortOrder::Frequency,
+            reverse_sort: true,
+            ..Default::default()
+        };
+        // Least frequent first, after words without a frequency
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["plum", "kiwi,5", "fig,20", "date,40", "apple,300"]
+        );
+    }
+
+    #[test]
+    fn can_dedup_ignoring_case_without_lowercasing() {
+        use tidy::list_manipulations::CanonicalCase;
+        let this_tidy_request = TidyRequest {
+            list: make_lists().0,
+            dedup_ignore_case: Some(CanonicalCase::Capitalized),
+            ..Default::default()
+        };
+        let mut list = this_tidy_request.list.clone();
+        list.push("charlie".to_string());
+        list.push("Tea".to_string());
+        let new_list = tidy_list(&TidyRequest {
+            list,
+            ..this_tidy_request
+        })
+        .unwrap();
+        assert!(new_list.contains(&"cHarLie".to_string()));
+        assert!(!new_list.contains(&"charlie".to_string()));
+        assert!(new_list.contains(&"Tea".to_string()));
+        assert!(!new_list.contains(&"tea".to_string()));
+        assert!(new_list.contains(&"zoo".to_string()));
+    }
+
+    #[test]
+    fn can_capitalize_words_after_removing_duplicates() {
+        use tidy::list_manipulations::Capitalization;
+        let this_tidy_request = TidyRequest {
+            list: ["apple,5", "ice cream,3", "Apple,7", "élan,1"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            ignore_after_delimiter: Some(','),
+            capitalization: Capitalization::All,
+            sort_by: SortOrder::Alphabetical,
+            ..Default::default()
+        };
+        // "apple" and "Apple" are only made the same by capitalizing, and
+        // the first keeps its metadata
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["Apple,5", "Élan,1", "Ice Cream,3"]
+        );
+    }
+
+    #[test]
+    fn can_remove_leet_variants_of_listed_and_rejected_words() {
+        let this_tidy_request = TidyRequest {
+            list: [
+                "P4ss", "pass", "dr4gon", "h4x0r", "s3cr3t", "secret", "n1nja", "l33t", "7est",
+                "test",
+            ]
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+            to_lowercase: true,
+            reject_list: Some(["ninja".to_string()].into_iter().collect()),
+            // Without 7=t, "7est" isn't a variant of "test"
+            leet_substitutions: Some(vec![('4', 'a'), ('3', 'e'), ('0', 'o'), ('1', 'i')]),
+            sort_by: SortOrder::Alphabetical,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["7est", "dr4gon", "h4x0r", "l33t", "pass", "secret", "test"]
+        );
+    }
+
+    #[test]
+    fn can_remove_gibberish_but_keep_real_words() {
+        use tidy::list_manipulations::DEFAULT_GIBBERISH_THRESHOLD;
+        let list: Vec<String> = [
+            "algorithm",
+            "McDonalds",
+            "jack-in-the-box",
+            "aGVsbG8gd29y",
+            "e3b0c44298fc",
+            "x7Kp2Lq9",
+            "YouTube",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let this_tidy_request = TidyRequest {
+            list: list.clone(),
+            gibberish_threshold: Some(DEFAULT_GIBBERISH_THRESHOLD),
+            sort_by: SortOrder::Unsorted,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["algorithm", "McDonalds", "jack-in-the-box", "YouTube"]
+        );
+        // A lower threshold is more sensitive
+        let this_tidy_request = TidyRequest {
+            list,
+            gibberish_threshold: Some(4.5),
+            sort_by: SortOrder::Unsorted,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["algorithm", "jack-in-the-box", "YouTube"]
+        );
+    }
+
+    #[test]
+    fn can_remove_inflections_keeping_base_forms() {
+        let this_tidy_request = TidyRequest {
+            list: [
+                "stops", "stop", "stopped", "news", "carry", "carried", "glass",
+            ]
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+            should_remove_inflections: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["stop", "news", "carry", "glass"]
+        );
+    }
+
+    #[test]
+    fn can_remove_words_with_confusable_chars() {
+        let this_tidy_request = TidyRequest {
+            list: ["barn", "bark", "Oslo", "hat", "cat", "f1ve", "vvord"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            confusable_chars: Some(vec!["l".to_string(), "1".to_string(), "rn".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["bark", "hat", "cat", "vvord"]
+        );
+    }
+
+    #[test]
+    fn can_remove_mixed_script_words() {
+        let this_tidy_request = TidyRequest {
+            // The "а" in the first word is Cyrillic
+            list: [
+                "pаypal",
+                "paypal",
+                "москва",
+                "café",
+                "東京タワー",
+                "mix東京",
+            ]
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+            should_remove_mixed_script: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["paypal", "москва", "café", "東京タワー"]
+        );
+    }
+
+    #[test]
+    fn can_remove_or_join_multiword_entries() {
+        let list: Vec<String> = ["ice cream", "sun", "hot  dog "]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list: list.clone(),
+            should_remove_multiword: true,
+            ..Default::default()
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), ["sun"]);
+
+        let this_tidy_request = TidyRequest {
+            list,
+            join_multiword_separator: Some("".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["icecream", "sun", "hotdog"]
+        );
+    }
+
+    #[test]
+    fn can_delete_apostrophes_and_normalize_hyphens() {
+        let this_tidy_request = TidyRequest {
+            list: ["don't", "o\u{2019}clock", "well\u{2010}known", "re-enter"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            should_delete_apostrophes: true,
+            should_normalize_hyphens: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["dont", "oclock", "well-known", "re-enter"]
+        );
+    }
+
+    #[test]
+    fn always_includes_words_and_reports_conflicts() {
+        let this_tidy_request = TidyRequest {
+            list: ["sunny", "moon", "star", "comet"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            always_include: Some(vec!["sun".to_string(), "x".to_string()]),
+            should_remove_prefix_words: true,
+            minimum_length: Some(3),
+            print_rand: Some(3),
+            ..Default::default()
+        };
+        let (new_list, report) = tidy_list_explained(&this_tidy_request).unwrap();
+        assert_eq!(new_list.len(), 3);
+        assert!(new_list.contains(&"sun".to_string()));
+        assert!(new_list.contains(&"x".to_string()));
+        assert_eq!(
+            report
+                .always_include_conflicts
+                .contains(&"sun is a prefix of sunny".to_string()),
+            new_list.contains(&"sunny".to_string())
+        );
+    }
+
+    #[test]
+    fn can_update_a_previous_list_keeping_positions() {
+        let previous: Vec<String> = ["ant", "bee", "cat", "dog"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list: ["zebu", "dog", "yak", "emu", "cat", "ant", "bee"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            reject_list: Some(["bee".to_string()].into_iter().collect()),
+            previous_list: Some(previous),
+            print_rand: Some(4),
+            ..Default::default()
+        };
+        let (new_list, report) = tidy_list_explained(&this_tidy_request).unwrap();
+        assert_eq!(new_list.len(), 4);
+        assert_eq!(new_list[0], "ant");
+        assert_eq!(new_list[2], "cat");
+        assert_eq!(new_list[3], "dog");
+        assert_eq!(report.previous_list_changes.len(), 1);
+        assert_eq!(report.previous_list_changes[0].1, Some("bee".to_string()));
+    }
+
+    #[test]
+    fn take_rand_prefers_lines_with_higher_weights() {
+        let this_tidy_request = TidyRequest {
+            list: ["good1", "good2", "good3", "noisy1", "noisy2", "noisy3"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            line_weights: Some(vec![1.0, 1.0, 1.0, 0.0, 0.0, 0.0]),
+            take_rand: Some(3),
+            ..Default::default()
+        };
+        let mut new_list = tidy_list(&this_tidy_request).unwrap();
+        new_list.sort();
+        assert_eq!(new_list, ["good1", "good2", "good3"]);
+    }
+
+    #[cfg(feature = "stemming")]
+    #[test]
+    fn can_lemmatize_keeping_first_word_of_each_stem() {
+        let this_tidy_request = TidyRequest {
+            list: ["generously", "connection", "generous", "connect", "zoo"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            lemmatize_language: Some("english".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["generously", "connection", "zoo"]
+        );
+    }
+
     #[test]
     fn removes_blank_lines() {
         let this_tidy_request = TidyRequest {
             list: make_lists().1,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.len() == make_lists().1.len() - 2);
     }
 
@@ -136,7 +457,7 @@ mod list_manipulation_tests {
             take_first: Some(4),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         println!("List length now {}: {:?}", new_list.len(), new_list);
         assert_eq!(new_list.len(), 4);
         assert_ne!(new_list.len(), 3);
@@ -149,7 +470,7 @@ mod list_manipulation_tests {
             list: make_lists().1,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"wizard".to_string()));
     }
 
@@ -159,7 +480,7 @@ mod list_manipulation_tests {
             list: make_lists().1,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"h as spaces".to_string()));
     }
 
@@ -170,7 +491,7 @@ mod list_manipulation_tests {
             should_straighten_quotes: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"\"smart\"".to_string()));
         assert!(new_list.contains(&"'quotes'".to_string()));
     }
@@ -181,7 +502,7 @@ mod list_manipulation_tests {
             should_delete_integers: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"active".to_string()));
     }
 
@@ -192,7 +513,7 @@ mod list_manipulation_tests {
             should_delete_nonalphanumeric: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"1968clad".to_string()));
         assert!(new_list.contains(&"take".to_string()));
     }
@@ -204,7 +525,7 @@ mod list_manipulation_tests {
             should_remove_nonalphanumeric: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
 
         assert!(new_list.contains(&"Uppercase".to_string()));
         assert!(new_list.contains(&"京".to_string()));
@@ -221,7 +542,7 @@ mod list_manipulation_tests {
             should_remove_nonalphabetic: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
 
         assert!(new_list.contains(&"Uppercase".to_string()));
         assert!(new_list.contains(&"京".to_string()));
@@ -237,7 +558,7 @@ mod list_manipulation_tests {
             should_remove_non_latin_alphabetic: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
 
         assert!(new_list.contains(&"Uppercase".to_string()));
         assert!(!new_list.contains(&"京".to_string()));
@@ -254,7 +575,7 @@ mod list_manipulation_tests {
             should_remove_nonascii: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
 
         assert!(new_list.contains(&"Uppercase".to_string()));
         assert!(new_list.contains(&"hello109823".to_string()));
@@ -271,7 +592,7 @@ mod list_manipulation_tests {
             should_delete_before_first_delimiter: Some('\t'),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"active".to_string()));
         assert!(new_list.contains(&"acclaim".to_string()));
         // Only remove through FIRST tab
@@ -285,7 +606,7 @@ mod list_manipulation_tests {
             should_delete_before_first_delimiter: Some(' '),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"clad".to_string()));
         // Check that it only removes characters through first space, rather than just
         // between first space and second space, for example
@@ -301,7 +622,7 @@ mod list_manipulation_tests {
             should_delete_before_first_delimiter: Some(','),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"203478".to_string()));
         assert!(new_list.contains(&"h as spaces".to_string()));
     }
@@ -313,7 +634,7 @@ mod list_manipulation_tests {
             should_delete_after_first_delimiter: Some('\t'),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"11225".to_string()));
         assert!(new_list.contains(&"11152".to_string()));
         // remove after FIRST tab
@@ -326,7 +647,7 @@ mod list_manipulation_tests {
             should_delete_after_first_delimiter: Some(' '),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"19-6-8".to_string()));
         assert!(new_list.contains(&"13910".to_string()));
         assert!(new_list.contains(&"post-modern".to_string()));
@@ -339,7 +660,7 @@ mod list_manipulation_tests {
             should_delete_after_first_delimiter: Some(','),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"comma".to_string()));
         assert!(new_list.contains(&"h as spaces".to_string()));
     }
@@ -351,14 +672,14 @@ mod list_manipulation_tests {
             to_lowercase: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"charlie".to_string()));
         let this_tidy_request = TidyRequest {
             list: make_lists().1,
             to_lowercase: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"vacation".to_string()));
         assert!(new_list.contains(&"ardor".to_string()));
     }
@@ -370,7 +691,7 @@ mod list_manipulation_tests {
             should_remove_prefix_words: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"station".to_string()));
         assert!(new_list.contains(&"stationary".to_string()));
         assert!(!new_list.contains(&"zoo".to_string()));
@@ -385,7 +706,7 @@ mod list_manipulation_tests {
             should_remove_suffix_words: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"keeper".to_string()));
         assert!(new_list.contains(&"apple".to_string()));
     }
@@ -397,7 +718,7 @@ mod list_manipulation_tests {
             should_remove_nonalphanumeric: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"19-6-8 clad".to_string()));
         assert!(new_list.contains(&"world999".to_string()));
         assert!(new_list.contains(&"take".to_string()));
@@ -410,7 +731,7 @@ mod list_manipulation_tests {
             should_remove_nonalphabetic: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"19-6-8 clad".to_string()));
         assert!(!new_list.contains(&"world999".to_string()));
         assert!(!new_list.contains(&"world".to_string()));
@@ -428,7 +749,7 @@ mod list_manipulation_tests {
             should_remove_integers: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"19-6-8 clad".to_string()));
         assert!(!new_list.contains(&"world999".to_string()));
         assert!(new_list.contains(&"be".to_string()));
@@ -442,7 +763,7 @@ mod list_manipulation_tests {
             minimum_length: Some(3),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"I".to_string()));
         assert!(!new_list.contains(&"be".to_string()));
         assert!(new_list.contains(&"tea".to_string()));
@@ -457,7 +778,7 @@ mod list_manipulation_tests {
             maximum_length: Some(7),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"addiction".to_string()));
         assert!(!new_list.contains(&"zookeeper".to_string()));
         assert!(!new_list.contains(&"stationary".to_string()));
@@ -475,7 +796,7 @@ mod list_manipulation_tests {
             maximum_length: Some(7),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"active".to_string()));
         assert!(new_list.contains(&"acclaim".to_string()));
         assert!(!new_list.contains(&"word with spacaes in it".to_string()));
@@ -488,7 +809,7 @@ mod list_manipulation_tests {
             maximum_shared_prefix_length: Some(3),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"zoo".to_string()));
         assert!(!new_list.contains(&"zookeeper".to_string()));
         assert!(new_list.contains(&"station".to_string()));
@@ -497,9 +818,155 @@ mod list_manipulation_tests {
         assert!(!new_list.contains(&"apple".to_string()));
     }
 
+    #[test]
+    fn can_balance_first_letters_when_printing_first_words() {
+        let this_tidy_request = TidyRequest {
+            list: ["sun", "sea", "sky", "salt", "moon", "mist", "air"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            max_per_first_letter: Some(3),
+            balance_first_letters: true,
+            print_first: Some(5),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["sun", "moon", "air", "sea", "mist"]
+        );
+    }
+
+    #[test]
+    fn can_cut_list_to_largest_power_that_fits() {
+        let this_tidy_request = TidyRequest {
+            list: [
+                "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+            ]
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+            cut_to_power: Some(2),
+            cut_strategy: CutStrategy::First,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["one", "two", "three", "four", "five", "six", "seven", "eight"]
+        );
+
+        let this_tidy_request = TidyRequest {
+            cut_to_power: Some(3),
+            cut_strategy: CutStrategy::Random,
+            ..this_tidy_request
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap().len(), 9);
+    }
+
+    #[test]
+    fn can_limit_share_of_long_words() {
+        let this_tidy_request = TidyRequest {
+            list: [
+                "carpenter",
+                "cat",
+                "dog",
+                "elephant",
+                "bee",
+                "mountains",
+                "owl",
+                "kangaroo",
+            ]
+            .iter()
+            .map(|x| x.to_string())
+            .collect(),
+            length_quotas: vec!["8+:25".parse().unwrap()],
+            ..Default::default()
+        };
+        // Words closest to the end go first
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["carpenter", "cat", "dog", "bee", "owl"]
+        );
+
+        let this_tidy_request = TidyRequest {
+            quota_priority: QuotaPriority::Longest,
+            ..this_tidy_request
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["cat", "dog", "elephant", "bee", "owl"]
+        );
+    }
+
+    #[test]
+    fn can_remove_words_with_doubled_letters_or_consonant_clusters() {
+        let list: Vec<String> = ["balloon", "months", "tests", "river", "Sheep", "lamp"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list: list.clone(),
+            should_remove_doubled_letters: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["months", "tests", "river", "lamp"]
+        );
+
+        let this_tidy_request = TidyRequest {
+            list,
+            consonant_clusters: Some(vec!["ths".to_string(), "MP".to_string()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["balloon", "tests", "river", "Sheep"]
+        );
+    }
+
+    #[test]
+    fn can_remove_anagrams() {
+        let this_tidy_request = TidyRequest {
+            list: ["Lemon", "melon", "stop", "pots", "tops", "spot", "apple"]
+                .iter()
+                .map(|x| x.to_string())
+                .collect(),
+            should_remove_anagrams: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["Lemon", "stop", "apple"]
+        );
+
+        // "pots" is 4 edits from "stop", so is kept
+        let this_tidy_request = TidyRequest {
+            anagram_edit_distance: Some(2),
+            ..this_tidy_request
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["Lemon", "stop", "pots", "apple"]
+        );
+    }
+
+    #[test]
+    fn can_truncate_words_to_a_unique_prefix() {
+        let this_tidy_request = TidyRequest {
+            list: make_lists().0,
+            truncate_to_unique_length: Some(3),
+            ..Default::default()
+        };
+        let new_list = tidy_list(&this_tidy_request).unwrap();
+        assert!(new_list.iter().all(|word| word.chars().count() == 3));
+        assert!(new_list.contains(&"zoo".to_string()));
+        assert!(new_list.contains(&"sta".to_string()));
+        assert!(!new_list.contains(&"station".to_string()));
+    }
+
     #[test]
     fn can_remove_reject_words() {
-        let words_to_reject: Vec<String> = vec!["mistake", "carnival"]
+        let words_to_reject: HashSet<String> = ["mistake", "carnival"]
             .iter()
             .map(|x| x.to_string())
             .collect();
@@ -510,7 +977,7 @@ mod list_manipulation_tests {
             to_lowercase: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(!new_list.contains(&"mistake".to_string()));
         assert!(!new_list.contains(&"carnival".to_string()));
         assert!(new_list.contains(&"wizard".to_string()));
@@ -518,7 +985,7 @@ mod list_manipulation_tests {
 
     #[test]
     fn can_remove_all_words_not_on_approved_list_words() {
-        let approved_words: Vec<String> = vec!["take", "vAcation", "airplane"]
+        let approved_words: HashSet<String> = ["take", "vAcation", "airplane"]
             .iter()
             .map(|x| x.to_string())
             .collect();
@@ -528,7 +995,7 @@ mod list_manipulation_tests {
             approved_list: Some(approved_words),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"take".to_string()));
         assert!(new_list.contains(&"vAcation".to_string()));
         assert!(!new_list.contains(&"carnival".to_string()));
@@ -547,7 +1014,7 @@ mod list_manipulation_tests {
             to_lowercase: true,
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
         assert!(new_list.contains(&"tea".to_string()));
         assert!(!new_list.contains(&"tee".to_string()));
         assert!(new_list.contains(&"be".to_string()));
@@ -559,12 +1026,12 @@ mod list_manipulation_tests {
     fn can_sort_accented_and_capitalized_letters_properly() {
         let this_tidy_request = TidyRequest {
             list: make_lists().3,
-            sort_alphabetically: true,
+            sort_by: SortOrder::Alphabetical,
             locale: "es-ES".to_string(),
             normalization_form: Some("nfkd".to_string()),
             ..Default::default()
         };
-        let new_list = tidy_list(this_tidy_request);
+        let new_list = tidy_list(&this_tidy_request).unwrap();
 
         let how_list_should_be_sorted: Vec<String> = vec![
             "abbey",
@@ -585,6 +1052,23 @@ mod list_manipulation_tests {
         assert_eq!(new_list, how_list_should_be_sorted);
     }
 
+    #[test]
+    fn accented_words_sort_next_to_unaccented_ones_rather_than_by_code_point() {
+        let this_tidy_request = TidyRequest {
+            list: ["zoo", "zèbre", "élan", "abricot", "zebu"]
+                .iter()
+                .map(|word| word.to_string())
+                .collect(),
+            sort_by: SortOrder::Alphabetical,
+            locale: "fr".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request).unwrap(),
+            ["abricot", "élan", "zèbre", "zebu", "zoo"]
+        );
+    }
+
     // this is really a WORD manipulation, so maybe should be in a
     // different test file
     use tidy::list_manipulations::normalize_unicode;
@@ -662,19 +1146,695 @@ mod list_manipulation_tests {
 
     #[test]
     fn can_print_dice_rolls_of_base_6() {
-        assert_eq!(print_as_dice(0, 6, 7776, false), "11111".to_string());
-        assert_eq!(print_as_dice(7775, 6, 7776, false), "66666".to_string());
-        assert_eq!(print_as_dice(2548, 6, 7776, false), "26555".to_string());
-        assert_eq!(print_as_dice(2548, 6, 7000, false), "26555".to_string());
+        assert_eq!(
+            print_as_dice(0, 6, 7776, false).unwrap(),
+            "11111".to_string()
+        );
+        assert_eq!(
+            print_as_dice(7775, 6, 7776, false).unwrap(),
+            "66666".to_string()
+        );
+        assert_eq!(
+            print_as_dice(2548, 6, 7776, false).unwrap(),
+            "26555".to_string()
+        );
+        assert_eq!(
+            print_as_dice(2548, 6, 7000, false).unwrap(),
+            "26555".to_string()
+        );
     }
     #[test]
     fn can_print_dice_rolls_of_base_2() {
-        assert_eq!(print_as_dice(1, 2, 7776, true), "0000000000001".to_string());
-        assert_eq!(print_as_dice(127, 2, 128, true), "1111111".to_string());
+        assert_eq!(
+            print_as_dice(1, 2, 7776, true).unwrap(),
+            "0000000000001".to_string()
+        );
+        assert_eq!(
+            print_as_dice(127, 2, 128, true).unwrap(),
+            "1111111".to_string()
+        );
     }
     #[test]
     fn can_print_dice_rolls_of_base_20() {
-        assert_eq!(print_as_dice(1000, 20, 8000, false), "03-11-01".to_string());
-        assert_eq!(print_as_dice(1000, 20, 8000, true), "2A0".to_string());
+        assert_eq!(
+            print_as_dice(1000, 20, 8000, false).unwrap(),
+            "03-11-01".to_string()
+        );
+        assert_eq!(
+            print_as_dice(1000, 20, 8000, true).unwrap(),
+            "2A0".to_string()
+        );
+    }
+    #[test]
+    fn can_print_card_codes_and_coin_flips() {
+        use tidy::cards::print_as_cards;
+        use tidy::coins::print_as_coins;
+        assert_eq!(print_as_cards(0, 676), "B02-B02");
+        assert_eq!(print_as_cards(675, 676), "RAc-RAc");
+        assert_eq!(print_as_coins(0, 7776), "HHHHHHHHHHHHH");
+        assert_eq!(print_as_coins(7775, 8192), "TTTTHHTHTTTTT");
+    }
+    #[test]
+    fn can_make_a_reroll_table_for_list_lengths_that_are_not_powers() {
+        use tidy::dice::{count_unreachable_rolls, make_reroll_table, DiceFormat};
+        assert_eq!(count_unreachable_rolls(1296, 6), 0);
+        assert!(make_reroll_table(1296, 6, false, &DiceFormat::default())
+            .unwrap()
+            .is_empty());
+
+        // 2 dice with 20 sides: rolls after 03-01 and up to 20-20
+        assert_eq!(count_unreachable_rolls(42, 20), 358);
+        let table = make_reroll_table(42, 20, false, &DiceFormat::default()).unwrap();
+        assert_eq!(
+            table,
+            vec![
+                ("03-03".to_string(), "03-20".to_string()),
+                ("04-01".to_string(), "20-20".to_string()),
+            ]
+        );
+        // Every unreachable roll is covered, and no reachable one is
+        let first_unreachable = print_as_dice(42, 20, 42, false).unwrap();
+        let last_reachable = print_as_dice(41, 20, 42, false).unwrap();
+        assert_eq!(table[0].0, first_unreachable);
+        assert_eq!(last_reachable, "03-02");
+    }
+
+    #[test]
+    fn can_schlinkert_prune_with_different_strategies() {
+        use tidy::display_information::uniquely_decodable::check_decodability;
+        use tidy::list_manipulations::schlinkert_prune;
+        use tidy::sardinas_patterson_pruning::SchlinkertStrategy;
+        let list: Vec<String> = [
+            "news",
+            "paper",
+            "newspaper",
+            "elephant",
+            "spill",
+            "sun",
+            "moved",
+            "spills",
+            "unmoved",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        for strategy in [
+            SchlinkertStrategy::All,
+            SchlinkertStrategy::Longest,
+            SchlinkertStrategy::LowestFrequency,
+            SchlinkertStrategy::Fewest,
+        ] {
+            let new_list = schlinkert_prune(&list, strategy);
+            assert!(check_decodability(&new_list));
+            assert!(new_list.contains(&"elephant".to_string()));
+        }
+        let new_list = schlinkert_prune(&list, SchlinkertStrategy::All);
+        assert!(!new_list.contains(&"paper".to_string()));
+        assert!(new_list.contains(&"newspaper".to_string()));
+        let new_list = schlinkert_prune(&list, SchlinkertStrategy::Fewest);
+        assert_eq!(new_list.len(), list.len() - 2);
+    }
+
+    #[test]
+    fn can_drop_repeated_lines_with_bloom_filter_before_tidying() {
+        let lines = vec!["zoo", "Apple", "zoo", "apple", "Apple"];
+        let this_tidy_request = TidyRequest {
+            should_bloom_dedup: true,
+            to_lowercase: true,
+            ..Default::default()
+        };
+        let (new_list, report) = tidy_lines_explained(&lines, &this_tidy_request).unwrap();
+        assert_eq!(new_list, vec!["zoo", "apple"]);
+        assert_eq!(report.step_counts[1], ("bloom-dedup", 3));
+    }
+
+    #[test]
+    fn schlinkert_prune_that_times_out_removes_prefix_words_instead() {
+        use std::time::Duration;
+        let list: Vec<String> = ["news", "paper", "newspaper", "elephant"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list,
+            should_schlinkert_prune: true,
+            schlinkert_timeout: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let (new_list, report) = tidy_list_explained(&this_tidy_request).unwrap();
+        assert!(report.schlinkert_timed_out);
+        assert_eq!(new_list, vec!["paper", "newspaper", "elephant"]);
+    }
+
+    #[test]
+    fn schlinkert_prune_can_report_ambiguous_strings_it_found() {
+        let list: Vec<String> = ["news", "paper", "newspaper", "elephant"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list,
+            should_schlinkert_prune: true,
+            ambiguity_examples: Some(5),
+            ..Default::default()
+        };
+        let (new_list, report) = tidy_list_explained(&this_tidy_request).unwrap();
+        assert_eq!(report.ambiguities.len(), 1);
+        let (first, second) = &report.ambiguities[0];
+        assert_eq!(first, &["newspaper"]);
+        assert_eq!(second, &["news", "paper"]);
+        assert!(
+            !new_list.contains(&"news".to_string()) || !new_list.contains(&"paper".to_string())
+        );
+    }
+
+    #[test]
+    fn interrupted_request_returns_partial_report() {
+        use std::sync::atomic::AtomicBool;
+        use std::sync::Arc;
+        use tidy::error::TidyError;
+        let this_tidy_request = TidyRequest {
+            list: vec!["news".to_string(), "newspaper".to_string()],
+            should_schlinkert_prune: true,
+            interrupted: Some(Arc::new(AtomicBool::new(true))),
+            ..Default::default()
+        };
+        match tidy_list_explained(&this_tidy_request) {
+            Err(TidyError::Interrupted(report)) => {
+                assert_eq!(report.step_counts[0], ("input", 2));
+                assert!(!report
+                    .step_counts
+                    .iter()
+                    .any(|(step, _)| *step == "schlinkert-prune"));
+            }
+            other => panic!("Expected an interrupted error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_whittle_list_to_exact_length() {
+        use tidy::whittle_list;
+        let list: Vec<String> = [
+            "news",
+            "newspaper",
+            "paper",
+            "elephant",
+            "zoo",
+            "zookeeper",
+            "apple",
+            "banana",
+            "cherry",
+            "date",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let this_tidy_request = TidyRequest {
+            list,
+            should_remove_prefix_words: true,
+            ..Default::default()
+        };
+        let (new_list, report) = whittle_list(&this_tidy_request, 5, 7).unwrap();
+        assert_eq!(new_list.len(), 5);
+        // Takes words from the start of the list
+        assert!(new_list.contains(&"newspaper".to_string()));
+        assert!(!new_list.contains(&"date".to_string()));
+
+        assert_eq!(report.step_counts.last(), Some(&("dedup", 5)));
+        // Word steps are timed once, then the guesses that weren't kept, then
+        // each step done to the list taken
+        let timed_stages: Vec<&str> = report
+            .step_timings
+            .iter()
+            .map(|(stage, _elapsed)| *stage)
+            .collect();
+        assert_eq!(
+            timed_stages,
+            ["word-steps", "whittle", "remove-prefix-words", "dedup"]
+        );
+
+        assert!(whittle_list(&this_tidy_request, 9, 7).is_err());
+    }
+
+    #[test]
+    fn whittle_reports_closest_lengths_when_length_is_unreachable() {
+        use tidy::error::TidyError;
+        use tidy::whittle_list;
+        let list: Vec<String> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        // Cutting to a power of 2 can only make lists of 1, 2, or 4 words
+        let this_tidy_request = TidyRequest {
+            list,
+            cut_to_power: Some(2),
+            cut_strategy: CutStrategy::First,
+            ..Default::default()
+        };
+        match whittle_list(&this_tidy_request, 3, 2) {
+            Err(TidyError::WhittleLengthUnreachable {
+                words_taken,
+                length,
+                shorter_length,
+                guesses,
+                ..
+            }) => {
+                assert_eq!((words_taken, length, shorter_length), (4, 4, 2));
+                assert!(guesses > 0);
+            }
+            other => panic!("Expected an unreachable length, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn whittle_can_finish_by_cutting_a_list_that_is_too_long() {
+        use tidy::filters::CustomStep;
+        use tidy::pipeline::PipelineStep;
+        use tidy::whittle_list;
+        let list: Vec<String> = ["a", "b", "c"].iter().map(|x| x.to_string()).collect();
+        // Doubling each word means lists can only have an even number of words
+        let double = CustomStep::list("double", |list: &[String]| {
+            list.iter()
+                .flat_map(|word| vec![word.to_string(), format!("{}2", word)])
+                .collect()
+        });
+        let this_tidy_request = TidyRequest {
+            list,
+            custom_steps: vec![(PipelineStep::Dedup, double)],
+            ..Default::default()
+        };
+        assert!(whittle_list(&this_tidy_request, 3, 1).is_err());
+
+        let finishing_request = TidyRequest {
+            whittle_finish: Some(CutStrategy::First),
+            ..this_tidy_request.clone()
+        };
+        let (new_list, _report) = whittle_list(&finishing_request, 3, 1).unwrap();
+        assert_eq!(new_list, vec!["a", "a2", "b"]);
+
+        let finishing_request = TidyRequest {
+            whittle_finish: Some(CutStrategy::Random),
+            ..this_tidy_request
+        };
+        let (new_list, _report) = whittle_list(&finishing_request, 3, 1).unwrap();
+        assert_eq!(new_list.len(), 3);
+    }
+
+    #[test]
+    fn whittle_can_resume_from_a_checkpoint() {
+        use std::sync::{Arc, Mutex};
+        use tidy::checkpoint::{CheckpointCallback, WhittleCheckpoint};
+        use tidy::whittle_list;
+        let list: Vec<String> = [
+            "news",
+            "newspaper",
+            "paper",
+            "elephant",
+            "zoo",
+            "zookeeper",
+            "apple",
+            "banana",
+            "cherry",
+            "date",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let checkpoints = Arc::new(Mutex::new(vec![]));
+        let checkpoints_to_push_to = checkpoints.clone();
+        let this_tidy_request = TidyRequest {
+            list,
+            should_remove_prefix_words: true,
+            whittle_checkpoint: Some(CheckpointCallback::new(move |checkpoint| {
+                checkpoints_to_push_to
+                    .lock()
+                    .unwrap()
+                    .push(checkpoint.clone());
+            })),
+            ..Default::default()
+        };
+        let (new_list, report) = whittle_list(&this_tidy_request, 5, 7).unwrap();
+        let checkpoints = checkpoints.lock().unwrap().clone();
+        let last_checkpoint = checkpoints.last().unwrap();
+        assert_eq!(last_checkpoint.low, last_checkpoint.high);
+
+        // Resuming from partway through makes the same list in fewer guesses
+        let resumed_request = TidyRequest {
+            whittle_resume_from: Some(checkpoints[0].clone()),
+            ..this_tidy_request.clone()
+        };
+        let (resumed_list, resumed_report) = whittle_list(&resumed_request, 5, 7).unwrap();
+        assert_eq!(resumed_list, new_list);
+        assert!(resumed_report.whittle_guesses.len() < report.whittle_guesses.len());
+
+        // A checkpoint from a whittle to another length is ignored
+        let mismatched_request = TidyRequest {
+            whittle_resume_from: Some(WhittleCheckpoint {
+                length_to_whittle_to: 4,
+                ..checkpoints[0].clone()
+            }),
+            ..this_tidy_request
+        };
+        assert_eq!(whittle_list(&mismatched_request, 5, 7).unwrap().0, new_list);
+    }
+
+    #[test]
+    fn can_remove_infix_words() {
+        let this_tidy_request = TidyRequest {
+            list: make_lists().0,
+            should_remove_infix_words: true,
+            ..Default::default()
+        };
+        let new_list = tidy_list(&this_tidy_request).unwrap();
+        // Prefix and suffix words are infix words too
+        assert!(!new_list.contains(&"station".to_string()));
+        assert!(!new_list.contains(&"zoo".to_string()));
+        assert!(!new_list.contains(&"keeper".to_string()));
+        assert!(new_list.contains(&"zookeeper".to_string()));
+        assert!(new_list.contains(&"apple".to_string()));
+    }
+
+    #[test]
+    fn tidied_words_keep_their_source_and_original_line() {
+        use tidy::words::{tidy_words, words_from_lists};
+        let lists = vec![
+            vec!["  Zoo".to_string(), "apple".to_string()],
+            vec!["zoo".to_string(), "Pear".to_string()],
+        ];
+        let this_tidy_request = TidyRequest {
+            to_lowercase: true,
+            sort_by: SortOrder::Alphabetical,
+            ..Default::default()
+        };
+        let (words, _report) = tidy_words(&words_from_lists(&lists), &this_tidy_request).unwrap();
+        let texts: Vec<&str> = words.iter().map(|word| word.text.as_str()).collect();
+        assert_eq!(texts, ["apple", "pear", "zoo"]);
+        assert_eq!(words[1].source, Some(1));
+        assert_eq!(words[1].original, "Pear");
+        assert_eq!(words[2].source, Some(0));
+        assert_eq!(words[2].original, "  Zoo");
+    }
+
+    #[test]
+    fn can_find_infix_words_with_accents_and_duplicates() {
+        use tidy::list_manipulations::find_infix_words;
+        let list: Vec<String> = ["décafé", "caf", "zoo", "zoo", "é", "oo"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let infix_words = find_infix_words(&list);
+        assert_eq!(infix_words.len(), 3);
+        assert!(infix_words.contains("caf"));
+        assert!(infix_words.contains("é"));
+        assert!(infix_words.contains("oo"));
+    }
+
+    #[test]
+    fn can_find_prefix_and_suffix_words_with_accents_and_duplicates() {
+        use tidy::list_manipulations::{find_prefix_words, find_suffix_words};
+        let list: Vec<String> = ["café", "décafé", "fé", "zoo", "zoo", "zoology"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let prefix_words = find_prefix_words(&list);
+        assert_eq!(prefix_words.len(), 1);
+        assert!(prefix_words.contains("zoo"));
+        let suffix_words = find_suffix_words(&list);
+        assert_eq!(suffix_words.len(), 2);
+        assert!(suffix_words.contains("café"));
+        assert!(suffix_words.contains("fé"));
+    }
+
+    #[test]
+    fn can_run_steps_in_a_custom_pipeline_order() {
+        use tidy::pipeline::{complete_pipeline, PipelineStep};
+        let list: Vec<String> = ["Apple", "banana", "Cherry"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let reject_list: HashSet<String> = ["apple".to_string()].into_iter().collect();
+        let default_request = TidyRequest {
+            list,
+            to_lowercase: true,
+            reject_list: Some(reject_list),
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&default_request).unwrap(),
+            vec!["banana", "cherry"]
+        );
+
+        // Rejecting words before lowercasing them keeps "Apple"
+        let custom_request = TidyRequest {
+            pipeline: Some(complete_pipeline(&[PipelineStep::Reject]).unwrap()),
+            ..default_request
+        };
+        let (new_list, report) = tidy_list_explained(&custom_request).unwrap();
+        assert_eq!(new_list, vec!["apple", "banana", "cherry"]);
+        assert_eq!(
+            report.step_counts,
+            vec![("input", 3), ("reject", 3), ("lowercase", 3), ("dedup", 3)]
+        );
+    }
+
+    #[test]
+    fn can_run_custom_steps_after_pipeline_steps() {
+        use tidy::filters::{CustomStep, WordFilter};
+        use tidy::pipeline::PipelineStep;
+        struct NoQ;
+        impl WordFilter for NoQ {
+            fn name(&self) -> &str {
+                "no-q"
+            }
+            fn keep(&self, word: &str) -> bool {
+                !word.contains('q')
+            }
+        }
+        let list: Vec<String> = ["Apple", "quiet", "banana", "Quince"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list,
+            to_lowercase: true,
+            custom_steps: vec![
+                (
+                    PipelineStep::Lowercase,
+                    CustomStep::Filter(std::sync::Arc::new(NoQ)),
+                ),
+                (
+                    PipelineStep::Dedup,
+                    CustomStep::transform("reverse", |word: &str| word.chars().rev().collect()),
+                ),
+            ],
+            ..Default::default()
+        };
+        let (new_list, report) = tidy_list_explained(&this_tidy_request).unwrap();
+        assert_eq!(new_list, vec!["elppa", "ananab"]);
+        assert_eq!(
+            report.step_counts,
+            vec![("input", 4), ("lowercase", 2), ("dedup", 2)]
+        );
+    }
+
+    #[test]
+    fn can_run_a_custom_step_on_the_whole_list() {
+        use tidy::filters::CustomStep;
+        use tidy::pipeline::PipelineStep;
+        let list: Vec<String> = ["cat", "dog", "cow", "pig"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        // Keeps every other word, which no word step could do
+        let every_other = CustomStep::list("every-other", |list: &[String]| {
+            list.iter().step_by(2).cloned().collect()
+        });
+        let this_tidy_request = TidyRequest {
+            list,
+            custom_steps: vec![(PipelineStep::Dedup, every_other)],
+            ..Default::default()
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), vec!["cat", "cow"]);
+    }
+
+    #[test]
+    fn refuses_to_run_a_whole_list_step_after_a_word_step() {
+        use tidy::error::TidyError;
+        use tidy::filters::CustomStep;
+        use tidy::pipeline::PipelineStep;
+        let every_other = CustomStep::list("every-other", |list: &[String]| {
+            list.iter().step_by(2).cloned().collect()
+        });
+        let this_tidy_request = TidyRequest {
+            list: vec!["cat".to_string(), "dog".to_string()],
+            to_lowercase: true,
+            custom_steps: vec![(PipelineStep::Lowercase, every_other)],
+            ..Default::default()
+        };
+        assert_eq!(
+            tidy_list(&this_tidy_request),
+            Err(TidyError::ListStepAfterWordStep {
+                custom: "every-other".to_string(),
+                step: "lowercase".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn can_report_progress_through_slow_operations() {
+        use std::sync::{Arc, Mutex};
+        use tidy::progress::ProgressCallback;
+        let updates = Arc::new(Mutex::new(vec![]));
+        let updates_to_push_to = updates.clone();
+        let list: Vec<String> = ["cat", "bat", "horse"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list,
+            minimum_edit_distance: Some(2),
+            progress: Some(ProgressCallback::new(move |task, done, total| {
+                updates_to_push_to
+                    .lock()
+                    .unwrap()
+                    .push((task.to_string(), done, total));
+            })),
+            ..Default::default()
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), vec!["horse"]);
+        let updates = updates.lock().unwrap();
+        assert_eq!(updates.len(), 4);
+        assert_eq!(
+            updates.last(),
+            Some(&("Enforcing minimum edit distance".to_string(), 3, 3))
+        );
+    }
+
+    #[test]
+    fn can_delete_before_the_last_or_every_delimiter() {
+        use tidy::list_manipulations::DelimiterOccurrence;
+        let list: Vec<String> = ["11111\tabacus\tnoun", "11112\tabbey\tnoun"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list: list.clone(),
+            should_delete_before_first_delimiter: Some('t'),
+            delimiter_occurrence: DelimiterOccurrence::Last,
+            ..Default::default()
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), ["noun"]);
+
+        let this_tidy_request = TidyRequest {
+            list,
+            should_delete_before_first_delimiter: Some('t'),
+            delimiter_occurrence: DelimiterOccurrence::All,
+            ..Default::default()
+        };
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), ["abacus", "abbey"]);
+    }
+
+    #[test]
+    fn can_take_one_field_of_each_line() {
+        let list: Vec<String> = ["11111\tAbacus\tnoun", "11112\tabbey", "11113"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let this_tidy_request = TidyRequest {
+            list,
+            field_number: Some(2),
+            field_separator: Some('t'),
+            to_lowercase: true,
+            ..Default::default()
+        };
+        // The last line has no second field, so is removed
+        assert_eq!(tidy_list(&this_tidy_request).unwrap(), ["abacus", "abbey"]);
+    }
+
+    #[test]
+    fn can_split_prose_into_words_by_how_often_they_appear() {
+        use tidy::list_manipulations::tokenize_prose;
+        let lines: Vec<String> = [
+            "It was the best of times, it was the worst of times;",
+            "it was the age of wisdom...",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let tokens = tokenize_prose(&lines);
+        assert_eq!(tokens[0], ("was".to_string(), 3));
+        assert_eq!(tokens[1], ("the".to_string(), 3));
+        assert!(tokens.contains(&("It".to_string(), 1)));
+        assert!(tokens.contains(&("it".to_string(), 2)));
+        assert!(!tokens.iter().any(|(word, _count)| word.contains(',')));
+        assert_eq!(tokens.iter().map(|(_word, count)| count).sum::<usize>(), 18);
+    }
+
+    #[test]
+    fn counts_of_words_tidied_together_are_added_up() {
+        use tidy::list_manipulations::tokenize_prose;
+        use tidy::words::count_tidied_words;
+        let lines: Vec<String> = [
+            "It was the best of times, it was the worst of times;",
+            "it was the age of wisdom...",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let tokens = tokenize_prose(&lines);
+        let this_tidy_request = TidyRequest {
+            list: tokens
+                .iter()
+                .map(|(word, _count)| word.to_string())
+                .collect(),
+            to_lowercase: true,
+            minimum_length: Some(2),
+            sort_by: SortOrder::Alphabetical,
+            ..Default::default()
+        };
+        let new_list = tidy_list(&this_tidy_request).unwrap();
+        assert_eq!(
+            new_list,
+            ["age", "best", "it", "of", "the", "times", "was", "wisdom", "worst"]
+        );
+        // "It" and "it" are counted together
+        assert_eq!(
+            count_tidied_words(&new_list, &tokens, &this_tidy_request),
+            [1, 1, 3, 3, 3, 2, 3, 1, 1]
+        );
+    }
+
+    #[test]
+    fn can_split_prose_into_phrases_that_stay_whole_through_tidying() {
+        use tidy::list_manipulations::tokenize_prose_into_ngrams;
+        let lines: Vec<String> = [
+            "It was the best of times, it was the worst of times;",
+            "it was the age of wisdom...",
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect();
+        let phrases = tokenize_prose_into_ngrams(&lines, 2);
+        assert_eq!(phrases[0], ("was the".to_string(), 3));
+        assert_eq!(phrases[1], ("of times".to_string(), 2));
+        assert_eq!(phrases[2], ("it was".to_string(), 2));
+        // Phrases don't run across punctuation or lines
+        assert!(!phrases.iter().any(|(phrase, _count)| phrase == "times it"));
+        assert_eq!(tokenize_prose_into_ngrams(&lines, 4).len(), 9);
+
+        let this_tidy_request = TidyRequest {
+            list: phrases
+                .iter()
+                .map(|(phrase, _count)| phrase.to_string())
+                .collect(),
+            to_lowercase: true,
+            join_multiword_separator: Some("-".to_string()),
+            ..Default::default()
+        };
+        let new_list = tidy_list(&this_tidy_request).unwrap();
+        assert!(new_list.contains(&"it-was".to_string()));
+        assert!(new_list.contains(&"the-worst".to_string()));
+        // "It was" and "it was" are one entry
+        assert_eq!(new_list.len(), phrases.len() - 1);
     }
 }
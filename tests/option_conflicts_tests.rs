@@ -0,0 +1,99 @@
+mod option_conflicts_tests {
+    use tidy::option_conflicts::*;
+    use tidy::*;
+
+    /// Makes a request, and output options, that use only the named option
+    fn using_only(option: &str) -> (TidyRequest, OutputOptions) {
+        let mut req = TidyRequest::default();
+        let mut output = OutputOptions::default();
+        match option {
+            "--delete-before" => req.should_delete_before_first_delimiter = Some(':'),
+            "--delete-after" => req.should_delete_after_first_delimiter = Some(':'),
+            "--dice" => output.dice_sides = Some(6),
+            "--sides-as-base" => output.print_dice_sides_as_their_base = true,
+            _ => panic!("No test request for {}", option),
+        }
+        (req, output)
+    }
+
+    #[test]
+    fn every_option_that_clashes_with_ignoring_metadata_is_named() {
+        for (option, _uses_option) in IGNORE_DELIMITER_CONFLICTS {
+            let (mut req, output) = using_only(option);
+            req.ignore_after_delimiter = Some(',');
+            assert_eq!(
+                validate_and_parse_ignore_options(&req, &output),
+                Err(format!(
+                    "Error: --ignore-after can't be used with {}.",
+                    option
+                ))
+            );
+
+            let (mut req, output) = using_only(option);
+            req.ignore_before_delimiter = Some(',');
+            assert_eq!(
+                validate_and_parse_ignore_options(&req, &output),
+                Err(format!(
+                    "Error: --ignore-before can't be used with {}.",
+                    option
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn each_conflict_check_only_spots_its_own_option() {
+        for (option, _uses_option) in IGNORE_DELIMITER_CONFLICTS {
+            let (req, output) = using_only(option);
+            for (other_option, uses_other_option) in IGNORE_DELIMITER_CONFLICTS {
+                assert_eq!(
+                    uses_other_option(&req, &output),
+                    option == other_option,
+                    "{} was spotted in a request that only uses {}",
+                    other_option,
+                    option
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn options_that_do_not_clash_are_allowed_with_ignoring_metadata() {
+        let req = TidyRequest {
+            ignore_after_delimiter: Some(','),
+            minimum_length: Some(3),
+            to_lowercase: true,
+            should_delete_integers: true,
+            should_remove_prefix_words: true,
+            minimum_edit_distance: Some(2),
+            truncate_to_unique_length: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(
+            validate_and_parse_ignore_options(&req, &OutputOptions::default()),
+            Ok((Some(','), None))
+        );
+    }
+
+    #[test]
+    fn requests_without_ignore_delimiters_never_clash() {
+        for (option, _uses_option) in IGNORE_DELIMITER_CONFLICTS {
+            let (req, output) = using_only(option);
+            assert_eq!(
+                validate_and_parse_ignore_options(&req, &output),
+                Ok((None, None))
+            );
+        }
+    }
+
+    #[test]
+    fn can_not_ignore_metadata_on_both_sides() {
+        let req = TidyRequest {
+            ignore_after_delimiter: Some(','),
+            ignore_before_delimiter: Some(','),
+            ..Default::default()
+        };
+        let error = validate_and_parse_ignore_options(&req, &OutputOptions::default()).unwrap_err();
+        assert!(error.contains("--ignore-after can't be used with --ignore-before"));
+    }
+}
@@ -1,5 +1,7 @@
 mod parse_tests {
-    use tidy::parsers::eval_list_length;
+    use tidy::dice::print_as_dice;
+    use tidy::list_manipulations::pin_words;
+    use tidy::parsers::{eval_list_length, parse_pinned_words};
 
     #[test]
     fn can_parse_print_rand() {
@@ -18,4 +20,19 @@ mod parse_tests {
     fn panics_when_too_many_exponents_inputted_to_print_rand() {
         assert!(eval_list_length("2**4**3").is_err());
     }
+
+    #[test]
+    fn can_pin_words_to_rolls_tidy_printed() {
+        let list: Vec<String> = (0..500).map(|n| format!("word{}", n)).collect();
+        let lines = vec![
+            format!("{}\tword7", print_as_dice(42, 20, 500, false).unwrap()),
+            format!("{}\tword0", print_as_dice(499, 20, 500, false).unwrap()),
+        ];
+        let pins = parse_pinned_words(&lines, Some(20), false).unwrap();
+        let pinned_list = pin_words(list, &pins).unwrap();
+        assert_eq!(pinned_list[42], "word7");
+        assert_eq!(pinned_list[499], "word0");
+        assert_eq!(pinned_list[0], "word1");
+        assert_eq!(pinned_list.len(), 500);
+    }
 }
@@ -0,0 +1,54 @@
+mod sampling_tests {
+    use tidy::sampling::*;
+    use tidy::*;
+
+    fn make_request(rand_strategy: RandStrategy, rand_seed: Option<u64>) -> TidyRequest {
+        TidyRequest {
+            list: (0..1000).map(|n| format!("word{}", n)).collect(),
+            print_rand: Some(20),
+            rand_strategy,
+            rand_seed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_seed_cuts_list_the_same_way_with_each_strategy() {
+        for strategy in [
+            RandStrategy::Shuffle,
+            RandStrategy::Reservoir,
+            RandStrategy::Stride,
+        ] {
+            let first_cut = tidy_list(&make_request(strategy, Some(42))).unwrap();
+            assert_eq!(first_cut.len(), 20);
+            assert_eq!(
+                first_cut,
+                tidy_list(&make_request(strategy, Some(42))).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn reservoir_sample_picks_distinct_items_from_a_stream() {
+        let mut picked = reservoir_sample((0..10_000).map(|n| n * 2), 100, &mut make_rng(Some(1)));
+        assert_eq!(picked.len(), 100);
+        assert!(picked.iter().all(|n| n % 2 == 0));
+        picked.sort();
+        picked.dedup();
+        assert_eq!(picked.len(), 100);
+    }
+
+    #[test]
+    fn stride_sample_keeps_order_of_items() {
+        let items: Vec<usize> = (0..1000).collect();
+        let picked = sample(&items, 7, RandStrategy::Stride, &mut make_rng(None));
+        assert_eq!(picked.len(), 7);
+        assert!(picked.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn can_parse_rand_strategies() {
+        assert_eq!("Reservoir".parse(), Ok(RandStrategy::Reservoir));
+        assert!("bogus".parse::<RandStrategy>().is_err());
+    }
+}
@@ -0,0 +1,28 @@
+mod suggestions_tests {
+    use tidy::suggestions::*;
+    use tidy::*;
+
+    #[test]
+    fn only_suggests_relaxations_that_make_the_list_longer() {
+        let lines = vec!["news", "newspaper", "paper", "paperwork", "cat", "catalog"];
+        let req = TidyRequest {
+            should_remove_prefix_words: true,
+            maximum_length: Some(8),
+            ..Default::default()
+        };
+        let (list, _report) = tidy_lines_explained(&lines, &req).unwrap();
+        assert_eq!(list.len(), 3);
+        // Allowing longer words would only remove more prefix words, so
+        // isn't suggested
+        assert_eq!(
+            suggest_relaxations(&lines, &req, list.len()),
+            vec![("without --remove-prefix".to_string(), 4)]
+        );
+    }
+
+    #[test]
+    fn does_not_suggest_relaxing_unused_filters() {
+        let req = TidyRequest::default();
+        assert!(find_relaxations(&req).is_empty());
+    }
+}
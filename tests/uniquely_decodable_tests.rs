@@ -1,5 +1,7 @@
 mod uniquely_decodable_tests {
+    use tidy::compound_collisions::*;
     use tidy::display_information::uniquely_decodable::check_decodability;
+    use tidy::display_information::uniquely_decodable::find_ambiguous_concatenation;
 
     #[test]
     fn can_determine_a_list_with_prefix_words_is_not_uniquely_decodable() {
@@ -89,4 +91,55 @@ mod uniquely_decodable_tests {
         .collect();
         assert!(check_decodability(&list));
     }
+
+    #[test]
+    fn can_find_an_ambiguous_concatenation() {
+        // "spill" + "sun" + "moved" spells the same as "spills" + "unmoved"
+        let list: Vec<String> = ["spill", "sun", "moved", "spills", "unmoved"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let (first, second) = find_ambiguous_concatenation(&list).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first.concat(), second.concat());
+        assert_eq!(first.concat(), "spillsunmoved");
+    }
+
+    #[test]
+    fn finds_no_ambiguous_concatenation_in_uniquely_decodable_list() {
+        let list: Vec<String> = ["excursion", "friday", "gyration", "natural", "zippy"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        assert!(check_decodability(&list));
+        assert_eq!(find_ambiguous_concatenation(&list), None);
+    }
+
+    #[test]
+    fn can_find_every_compound_collision() {
+        let list: Vec<String> = ["spill", "sun", "spills", "un", "news", "paper", "newspaper"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let collisions = find_compound_collisions(&list);
+        assert_eq!(collisions.len(), 2);
+        for (first, second) in &collisions {
+            assert_ne!(first, second);
+            assert_eq!(first.concat(), second.concat());
+        }
+        assert_eq!(collisions[0].0.concat(), "newspaper");
+        assert_eq!(collisions[1].0, vec!["spill", "sun"]);
+        assert_eq!(collisions[1].1, vec!["spills", "un"]);
+    }
+
+    #[test]
+    fn removing_compound_collisions_leaves_none() {
+        let list: Vec<String> = ["spill", "sun", "spills", "un", "news", "paper", "newspaper"]
+            .iter()
+            .map(|x| x.to_string())
+            .collect();
+        let new_list = remove_compound_collisions(&list);
+        assert_eq!(new_list, ["spill", "sun", "un", "news", "paper"]);
+        assert!(find_compound_collisions(&new_list).is_empty());
+    }
 }
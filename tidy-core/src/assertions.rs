@@ -0,0 +1,105 @@
+//! Check a finished word list against constraints the user requires of it,
+//! like an exact length, so that scripts can tell a good list from a bad one
+
+use crate::list_manipulations::get_prefix;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Constraints a finished list must meet. Constraints left as `None` aren't
+/// checked.
+#[derive(Default, Debug, Clone)]
+pub struct ListAssertions {
+    /// The list must have exactly this many words
+    pub length: Option<usize>,
+    /// Knowing this many of the first characters of any word must be enough
+    /// to know which word it is
+    pub unique_prefix: Option<usize>,
+    /// None of these words may be on the list
+    pub free_of: Option<HashSet<String>>,
+}
+
+/// The ways a list can fail a `ListAssertions`
+#[derive(Debug, Clone, PartialEq)]
+pub enum AssertionFailure {
+    /// The list's length, and the length it must be
+    WrongLength(usize, usize),
+    /// Two words with the same first characters, and how many characters
+    /// must be unique
+    SharedPrefix(String, String, usize),
+    ForbiddenWord(String),
+}
+
+impl fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssertionFailure::WrongLength(length, required_length) => write!(
+                f,
+                "List has {} words, but --assert-length requires exactly {}",
+                length, required_length
+            ),
+            AssertionFailure::SharedPrefix(word1, word2, prefix_length) => write!(
+                f,
+                "\"{}\" and \"{}\" share their first {} characters, but --assert-unique-prefix requires them to differ",
+                word1, word2, prefix_length
+            ),
+            AssertionFailure::ForbiddenWord(word) => write!(
+                f,
+                "\"{}\" is on the list, but --assert-free-of forbids it",
+                word
+            ),
+        }
+    }
+}
+
+/// Checks a finished list against the given assertions, returning every
+/// failure found. An empty Vector means the list meets them all.
+/// ```
+/// use tidy_core::assertions::{find_assertion_failures, AssertionFailure, ListAssertions};
+/// let list: Vec<String> = vec!["abandon".to_string(), "abandoned".to_string()];
+/// let assertions = ListAssertions {
+///     length: Some(2),
+///     unique_prefix: Some(4),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     find_assertion_failures(&list, &assertions),
+///     vec![AssertionFailure::SharedPrefix(
+///         "abandon".to_string(),
+///         "abandoned".to_string(),
+///         4
+///     )]
+/// );
+/// ```
+pub fn find_assertion_failures(
+    list: &[String],
+    assertions: &ListAssertions,
+) -> Vec<AssertionFailure> {
+    let mut failures = vec![];
+    if let Some(required_length) = assertions.length {
+        if list.len() != required_length {
+            failures.push(AssertionFailure::WrongLength(list.len(), required_length));
+        }
+    }
+    if let Some(prefix_length) = assertions.unique_prefix {
+        let mut words_by_prefix: HashMap<String, &String> = HashMap::new();
+        for word in list {
+            if let Some(existing_word) =
+                words_by_prefix.insert(get_prefix(word, prefix_length), word)
+            {
+                failures.push(AssertionFailure::SharedPrefix(
+                    existing_word.to_string(),
+                    word.to_string(),
+                    prefix_length,
+                ));
+            }
+        }
+    }
+    if let Some(ref forbidden_words) = assertions.free_of {
+        for word in list {
+            if forbidden_words.contains(word) {
+                failures.push(AssertionFailure::ForbiddenWord(word.to_string()));
+            }
+        }
+    }
+    failures
+}
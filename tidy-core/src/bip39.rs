@@ -0,0 +1,112 @@
+//! Check a word list against the constraints of the BIP39 standard
+
+use crate::count_characters;
+use crate::list_manipulations::get_prefix;
+use std::collections::HashMap;
+use std::fmt;
+use unicode_normalization::is_nfkd;
+
+/// BIP39 word lists must have exactly this many words (2**11)
+pub const BIP39_LIST_LENGTH: usize = 2048;
+/// Knowing the first 4 characters of any BIP39 word must be enough to know
+/// which word it is.
+pub const BIP39_UNIQUE_PREFIX_LENGTH: usize = 4;
+/// No word on the English BIP39 list is longer than 8 characters.
+pub const BIP39_MAXIMUM_WORD_LENGTH: usize = 8;
+
+/// The ways a word list can fail to comply with BIP39.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Bip39Violation {
+    WrongListLength(usize),
+    SharedPrefix(String, String),
+    WordTooLong(String),
+    NotNfkdNormalized(String),
+}
+
+impl fmt::Display for Bip39Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Bip39Violation::WrongListLength(length) => write!(
+                f,
+                "List has {} words, but BIP39 requires exactly {}",
+                length, BIP39_LIST_LENGTH
+            ),
+            Bip39Violation::SharedPrefix(word1, word2) => write!(
+                f,
+                "\"{}\" and \"{}\" share their first {} characters",
+                word1, word2, BIP39_UNIQUE_PREFIX_LENGTH
+            ),
+            Bip39Violation::WordTooLong(word) => write!(
+                f,
+                "\"{}\" is longer than {} characters",
+                word, BIP39_MAXIMUM_WORD_LENGTH
+            ),
+            Bip39Violation::NotNfkdNormalized(word) => {
+                write!(f, "\"{}\" is not NFKD-normalized", word)
+            }
+        }
+    }
+}
+
+/// Checks a word list against the BIP39 constraints, returning every
+/// violation found. An empty Vector means the list is BIP39-compliant.
+/// ```
+/// use tidy_core::bip39::{find_bip39_violations, Bip39Violation};
+/// let list: Vec<String> = vec!["abandon".to_string(), "abandoned".to_string()];
+/// let violations = find_bip39_violations(&list);
+/// assert!(violations.contains(&Bip39Violation::WrongListLength(2)));
+/// assert!(violations.contains(&Bip39Violation::WordTooLong("abandoned".to_string())));
+/// ```
+pub fn find_bip39_violations(list: &[String]) -> Vec<Bip39Violation> {
+    let mut violations = vec![];
+    if list.len() != BIP39_LIST_LENGTH {
+        violations.push(Bip39Violation::WrongListLength(list.len()));
+    }
+    let mut words_by_prefix: HashMap<String, &String> = HashMap::new();
+    for word in list {
+        if let Some(existing_word) =
+            words_by_prefix.insert(get_prefix(word, BIP39_UNIQUE_PREFIX_LENGTH), word)
+        {
+            violations.push(Bip39Violation::SharedPrefix(
+                existing_word.to_string(),
+                word.to_string(),
+            ));
+        }
+        if count_characters(word) > BIP39_MAXIMUM_WORD_LENGTH {
+            violations.push(Bip39Violation::WordTooLong(word.to_string()));
+        }
+        if !is_nfkd(word) {
+            violations.push(Bip39Violation::NotNfkdNormalized(word.to_string()));
+        }
+    }
+    violations
+}
+
+use crate::TidyRequest;
+/// Tightens the options of a `TidyRequest` so that the resulting list meets
+/// the BIP39 word constraints: words are NFKD-normalized, unique within their
+/// first 4 characters, and no longer than 8 characters. Errors if the user
+/// asked for a different normalization form.
+///
+/// Note that this does not cut the list to 2,048 words. Use a whittle-to or
+/// print option for that.
+pub fn apply_bip39_constraints(mut req: TidyRequest) -> Result<TidyRequest, String> {
+    if let Some(ref nf) = req.normalization_form {
+        if nf.to_lowercase() != "nfkd" {
+            return Err(format!(
+                "Error: BIP39 requires NFKD normalization, but {} was requested.",
+                nf
+            ));
+        }
+    }
+    req.normalization_form = Some("nfkd".to_string());
+    req.maximum_length = Some(match req.maximum_length {
+        Some(maximum_length) => maximum_length.min(BIP39_MAXIMUM_WORD_LENGTH),
+        None => BIP39_MAXIMUM_WORD_LENGTH,
+    });
+    req.maximum_shared_prefix_length = Some(match req.maximum_shared_prefix_length {
+        Some(prefix_length) => prefix_length.min(BIP39_UNIQUE_PREFIX_LENGTH),
+        None => BIP39_UNIQUE_PREFIX_LENGTH,
+    });
+    Ok(req)
+}
@@ -0,0 +1,101 @@
+//! A Bloom filter, for cheaply finding which lines of a very large input
+//! might be duplicates before doing an exact de-duplication
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// How often a Bloom filter made by `dedup_lines_with_bloom_filter` wrongly
+/// says it might have seen a line. Such lines are only checked exactly, so
+/// this affects speed and memory use, not which lines are kept.
+pub const BLOOM_FILTER_FALSE_POSITIVE_RATE: f64 = 0.01;
+
+/// A set that can say an item is definitely not in it, or that it might be,
+/// using far less memory than a `HashSet`.
+/// ```
+/// use tidy_core::bloom_filter::BloomFilter;
+/// let mut seen = BloomFilter::new(1000, 0.01);
+/// seen.insert("apple");
+/// assert!(seen.might_contain("apple"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    number_of_bits: u64,
+    number_of_hashes: u32,
+}
+
+impl BloomFilter {
+    /// Makes a Bloom filter sized so that, after `expected_items` items are
+    /// inserted, it wrongly says it might contain an item about
+    /// `false_positive_rate` of the time.
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        let expected_items = expected_items.max(1) as f64;
+        let ln_2 = std::f64::consts::LN_2;
+        let number_of_bits =
+            ((-expected_items * false_positive_rate.ln() / (ln_2 * ln_2)).ceil() as u64).max(64);
+        let number_of_hashes =
+            ((number_of_bits as f64 / expected_items * ln_2).round() as u32).max(1);
+        BloomFilter {
+            bits: vec![0; number_of_bits.div_ceil(64) as usize],
+            number_of_bits,
+            number_of_hashes,
+        }
+    }
+
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
+        for bit in self.bit_positions(item) {
+            self.bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        }
+    }
+
+    /// Returns false if `item` was definitely never inserted
+    pub fn might_contain<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        self.bit_positions(item)
+            .all(|bit| self.bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+    }
+
+    /// The bits an item sets, found by combining two hashes of it (see
+    /// Kirsch and Mitzenmacher's "Less Hashing, Same Performance")
+    fn bit_positions<T: Hash + ?Sized>(&self, item: &T) -> impl Iterator<Item = u64> {
+        let (first_hash, second_hash) = (hash_with_seed(item, 0), hash_with_seed(item, 1));
+        let number_of_bits = self.number_of_bits;
+        (0..self.number_of_hashes as u64)
+            .map(move |i| first_hash.wrapping_add(i.wrapping_mul(second_hash)) % number_of_bits)
+    }
+}
+
+fn hash_with_seed<T: Hash + ?Sized>(item: &T, seed: u64) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    seed.hash(&mut hasher);
+    item.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// De-duplicates lines while maintaining their order, like
+/// `list_manipulations::dedup_without_sorting`, but without keeping every
+/// line in a `HashSet`. A first pass puts each line in a Bloom filter and
+/// notes the few lines the filter might have seen already. A second pass
+/// then only checks those lines exactly. The result is exact: a line is
+/// never dropped unless it really is a duplicate.
+/// ```
+/// use tidy_core::bloom_filter::dedup_lines_with_bloom_filter;
+/// let lines = vec!["b", "a", "b", "c", "a"];
+/// assert_eq!(dedup_lines_with_bloom_filter(&lines), vec!["b", "a", "c"]);
+/// ```
+pub fn dedup_lines_with_bloom_filter<'a>(lines: &[&'a str]) -> Vec<&'a str> {
+    let mut seen = BloomFilter::new(lines.len(), BLOOM_FILTER_FALSE_POSITIVE_RATE);
+    let mut maybe_duplicates: HashSet<&str> = HashSet::new();
+    for line in lines {
+        if seen.might_contain(line) {
+            maybe_duplicates.insert(*line);
+        } else {
+            seen.insert(line);
+        }
+    }
+    let mut seen_maybe_duplicates: HashSet<&str> = HashSet::new();
+    lines
+        .iter()
+        .filter(|line| !maybe_duplicates.contains(**line) || seen_maybe_duplicates.insert(**line))
+        .copied()
+        .collect()
+}
@@ -0,0 +1,17 @@
+use crate::dice::digits_in_base;
+
+/// Card codes for each digit of a number in base 26: 13 black cards, then
+/// 13 red cards
+const CARD_CODES: [&str; 26] = [
+    "B02", "B03", "B04", "B05", "B06", "B07", "B08", "B09", "B10", "BJa", "BQu", "BKi", "BAc",
+    "R02", "R03", "R04", "R05", "R06", "R07", "R08", "R09", "R10", "RJa", "RQu", "RKi", "RAc",
+];
+
+/// Convert a number in a "card code" (base 26)
+pub fn print_as_cards(n: usize, list_length: usize) -> String {
+    digits_in_base(n, 26, list_length)
+        .iter()
+        .map(|digit| CARD_CODES[*digit])
+        .collect::<Vec<&str>>()
+        .join("-")
+}
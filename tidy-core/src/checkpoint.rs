@@ -0,0 +1,97 @@
+//! Saving how far a whittle has got, so that a long whittle that's stopped
+//! can pick up where it left off (see `whittle_list`)
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// Where a whittle's search for how many words to take stands: the fewest
+/// words to take is known to be in the range [`low`, `high`]. Written as
+/// lines of names and values, so it can be saved to a file.
+/// ```
+/// use tidy_core::checkpoint::WhittleCheckpoint;
+/// let checkpoint = WhittleCheckpoint {
+///     length_to_whittle_to: 4,
+///     list_length: 100,
+///     low: 6,
+///     high: 12,
+/// };
+/// let saved = checkpoint.to_string();
+/// assert!(saved.contains("low 6"));
+/// assert_eq!(saved.parse::<WhittleCheckpoint>(), Ok(checkpoint));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WhittleCheckpoint {
+    /// How long the list is being whittled to
+    pub length_to_whittle_to: usize,
+    /// How many lines the inputted list has
+    pub list_length: usize,
+    pub low: usize,
+    pub high: usize,
+}
+
+impl WhittleCheckpoint {
+    /// Whether the checkpoint is from a whittle of the same list, to the
+    /// same length
+    pub fn fits(&self, length_to_whittle_to: usize, list_length: usize) -> bool {
+        self.length_to_whittle_to == length_to_whittle_to
+            && self.list_length == list_length
+            && self.low <= self.high
+            && self.high <= list_length
+    }
+}
+
+impl fmt::Display for WhittleCheckpoint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "length_to_whittle_to {}", self.length_to_whittle_to)?;
+        writeln!(f, "list_length {}", self.list_length)?;
+        writeln!(f, "low {}", self.low)?;
+        writeln!(f, "high {}", self.high)
+    }
+}
+
+impl FromStr for WhittleCheckpoint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut checkpoint = WhittleCheckpoint::default();
+        let parse_number = |value: &str| {
+            value
+                .parse::<usize>()
+                .map_err(|_| format!("Unable to parse {} in whittle checkpoint", value))
+        };
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (name, value) = line.split_once(' ').unwrap_or((line, ""));
+            match name {
+                "length_to_whittle_to" => checkpoint.length_to_whittle_to = parse_number(value)?,
+                "list_length" => checkpoint.list_length = parse_number(value)?,
+                "low" => checkpoint.low = parse_number(value)?,
+                "high" => checkpoint.high = parse_number(value)?,
+                _ => return Err(format!("Unknown line in whittle checkpoint: {}", line)),
+            }
+        }
+        Ok(checkpoint)
+    }
+}
+
+/// Called with a whittle's checkpoint after each guess, so that callers can
+/// save it
+#[derive(Clone)]
+pub struct CheckpointCallback(Arc<CheckpointFn>);
+
+type CheckpointFn = dyn Fn(&WhittleCheckpoint) + Send + Sync;
+
+impl CheckpointCallback {
+    pub fn new(callback: impl Fn(&WhittleCheckpoint) + Send + Sync + 'static) -> Self {
+        CheckpointCallback(Arc::new(callback))
+    }
+
+    pub fn save(&self, checkpoint: &WhittleCheckpoint) {
+        (self.0)(checkpoint)
+    }
+}
+
+impl fmt::Debug for CheckpointCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CheckpointCallback")
+    }
+}
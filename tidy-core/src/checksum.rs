@@ -0,0 +1,43 @@
+//! Fingerprint word lists with SHA-256, so that list publishers and
+//! downstream users can confirm they have identical lists.
+
+use sha2::{Digest, Sha256};
+
+/// Calculates the SHA-256 hash of the given list, as printed with one word
+/// per line and a line feed (`\n`) after every word (including the last).
+/// Returns the hash as a lowercase hexadecimal `String`.
+/// ```
+/// use tidy_core::checksum::list_checksum;
+/// let list = vec!["abbey".to_string(), "zoo".to_string()];
+/// // Same as `printf 'abbey\nzoo\n' | sha256sum`
+/// assert_eq!(
+///     list_checksum(&list),
+///     "f9011f50a2cf459e2af85edb7cacaab123cb1e9e1497afa4dd981ee984dd264f"
+/// );
+/// ```
+pub fn list_checksum(list: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for word in list {
+        hasher.update(word.as_bytes());
+        hasher.update(b"\n");
+    }
+    to_hex(&hasher.finalize())
+}
+
+/// Formats bytes as a lowercase hexadecimal `String`.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Calculates the SHA-256 hash of raw bytes, like the contents of a file.
+/// Returns the hash as a lowercase hexadecimal `String`.
+/// ```
+/// use tidy_core::checksum::bytes_checksum;
+/// assert_eq!(
+///     bytes_checksum(b"abbey\nzoo\n"),
+///     "f9011f50a2cf459e2af85edb7cacaab123cb1e9e1497afa4dd981ee984dd264f"
+/// );
+/// ```
+pub fn bytes_checksum(bytes: &[u8]) -> String {
+    to_hex(&Sha256::digest(bytes))
+}
@@ -0,0 +1,17 @@
+use crate::dice::digits_in_base;
+
+/// Convert a number into a sequence of coin flips (base 2), with `H`
+/// for heads and `T` for tails. Like dice rolls, every word of the list
+/// gets the same number of flips, so a 7,776-word list needs 13 flips
+/// per word.
+/// ```
+/// use tidy_core::coins::print_as_coins;
+/// assert_eq!(print_as_coins(0, 8), "HHH");
+/// assert_eq!(print_as_coins(6, 8), "TTH");
+/// ```
+pub fn print_as_coins(n: usize, list_length: usize) -> String {
+    digits_in_base(n, 2, list_length)
+        .iter()
+        .map(|digit| if *digit == 0 { 'H' } else { 'T' })
+        .collect()
+}
@@ -0,0 +1,169 @@
+//! Compare a word list against a baseline list, like one of the EFF's lists,
+//! or against an earlier version of itself
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Word lists built into Tidy to compare against, by name.
+///
+/// The EFF's word lists are by the Electronic Frontier Foundation and are
+/// licensed under a Creative Commons Attribution 3.0 US license. See
+/// <https://www.eff.org/deeplinks/2016/07/new-wordlists-random-passphrases>
+pub const BUILTIN_LISTS: [(&str, &str); 2] = [
+    (
+        "eff-long",
+        include_str!("baseline_lists/eff_large_wordlist.txt"),
+    ),
+    (
+        "eff-short",
+        include_str!("baseline_lists/eff_short_wordlist_1.txt"),
+    ),
+];
+
+/// Gets one of the `BUILTIN_LISTS` by name, if there is one by that name.
+/// Callers that can read files can fall back to reading a list from a path
+/// (see `file_readers::load_baseline_list` in the `tidy` crate).
+/// ```
+/// use tidy_core::compare::builtin_list;
+/// assert_eq!(builtin_list("eff-long").unwrap().len(), 7776);
+/// assert!(builtin_list("no-such-list").is_none());
+/// ```
+pub fn builtin_list(name: &str) -> Option<Vec<String>> {
+    BUILTIN_LISTS
+        .iter()
+        .find(|(builtin_name, _)| *builtin_name == name)
+        .map(|(_name, contents)| parse_baseline_list(contents))
+}
+
+/// Splits the contents of a list to compare against into its words, one
+/// per line, skipping blank lines.
+pub fn parse_baseline_list(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+/// How a word list overlaps with a baseline list
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListComparison {
+    /// Number of words on both lists
+    pub overlap: usize,
+    /// Words on the new list but not the baseline, in the new list's order
+    pub unique_to_list: Vec<String>,
+    /// Words on the baseline but not the new list, in the baseline's order
+    pub unique_to_baseline: Vec<String>,
+}
+
+impl ListComparison {
+    /// Percent of the new list's words that are also on the baseline
+    pub fn overlap_percent(&self) -> f64 {
+        let list_length = self.overlap + self.unique_to_list.len();
+        if list_length == 0 {
+            0.0
+        } else {
+            self.overlap as f64 / list_length as f64 * 100.0
+        }
+    }
+}
+
+/// Compares a word list against a baseline list.
+/// ```
+/// use tidy_core::compare::compare_lists;
+/// let list: Vec<String> = ["abacus", "zebra"].iter().map(|w| w.to_string()).collect();
+/// let baseline: Vec<String> = ["abacus", "zoo"].iter().map(|w| w.to_string()).collect();
+/// let comparison = compare_lists(&list, &baseline);
+/// assert_eq!(comparison.overlap, 1);
+/// assert_eq!(comparison.unique_to_list, vec!["zebra"]);
+/// assert_eq!(comparison.overlap_percent(), 50.0);
+/// ```
+pub fn compare_lists(list: &[String], baseline: &[String]) -> ListComparison {
+    let list_words: HashSet<&String> = list.iter().collect();
+    let baseline_words: HashSet<&String> = baseline.iter().collect();
+    ListComparison {
+        overlap: list_words.intersection(&baseline_words).count(),
+        unique_to_list: list
+            .iter()
+            .filter(|word| !baseline_words.contains(word))
+            .map(|word| word.to_string())
+            .collect(),
+        unique_to_baseline: baseline
+            .iter()
+            .filter(|word| !list_words.contains(word))
+            .map(|word| word.to_string())
+            .collect(),
+    }
+}
+
+/// Words that are on both lists, in the first list's order
+/// ```
+/// use tidy_core::compare::find_shared_words;
+/// let adjectives: Vec<String> = ["blue", "fast", "light"].iter().map(|w| w.to_string()).collect();
+/// let nouns: Vec<String> = ["cat", "light"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(find_shared_words(&adjectives, &nouns), vec!["light"]);
+/// ```
+pub fn find_shared_words(list: &[String], other_list: &[String]) -> Vec<String> {
+    let other_words: HashSet<&String> = other_list.iter().collect();
+    list.iter()
+        .filter(|word| other_words.contains(word))
+        .map(|word| word.to_string())
+        .collect()
+}
+
+/// How the lines of a file would change if it were written again. Line
+/// numbers count from 1.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LinesDiff {
+    /// Lines only in the new version, with their line numbers in it
+    pub added: Vec<(usize, String)>,
+    /// Lines only in the old version, with their line numbers in it
+    pub removed: Vec<(usize, String)>,
+    /// Lines in both versions, but at different line numbers, with their old
+    /// and new line numbers
+    pub moved: Vec<(String, usize, usize)>,
+}
+
+impl LinesDiff {
+    /// Whether the two versions are the same
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.moved.is_empty()
+    }
+}
+
+/// Compares the lines of an old and new version of a file, like a
+/// published word list and the list Tidy would write in its place. A line
+/// that's in the old version more than once is matched up with its copies
+/// in the new version in order.
+/// ```
+/// use tidy_core::compare::diff_lines;
+/// let old: Vec<String> = ["abacus", "acid", "zoo"].iter().map(|w| w.to_string()).collect();
+/// let new: Vec<String> = ["abacus", "zebra", "acid"].iter().map(|w| w.to_string()).collect();
+/// let diff = diff_lines(&old, &new);
+/// assert_eq!(diff.added, vec![(2, "zebra".to_string())]);
+/// assert_eq!(diff.removed, vec![(3, "zoo".to_string())]);
+/// assert_eq!(diff.moved, vec![("acid".to_string(), 2, 3)]);
+/// ```
+pub fn diff_lines(old: &[String], new: &[String]) -> LinesDiff {
+    let mut old_positions: HashMap<&str, VecDeque<usize>> = HashMap::new();
+    for (i, line) in old.iter().enumerate() {
+        old_positions.entry(line).or_default().push_back(i + 1);
+    }
+    let mut diff = LinesDiff::default();
+    for (i, line) in new.iter().enumerate() {
+        match old_positions
+            .get_mut(line.as_str())
+            .and_then(|positions| positions.pop_front())
+        {
+            Some(old_position) if old_position != i + 1 => {
+                diff.moved.push((line.to_string(), old_position, i + 1))
+            }
+            Some(_same_position) => (),
+            None => diff.added.push((i + 1, line.to_string())),
+        }
+    }
+    diff.removed = old_positions
+        .into_iter()
+        .flat_map(|(line, positions)| positions.into_iter().map(move |i| (i, line.to_string())))
+        .collect();
+    diff.removed.sort();
+    diff
+}
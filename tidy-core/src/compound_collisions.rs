@@ -0,0 +1,176 @@
+//! Find pairs of words that, typed without a separator, spell the same thing
+//! as another word ("in" + "put" and "input") or another pair of words
+//! ("to" + "pin" and "top" + "in").
+//!
+//! This is the two-word case of unique decodability (see
+//! `uniquely_decodable`), which is the most likely to trip up someone
+//! reading back a passphrase, and it lists every collision rather than one
+//! example.
+use std::collections::HashSet;
+
+/// Finds every way to spell a string with one or two words from the list
+/// that can also be spelled with a different one or two words from the list.
+/// Returns the two spellings of each collision, the shorter one first (or the
+/// one whose first word is shorter), sorted by the string they spell.
+/// ```
+/// use tidy_core::compound_collisions::find_compound_collisions;
+/// let list: Vec<String> = ["in", "put", "input", "to", "pin", "top"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// let collisions = find_compound_collisions(&list);
+/// assert_eq!(
+///     collisions,
+///     vec![
+///         (vec!["input".to_string()], vec!["in".to_string(), "put".to_string()]),
+///         (
+///             vec!["to".to_string(), "pin".to_string()],
+///             vec!["top".to_string(), "in".to_string()]
+///         ),
+///     ]
+/// );
+/// ```
+pub fn find_compound_collisions(list: &[String]) -> Vec<(Vec<String>, Vec<String>)> {
+    // Sort words so that words beginning with a given string can be found
+    // with a binary search
+    let mut words: Vec<&str> = list.iter().map(|word| word.as_str()).collect();
+    words.sort_unstable();
+    words.dedup();
+    let word_set: HashSet<&str> = words.iter().copied().collect();
+
+    let mut collisions: Vec<(Vec<&str>, Vec<&str>)> = vec![];
+    for &word in &words {
+        // A word that is also a pair of words
+        for (i, _c) in word.char_indices().skip(1) {
+            let (first, second) = word.split_at(i);
+            if word_set.contains(first) && word_set.contains(second) {
+                collisions.push((vec![word], vec![first, second]));
+            }
+        }
+        // Two pairs of words, word + rest and longer_word + end, where
+        // longer_word starts with word, and rest starts with what's left of
+        // longer_word (its dangling suffix)
+        let start = words.partition_point(|w| *w <= word);
+        for &longer_word in words[start..].iter().take_while(|w| w.starts_with(word)) {
+            let dangling_suffix = &longer_word[word.len()..];
+            let start = words.partition_point(|w| *w <= dangling_suffix);
+            for &rest in words[start..]
+                .iter()
+                .take_while(|w| w.starts_with(dangling_suffix))
+            {
+                let end = &rest[dangling_suffix.len()..];
+                if word_set.contains(end) {
+                    collisions.push((vec![word, rest], vec![longer_word, end]));
+                }
+            }
+        }
+    }
+    collisions.sort_by(|a, b| a.0.concat().cmp(&b.0.concat()).then(a.cmp(b)));
+    collisions
+        .into_iter()
+        .map(|(first, second)| (to_strings(&first), to_strings(&second)))
+        .collect()
+}
+
+/// For passphrases made of a word from one list followed by a word from
+/// another, like an adjective and a noun, finds every pair that, typed
+/// without a separator, spells the same thing as a different pair. Returns
+/// the two spellings of each collision, the one whose first word is shorter
+/// first, sorted by the string they spell.
+/// ```
+/// use tidy_core::compound_collisions::find_cross_list_collisions;
+/// let adjectives: Vec<String> = ["red", "redo", "big"].iter().map(|w| w.to_string()).collect();
+/// let nouns: Vec<String> = ["ore", "re", "dog"].iter().map(|w| w.to_string()).collect();
+/// // "red" + "ore" and "redo" + "re" both spell "redore"
+/// assert_eq!(
+///     find_cross_list_collisions(&adjectives, &nouns),
+///     vec![(
+///         vec!["red".to_string(), "ore".to_string()],
+///         vec!["redo".to_string(), "re".to_string()]
+///     )]
+/// );
+/// ```
+pub fn find_cross_list_collisions(
+    first_list: &[String],
+    second_list: &[String],
+) -> Vec<(Vec<String>, Vec<String>)> {
+    let mut first_words: Vec<&str> = first_list.iter().map(|word| word.as_str()).collect();
+    first_words.sort_unstable();
+    first_words.dedup();
+    let mut second_words: Vec<&str> = second_list.iter().map(|word| word.as_str()).collect();
+    second_words.sort_unstable();
+    second_words.dedup();
+    let second_set: HashSet<&str> = second_words.iter().copied().collect();
+
+    let mut collisions: Vec<(Vec<&str>, Vec<&str>)> = vec![];
+    // Two pairs, word + rest and longer_word + end, where longer_word starts
+    // with word, and rest starts with what's left of longer_word (its
+    // dangling suffix)
+    for &word in &first_words {
+        let start = first_words.partition_point(|w| *w <= word);
+        for &longer_word in first_words[start..]
+            .iter()
+            .take_while(|w| w.starts_with(word))
+        {
+            let dangling_suffix = &longer_word[word.len()..];
+            let start = second_words.partition_point(|w| *w <= dangling_suffix);
+            for &rest in second_words[start..]
+                .iter()
+                .take_while(|w| w.starts_with(dangling_suffix))
+            {
+                let end = &rest[dangling_suffix.len()..];
+                if second_set.contains(end) {
+                    collisions.push((vec![word, rest], vec![longer_word, end]));
+                }
+            }
+        }
+    }
+    collisions.sort_by(|a, b| a.0.concat().cmp(&b.0.concat()).then(a.cmp(b)));
+    collisions
+        .into_iter()
+        .map(|(first, second)| (to_strings(&first), to_strings(&second)))
+        .collect()
+}
+
+/// Removes words until no two words spell the same thing as another word
+/// or pair of words (see `find_compound_collisions`). Of the words in each
+/// collision, removes the longest, or, of those, the one furthest down the
+/// list, so lists sorted by word frequency keep their more common words.
+/// ```
+/// use tidy_core::compound_collisions::remove_compound_collisions;
+/// let list: Vec<String> = ["in", "put", "input", "cat"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(remove_compound_collisions(&list), vec!["in", "put", "cat"]);
+/// ```
+pub fn remove_compound_collisions(list: &[String]) -> Vec<String> {
+    let mut removed: HashSet<String> = HashSet::new();
+    for (first, second) in find_compound_collisions(list) {
+        if first
+            .iter()
+            .chain(&second)
+            .any(|word| removed.contains(word))
+        {
+            continue;
+        }
+        // Removing words can't make new collisions, so one word of each
+        // collision is enough
+        let to_remove = first
+            .iter()
+            .chain(&second)
+            .max_by_key(|word| {
+                (
+                    word.chars().count(),
+                    list.iter().rposition(|other| other == *word),
+                )
+            })
+            .unwrap();
+        removed.insert(to_remove.to_string());
+    }
+    list.iter()
+        .filter(|word| !removed.contains(*word))
+        .cloned()
+        .collect()
+}
+
+fn to_strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
@@ -0,0 +1,320 @@
+use crate::error::TidyError;
+use radix_fmt::*; // https://stackoverflow.com/a/50278316
+/// Print dice rolls before each corresponding word. Note
+/// that the `n` parameter should be zero-indexed. By default, a tab
+/// (`\t`) is printed between the dice roll and the word.
+///
+/// The `base` parameter represents the number of sides of the
+/// dice, which can be set from 2 to 9.
+///
+/// Here's an example of an outputted word list with base 6:
+/// ```text
+/// 11111    aback
+/// 11112    abandons
+/// 11113    abated
+/// 11114    abbey
+/// 11115    abbot
+/// 11116    abbreviated
+/// 11121    abdomen
+/// 11122    abducted
+/// 11123    aberrant
+/// 11124    abide
+/// 11125    ability
+/// 11126    abject
+/// 11131    abnormally
+/// // etc.
+/// ```
+///
+/// If this base is between 4 and 8,
+/// this function assumes the user will be using actual dice, which are index at 1.
+/// Thus, `if 4 <= base && base <= 8`, we add `1` to each digit of the dice
+/// roll before printing it.
+///
+/// Returns an error if `base` isn't between 2 and 36.
+///
+/// I wish I could replicate this radix function easily without the dependency,
+/// but that doesn't seem [very easy](https://stackoverflow.com/a/50278316).
+pub fn print_as_dice(
+    n: usize,
+    base: u8,
+    list_length: usize,
+    use_letters: bool,
+) -> Result<String, TidyError> {
+    format_dice_roll(n, base, list_length, use_letters, &DiceFormat::default())
+}
+
+/// Options for how to format each dice roll, so that outputted lists can
+/// match the formatting of existing published lists
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct DiceFormat {
+    /// String to print between each die of a roll. If `None`, dice with
+    /// more than 9 sides are separated with a dash (`03-11-01`) and other
+    /// dice aren't separated at all (`26555`).
+    pub digit_separator: Option<String>,
+    /// Don't left-pad faces of dice with more than 9 sides with a zero,
+    /// printing `3-11-1` rather than `03-11-01`
+    pub unpadded_digits: bool,
+}
+
+/// Like `print_as_dice`, but formats the roll according to the given
+/// `DiceFormat`.
+/// ```
+/// use tidy_core::dice::{format_dice_roll, DiceFormat};
+/// let format = DiceFormat {
+///     digit_separator: Some(" ".to_string()),
+///     unpadded_digits: true,
+/// };
+/// assert_eq!(format_dice_roll(1000, 20, 8000, false, &format).unwrap(), "3 11 1");
+/// assert_eq!(format_dice_roll(2548, 6, 7776, false, &format).unwrap(), "2 6 5 5 5");
+/// assert!(format_dice_roll(0, 1, 7776, false, &format).is_err());
+/// ```
+pub fn format_dice_roll(
+    n: usize,
+    base: u8,
+    list_length: usize,
+    use_letters: bool,
+    format: &DiceFormat,
+) -> Result<String, TidyError> {
+    check_dice_sides(base)?;
+    let digits = digits_in_base(n, base, list_length);
+
+    // Print the dice rolls in slightly different ways,
+    // depending on the value of the base.
+    let faces: Vec<String> = if use_letters {
+        // We'll use zero-indexed values if sides_as_letters is
+        // selected
+        digits
+            .iter()
+            .map(|digit| {
+                (DIGIT_CHARACTERS[*digit] as char)
+                    .to_ascii_uppercase()
+                    .to_string()
+            })
+            .collect()
+    } else if base <= 9 {
+        // We'll use 1-indexed values if sides_as_letters is NOT
+        // selected
+        digits.iter().map(|digit| (digit + 1).to_string()).collect()
+    } else {
+        digits
+            .iter()
+            .map(|digit| {
+                if format.unpadded_digits {
+                    (digit + 1).to_string()
+                } else {
+                    format!("{:02}", digit + 1)
+                }
+            })
+            .collect()
+    };
+    let default_separator = if base > 9 && !use_letters { "-" } else { "" };
+    Ok(faces.join(
+        format
+            .digit_separator
+            .as_deref()
+            .unwrap_or(default_separator),
+    ))
+}
+
+/// Checks that dice with the given number of sides can be printed: they
+/// need between 2 and 36 sides
+pub fn check_dice_sides(base: u8) -> Result<(), TidyError> {
+    if (2..=36).contains(&base) {
+        Ok(())
+    } else {
+        Err(TidyError::InvalidDiceSides(base))
+    }
+}
+
+/// Reads a dice roll, as printed by `format_dice_roll`, back into the
+/// (zero-indexed) position of the word it stands for. Any separators
+/// between dice are skipped. Faces of dice with more than 9 sides must
+/// either be separated or all be two digits long.
+/// ```
+/// use tidy_core::dice::parse_dice_roll;
+/// assert_eq!(parse_dice_roll("11111", 6, false), Ok(0));
+/// assert_eq!(parse_dice_roll("26555", 6, false), Ok(2548));
+/// assert_eq!(parse_dice_roll("03-11-01", 20, false), Ok(1000));
+/// assert_eq!(parse_dice_roll("031101", 20, false), Ok(1000));
+/// assert_eq!(parse_dice_roll("1A", 16, true), Ok(26));
+/// assert!(parse_dice_roll("17", 6, false).is_err());
+/// ```
+pub fn parse_dice_roll(roll: &str, base: u8, use_letters: bool) -> Result<usize, String> {
+    let bad_roll = || format!("Can't read {:?} as a roll of {}-sided dice", roll, base);
+    let digits: Vec<u32> = if use_letters {
+        roll.chars()
+            .filter(|ch| ch.is_alphanumeric())
+            .map(|ch| ch.to_digit(36).filter(|digit| *digit < base as u32))
+            .collect::<Option<Vec<u32>>>()
+            .ok_or_else(bad_roll)?
+    } else {
+        let faces: Vec<String> = if base <= 9 {
+            roll.chars()
+                .filter(|ch| ch.is_ascii_digit())
+                .map(|ch| ch.to_string())
+                .collect()
+        } else if roll.chars().all(|ch| ch.is_ascii_digit()) {
+            let roll: Vec<char> = roll.chars().collect();
+            if !roll.len().is_multiple_of(2) {
+                return Err(bad_roll());
+            }
+            roll.chunks(2).map(|face| face.iter().collect()).collect()
+        } else {
+            roll.split(|ch: char| !ch.is_ascii_digit())
+                .filter(|face| !face.is_empty())
+                .map(|face| face.to_string())
+                .collect()
+        };
+        faces
+            .iter()
+            .map(|face| {
+                face.parse::<u32>()
+                    .ok()
+                    .filter(|face| (1..=base as u32).contains(face))
+                    .map(|face| face - 1)
+            })
+            .collect::<Option<Vec<u32>>>()
+            .ok_or_else(bad_roll)?
+    };
+    if digits.is_empty() {
+        return Err(bad_roll());
+    }
+    Ok(digits
+        .iter()
+        .fold(0, |n, digit| n * base as usize + *digit as usize))
+}
+
+/// Characters of each digit of a number in bases up to 36
+const DIGIT_CHARACTERS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+/// Writes `n` in the given base (2 to 36), zero-padded to the width of
+/// the largest index of a list of `list_length` words. Letters are
+/// lowercase.
+/// ```
+/// use tidy_core::dice::pad_in_base;
+/// assert_eq!(pad_in_base(5, 2, 128), "0000101");
+/// assert_eq!(pad_in_base(35, 36, 1296), "0z");
+/// ```
+pub fn pad_in_base(n: usize, base: u8, list_length: usize) -> String {
+    digits_in_base(n, base, list_length)
+        .iter()
+        .map(|digit| DIGIT_CHARACTERS[*digit] as char)
+        .collect()
+}
+
+/// The digits of `n` in the given base (2 to 36), most significant first,
+/// padded with zeros like `pad_in_base`. Dice rolls, card codes, and coin
+/// flips are all made by converting each of these digits.
+/// ```
+/// use tidy_core::dice::digits_in_base;
+/// assert_eq!(digits_in_base(5, 2, 16), vec![0, 1, 0, 1]);
+/// assert_eq!(digits_in_base(1000, 20, 8000), vec![2, 10, 0]);
+/// ```
+pub fn digits_in_base(n: usize, base: u8, list_length: usize) -> Vec<usize> {
+    let sides = base as usize;
+    let mut digits = vec![];
+    let mut remainder = n;
+    loop {
+        digits.push(remainder % sides);
+        remainder /= sides;
+        if remainder == 0 {
+            break;
+        }
+    }
+    // Pad to the width of the largest number we'll be printing, which is,
+    // by definition, one less than the length of the list
+    let pad_width = count_dice_needed(list_length, base) as usize;
+    if digits.len() < pad_width {
+        digits.resize(pad_width, 0);
+    }
+    digits.reverse();
+    digits
+}
+
+/// Counts how many dice needed to give every word of a list of
+/// `list_length` words its own roll.
+/// ```
+/// use tidy_core::dice::count_dice_needed;
+/// assert_eq!(count_dice_needed(7776, 6), 5);
+/// assert_eq!(count_dice_needed(7777, 6), 6);
+/// assert_eq!(count_dice_needed(8000, 20), 3);
+/// ```
+pub fn count_dice_needed(list_length: usize, base: u8) -> u32 {
+    radix(list_length.saturating_sub(1), base).to_string().len() as u32
+}
+
+/// Counts the dice roll combinations that don't correspond to any word.
+/// This is zero only when the list length is a power of the number of
+/// dice sides. Otherwise, users need to re-roll whenever they get one
+/// of these combinations.
+/// ```
+/// use tidy_core::dice::count_unreachable_rolls;
+/// assert_eq!(count_unreachable_rolls(7776, 6), 0);
+/// assert_eq!(count_unreachable_rolls(7000, 6), 776);
+/// ```
+pub fn count_unreachable_rolls(list_length: usize, base: u8) -> usize {
+    (base as usize)
+        .saturating_pow(count_dice_needed(list_length, base))
+        .saturating_sub(list_length)
+}
+
+/// Makes a table of the dice rolls that don't correspond to any word, as
+/// ranges of first and last roll. Unreachable rolls are always the
+/// highest ones, so they could be written as one range, but each range
+/// here covers rolls that share their first few dice. That way, users
+/// rolling one die at a time can tell they need to re-roll as soon as
+/// possible. Rolls are formatted the same way as `format_dice_roll` does.
+/// ```
+/// use tidy_core::dice::{make_reroll_table, DiceFormat};
+/// assert_eq!(
+///     make_reroll_table(7000, 6, false, &DiceFormat::default()).unwrap(),
+///     vec![
+///         ("63335".to_string(), "63336".to_string()),
+///         ("63341".to_string(), "63366".to_string()),
+///         ("63411".to_string(), "63666".to_string()),
+///         ("64111".to_string(), "66666".to_string()),
+///     ]
+/// );
+/// ```
+pub fn make_reroll_table(
+    list_length: usize,
+    base: u8,
+    use_letters: bool,
+    format: &DiceFormat,
+) -> Result<Vec<(String, String)>, TidyError> {
+    check_dice_sides(base)?;
+    if count_unreachable_rolls(list_length, base) == 0 {
+        return Ok(vec![]);
+    }
+    let sides = base as usize;
+    let dice = count_dice_needed(list_length, base) as usize;
+    // Digits of the first unreachable roll, most significant first
+    let mut first_unreachable = vec![0; dice];
+    let mut remainder = list_length;
+    for digit in first_unreachable.iter_mut().rev() {
+        *digit = remainder % sides;
+        remainder /= sides;
+    }
+    let to_number = |digits: &[usize]| digits.iter().fold(0, |n, digit| n * sides + digit);
+
+    let mut table = vec![];
+    // Starting from the last die, each row keeps the first few dice of
+    // the first unreachable roll and raises the next one
+    for position in (0..dice).rev() {
+        let mut first = first_unreachable.clone();
+        if position < dice - 1 {
+            if first[position] == sides - 1 {
+                continue;
+            }
+            first[position] += 1;
+            first[position + 1..].fill(0);
+        }
+        let mut last = first_unreachable.clone();
+        last[position..].fill(sides - 1);
+        table.push((
+            format_dice_roll(to_number(&first), base, list_length, use_letters, format)?,
+            format_dice_roll(to_number(&last), base, list_length, use_letters, format)?,
+        ));
+    }
+    Ok(table)
+}
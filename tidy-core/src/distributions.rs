@@ -0,0 +1,115 @@
+//! Count how often letters and pairs of letters (bigrams) appear in a word list
+use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Counts how often each character (grapheme cluster) appears in the list.
+/// Returns characters and their counts, most common first.
+/// ```
+/// use tidy_core::distributions::letter_frequencies;
+/// let list: Vec<String> = vec!["banana".to_string()];
+/// assert_eq!(
+///     letter_frequencies(&list),
+///     vec![("a".to_string(), 3), ("n".to_string(), 2), ("b".to_string(), 1)]
+/// );
+/// ```
+pub fn letter_frequencies(list: &[String]) -> Vec<(String, usize)> {
+    count_and_sort(
+        list.iter()
+            .flat_map(|word| word.graphemes(true).map(|g| g.to_string())),
+    )
+}
+
+/// Counts how many words start with each character.
+/// Returns characters and their counts, most common first.
+pub fn first_letter_frequencies(list: &[String]) -> Vec<(String, usize)> {
+    starting_prefix_frequencies(list, 1)
+}
+
+/// Counts how often each pair of adjacent characters appears in the list.
+/// Returns bigrams and their counts, most common first.
+/// ```
+/// use tidy_core::distributions::bigram_frequencies;
+/// let list: Vec<String> = vec!["banana".to_string()];
+/// assert_eq!(bigram_frequencies(&list)[0], ("an".to_string(), 2));
+/// ```
+pub fn bigram_frequencies(list: &[String]) -> Vec<(String, usize)> {
+    count_and_sort(list.iter().flat_map(|word| {
+        let graphemes: Vec<&str> = word.graphemes(true).collect();
+        graphemes
+            .windows(2)
+            .map(|pair| pair.concat())
+            .collect::<Vec<String>>()
+    }))
+}
+
+/// Counts how many words start with each prefix of the given length.
+/// Words shorter than `length` are not counted.
+/// Returns prefixes and their counts, most common first.
+/// ```
+/// use tidy_core::distributions::starting_prefix_frequencies;
+/// let list: Vec<String> = ["undo", "unmoved", "zoo"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(starting_prefix_frequencies(&list, 2)[0], ("un".to_string(), 2));
+/// ```
+pub fn starting_prefix_frequencies(list: &[String], length: usize) -> Vec<(String, usize)> {
+    count_and_sort(list.iter().filter_map(|word| {
+        let graphemes: Vec<&str> = word.graphemes(true).take(length).collect();
+        if graphemes.len() == length {
+            Some(graphemes.concat())
+        } else {
+            None
+        }
+    }))
+}
+
+/// Finds starting prefixes of 1 or 2 characters that more than
+/// `maximum_percent` of words on the list begin with. These suggest the
+/// list is skewed, perhaps by an artifact of the corpus it was made from.
+/// Returns each offending prefix and the percent of words that start with it.
+/// ```
+/// use tidy_core::distributions::find_skewed_prefixes;
+/// let list: Vec<String> = ["undo", "unmoved", "untie", "zoo"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(find_skewed_prefixes(&list, 50.0), vec![("u".to_string(), 75.0), ("un".to_string(), 75.0)]);
+/// ```
+pub fn find_skewed_prefixes(list: &[String], maximum_percent: f64) -> Vec<(String, f64)> {
+    let mut skewed_prefixes = vec![];
+    for length in 1..=2 {
+        for (prefix, count) in starting_prefix_frequencies(list, length) {
+            let percent = percent_of(count, list.len());
+            if percent > maximum_percent {
+                skewed_prefixes.push((prefix, percent));
+            }
+        }
+    }
+    skewed_prefixes
+}
+
+/// Formats the most common items of a frequency count as a
+/// comma-separated list of items and their percent of the given total.
+/// ```
+/// use tidy_core::distributions::format_top_frequencies;
+/// let frequencies = vec![("a".to_string(), 3), ("n".to_string(), 2), ("b".to_string(), 1)];
+/// assert_eq!(format_top_frequencies(&frequencies, 6, 2), "a (50.0%), n (33.3%)");
+/// ```
+pub fn format_top_frequencies(frequencies: &[(String, usize)], total: usize, top: usize) -> String {
+    frequencies
+        .iter()
+        .take(top)
+        .map(|(item, count)| format!("{} ({:.1}%)", item, percent_of(*count, total)))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn percent_of(count: usize, total: usize) -> f64 {
+    count as f64 / total as f64 * 100.0
+}
+
+/// Counts each item, sorting by count (descending), then alphabetically
+fn count_and_sort(items: impl Iterator<Item = String>) -> Vec<(String, usize)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+}
@@ -0,0 +1,245 @@
+//! Convert word lists from legacy text encodings into UTF-8
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Text encodings that Tidy can read word lists from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InputEncoding {
+    Utf8,
+    Latin1,
+    Windows1252,
+    /// UTF-16, with byte order taken from the byte order mark (BOM).
+    /// Assumes little-endian if there is no BOM.
+    Utf16,
+    /// Guess encoding from byte order mark, falling back to UTF-8 if the
+    /// bytes are valid UTF-8 and Windows-1252 otherwise.
+    Auto,
+}
+
+impl FromStr for InputEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(InputEncoding::Utf8),
+            "latin1" | "latin-1" | "iso-8859-1" => Ok(InputEncoding::Latin1),
+            "windows-1252" | "cp1252" => Ok(InputEncoding::Windows1252),
+            "utf-16" | "utf16" => Ok(InputEncoding::Utf16),
+            "auto" => Ok(InputEncoding::Auto),
+            _ => Err(format!(
+                "Unknown encoding {}. Use utf-8, latin1, windows-1252, utf-16, or auto.",
+                s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for InputEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            InputEncoding::Utf8 => "UTF-8",
+            InputEncoding::Latin1 => "Latin-1",
+            InputEncoding::Windows1252 => "Windows-1252",
+            InputEncoding::Utf16 => "UTF-16",
+            InputEncoding::Auto => "auto",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Windows-1252 characters for bytes 0x80 through 0x9F. All other bytes
+/// map to the same code point as in Latin-1. The five bytes Windows-1252 leaves
+/// undefined are `None`.
+const WINDOWS_1252_HIGH_CHARACTERS: [Option<char>; 32] = [
+    Some('€'),
+    None,
+    Some('‚'),
+    Some('ƒ'),
+    Some('„'),
+    Some('…'),
+    Some('†'),
+    Some('‡'),
+    Some('ˆ'),
+    Some('‰'),
+    Some('Š'),
+    Some('‹'),
+    Some('Œ'),
+    None,
+    Some('Ž'),
+    None,
+    None,
+    Some('‘'),
+    Some('’'),
+    Some('“'),
+    Some('”'),
+    Some('•'),
+    Some('–'),
+    Some('—'),
+    Some('˜'),
+    Some('™'),
+    Some('š'),
+    Some('›'),
+    Some('œ'),
+    None,
+    Some('ž'),
+    Some('Ÿ'),
+];
+
+/// Decode the raw bytes of a file into a UTF-8 `String`.
+///
+/// If `strict` is true, bytes that are invalid in the given encoding cause an
+/// error. Otherwise they're replaced with the Unicode replacement character (�).
+/// ```
+/// use tidy_core::encodings::{decode, InputEncoding};
+/// assert_eq!(decode(b"caf\xe9", InputEncoding::Latin1, true).unwrap(), "café");
+/// assert_eq!(decode(b"\x93hi\x94", InputEncoding::Windows1252, true).unwrap(), "“hi”");
+/// assert_eq!(decode(b"caf\xe9", InputEncoding::Utf8, false).unwrap(), "caf�");
+/// assert!(decode(b"caf\xe9", InputEncoding::Utf8, true).is_err());
+/// ```
+pub fn decode(bytes: &[u8], encoding: InputEncoding, strict: bool) -> Result<String, String> {
+    match encoding {
+        InputEncoding::Utf8 => {
+            let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+            if strict {
+                String::from_utf8(bytes.to_vec()).map_err(|e| e.to_string())
+            } else {
+                Ok(String::from_utf8_lossy(bytes).to_string())
+            }
+        }
+        // Every Latin-1 byte is the Unicode code point of the same value
+        InputEncoding::Latin1 => Ok(bytes.iter().map(|&b| b as char).collect()),
+        InputEncoding::Windows1252 => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => match WINDOWS_1252_HIGH_CHARACTERS[(b - 0x80) as usize] {
+                    Some(c) => Ok(c),
+                    None if strict => Err(format!("Byte {:#04X} is undefined in Windows-1252", b)),
+                    None => Ok(char::REPLACEMENT_CHARACTER),
+                },
+                _ => Ok(b as char),
+            })
+            .collect(),
+        InputEncoding::Utf16 => {
+            let (bytes, big_endian) = if let Some(rest) = bytes.strip_prefix(b"\xFE\xFF") {
+                (rest, true)
+            } else if let Some(rest) = bytes.strip_prefix(b"\xFF\xFE") {
+                (rest, false)
+            } else {
+                (bytes, false)
+            };
+            if strict && bytes.len() % 2 != 0 {
+                return Err("UTF-16 input has an odd number of bytes".to_string());
+            }
+            let units: Vec<u16> = bytes
+                .chunks_exact(2)
+                .map(|pair| {
+                    if big_endian {
+                        u16::from_be_bytes([pair[0], pair[1]])
+                    } else {
+                        u16::from_le_bytes([pair[0], pair[1]])
+                    }
+                })
+                .collect();
+            if strict {
+                String::from_utf16(&units).map_err(|e| e.to_string())
+            } else {
+                Ok(String::from_utf16_lossy(&units))
+            }
+        }
+        InputEncoding::Auto => decode(bytes, guess_encoding(bytes), strict),
+    }
+}
+
+/// Guess the encoding of the given bytes, first by looking for a byte order
+/// mark, then by checking if the bytes are valid UTF-8. Falls back to
+/// Windows-1252, a superset of the printable characters of Latin-1.
+pub fn guess_encoding(bytes: &[u8]) -> InputEncoding {
+    if bytes.starts_with(b"\xFE\xFF") || bytes.starts_with(b"\xFF\xFE") {
+        InputEncoding::Utf16
+    } else if std::str::from_utf8(bytes).is_ok() {
+        InputEncoding::Utf8
+    } else {
+        InputEncoding::Windows1252
+    }
+}
+
+/// Text encodings that Tidy can write word lists in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum OutputEncoding {
+    #[default]
+    Utf8,
+    /// Little-endian UTF-16, starting with a byte order mark
+    Utf16Le,
+    /// Big-endian UTF-16, starting with a byte order mark
+    Utf16Be,
+    Latin1,
+    Windows1252,
+}
+
+impl FromStr for OutputEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "utf-8" | "utf8" => Ok(OutputEncoding::Utf8),
+            "utf-16le" | "utf16le" | "utf-16" | "utf16" => Ok(OutputEncoding::Utf16Le),
+            "utf-16be" | "utf16be" => Ok(OutputEncoding::Utf16Be),
+            "latin1" | "latin-1" | "iso-8859-1" => Ok(OutputEncoding::Latin1),
+            "windows-1252" | "cp1252" => Ok(OutputEncoding::Windows1252),
+            _ => Err(format!(
+                "Unknown encoding {}. Use utf-8, utf-16le, utf-16be, latin1, or windows-1252.",
+                s
+            )),
+        }
+    }
+}
+
+/// Encode a `String` into the bytes of the given encoding. Errors if
+/// the text contains a character that the encoding can not represent.
+/// ```
+/// use tidy_core::encodings::{encode, OutputEncoding};
+/// assert_eq!(encode("café", OutputEncoding::Latin1).unwrap(), b"caf\xe9");
+/// assert_eq!(encode("“hi”", OutputEncoding::Windows1252).unwrap(), b"\x93hi\x94");
+/// assert_eq!(encode("hi", OutputEncoding::Utf16Be).unwrap(), b"\xFE\xFF\x00h\x00i");
+/// assert!(encode("京", OutputEncoding::Latin1).is_err());
+/// ```
+pub fn encode(text: &str, encoding: OutputEncoding) -> Result<Vec<u8>, String> {
+    match encoding {
+        OutputEncoding::Utf8 => Ok(text.as_bytes().to_vec()),
+        OutputEncoding::Utf16Le => Ok("\u{FEFF}"
+            .encode_utf16()
+            .chain(text.encode_utf16())
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect()),
+        OutputEncoding::Utf16Be => Ok("\u{FEFF}"
+            .encode_utf16()
+            .chain(text.encode_utf16())
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect()),
+        OutputEncoding::Latin1 => text
+            .chars()
+            .map(|c| {
+                u8::try_from(c as u32)
+                    .map_err(|_| format!("Character {} can not be encoded as Latin-1", c))
+            })
+            .collect(),
+        OutputEncoding::Windows1252 => text
+            .chars()
+            .map(|c| {
+                match WINDOWS_1252_HIGH_CHARACTERS
+                    .iter()
+                    .position(|&high_character| high_character == Some(c))
+                {
+                    Some(i) => Ok(0x80 + i as u8),
+                    // Windows-1252 uses 0x80 through 0x9F for the characters above
+                    None if (c as u32) < 0x80 || (0xA0..=0xFF).contains(&(c as u32)) => Ok(c as u8),
+                    None => Err(format!(
+                        "Character {} can not be encoded as Windows-1252",
+                        c
+                    )),
+                }
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,118 @@
+//! Errors Tidy can return instead of a tidied list
+use crate::TidyReport;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TidyError {
+    /// The request's locale couldn't be parsed
+    InvalidLocale(String),
+    /// The request's locale was parsed, but Tidy has no rules for sorting
+    /// words in it
+    UnsortableLocale(String),
+    /// The request's Unicode normalization form isn't one Tidy knows
+    InvalidNormalizationForm(String),
+    /// The request asked to ignore metadata both before and after words
+    IgnoringMetadataOnBothSides,
+    /// There's no stemmer for the requested language
+    UnknownStemmerLanguage(String),
+    /// The request asked to stem words, but Tidy was built without the
+    /// `stemming` feature
+    StemmingNotEnabled,
+    /// The request asked to sort by frequency, but not to ignore metadata
+    /// after words, where frequencies would be
+    FrequencySortWithoutFrequencies,
+    /// Even every inputted word isn't enough to whittle the list to the
+    /// requested length
+    WhittleListTooShort { requested: usize, longest: usize },
+    /// No number of words to take makes a list of exactly the requested
+    /// length. Has the closest lengths on either side: the length of the
+    /// list made by taking the fewest words that make a list that's too
+    /// long, and by taking one fewer word. Also has how many guesses the
+    /// whittle made.
+    WhittleLengthUnreachable {
+        requested: usize,
+        words_taken: usize,
+        length: usize,
+        shorter_length: usize,
+        guesses: usize,
+    },
+    /// A custom step that changes the list as a whole was put after a word
+    /// step, where it would be run on one word at a time. Has the names of
+    /// the custom step and the word step.
+    ListStepAfterWordStep { custom: String, step: String },
+    /// Dice rolls were asked for with a number of sides other than 2 to 36
+    InvalidDiceSides(u8),
+    /// The request's interrupted flag was set before Tidy finished. Has a
+    /// report on the steps done before then.
+    Interrupted(Box<TidyReport>),
+}
+
+impl fmt::Display for TidyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TidyError::InvalidLocale(locale) => write!(
+                f,
+                "Error: given locale {} is not parse-able. Try form similar to en-US or es-ES.",
+                locale
+            ),
+            TidyError::UnsortableLocale(locale) => write!(
+                f,
+                "Error: Tidy doesn't know how to sort words in locale {}. Try a locale like en-US or es-ES.",
+                locale
+            ),
+            TidyError::InvalidNormalizationForm(form) => write!(
+                f,
+                "Error: Unknown Unicode Normalization Form {}. Use nfc, nfd, nfkc, or nfkd.",
+                form
+            ),
+            TidyError::IgnoringMetadataOnBothSides => {
+                write!(f, "Error: Can't ignore metadata on both sides currently")
+            }
+            TidyError::UnknownStemmerLanguage(language) => write!(
+                f,
+                "Error: Can't stem words of language {}. Languages are: {}.",
+                language,
+                crate::stemming::STEMMER_LANGUAGES.join(", ")
+            ),
+            TidyError::StemmingNotEnabled => write!(
+                f,
+                "Error: This copy of Tidy was built without the stemming feature, so it can't lemmatize words. Reinstall with `cargo install --features stemming`."
+            ),
+            TidyError::FrequencySortWithoutFrequencies => write!(
+                f,
+                "Error: Sorting by frequency needs frequency counts after each word. Use --ignore-after to say what separates them."
+            ),
+            TidyError::WhittleListTooShort { requested, longest } => write!(
+                f,
+                "Error: Can't whittle to {} words. Using every inputted word only makes a list of {} words.",
+                requested, longest
+            ),
+            TidyError::WhittleLengthUnreachable {
+                requested,
+                words_taken,
+                length,
+                shorter_length,
+                guesses,
+            } => write!(
+                f,
+                "Error: Can't whittle to exactly {} words. Taking {} words makes a list of {} words, but taking one fewer makes a list of {} words. Gave up after {} guesses.",
+                requested, words_taken, length, shorter_length, guesses
+            ),
+            TidyError::ListStepAfterWordStep { custom, step } => write!(
+                f,
+                "Error: Custom step {} works on the whole list, so it can't run after word step {}. Put it after a step like dedup.",
+                custom, step
+            ),
+            TidyError::InvalidDiceSides(sides) => write!(
+                f,
+                "Error: Dice must have between 2 and 36 sides, not {}.",
+                sides
+            ),
+            TidyError::Interrupted(_report) => {
+                write!(f, "Error: Interrupted before the list was finished.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TidyError {}
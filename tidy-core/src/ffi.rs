@@ -0,0 +1,148 @@
+//! C bindings, so that tools written in other languages, like Python or
+//! Node, can run Tidy on a buffer without shelling out to the `tidy`
+//! command-line tool. Built with the `ffi` feature.
+//!
+//! From C, a list is tidied like this:
+//!
+//! ```c
+//! TidyOptions options = tidy_default_options();
+//! options.to_lowercase = true;
+//! options.minimum_length = 3;
+//! char *tidied = tidy_run(text, strlen(text), &options);
+//! if (tidied != NULL) {
+//!     puts(tidied);
+//!     tidy_free_string(tidied);
+//! }
+//! ```
+use crate::list_manipulations::SortOrder;
+use crate::{tidy_lines_explained, TidyRequest};
+use std::ffi::{c_char, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::slice;
+
+/// The options of a `TidyRequest` that can be set from C. Numbers set to
+/// 0 mean the option isn't used. Get the defaults with
+/// `tidy_default_options`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TidyOptions {
+    pub to_lowercase: bool,
+    pub should_straighten_quotes: bool,
+    pub should_remove_prefix_words: bool,
+    pub should_remove_suffix_words: bool,
+    pub should_schlinkert_prune: bool,
+    pub should_remove_nonalphanumeric: bool,
+    pub should_delete_nonalphanumeric: bool,
+    pub should_remove_nonalphabetic: bool,
+    pub should_remove_nonascii: bool,
+    pub should_remove_integers: bool,
+    pub should_delete_integers: bool,
+    pub sort_alphabetically: bool,
+    pub minimum_length: usize,
+    pub maximum_length: usize,
+    pub maximum_shared_prefix_length: usize,
+    pub minimum_edit_distance: usize,
+    pub print_first: usize,
+    pub print_rand: usize,
+}
+
+impl TidyOptions {
+    /// Makes the `TidyRequest` these options describe, with no words
+    /// ```
+    /// use tidy_core::ffi::TidyOptions;
+    /// let options = TidyOptions {
+    ///     minimum_length: 3,
+    ///     ..Default::default()
+    /// };
+    /// let req = options.to_request();
+    /// assert_eq!(req.minimum_length, Some(3));
+    /// assert_eq!(req.maximum_length, None);
+    /// ```
+    pub fn to_request(&self) -> TidyRequest {
+        let unless_zero = |n: usize| if n == 0 { None } else { Some(n) };
+        TidyRequest {
+            to_lowercase: self.to_lowercase,
+            should_straighten_quotes: self.should_straighten_quotes,
+            should_remove_prefix_words: self.should_remove_prefix_words,
+            should_remove_suffix_words: self.should_remove_suffix_words,
+            should_schlinkert_prune: self.should_schlinkert_prune,
+            should_remove_nonalphanumeric: self.should_remove_nonalphanumeric,
+            should_delete_nonalphanumeric: self.should_delete_nonalphanumeric,
+            should_remove_nonalphabetic: self.should_remove_nonalphabetic,
+            should_remove_nonascii: self.should_remove_nonascii,
+            should_remove_integers: self.should_remove_integers,
+            should_delete_integers: self.should_delete_integers,
+            sort_by: if self.sort_alphabetically {
+                SortOrder::Alphabetical
+            } else {
+                SortOrder::Unsorted
+            },
+            minimum_length: unless_zero(self.minimum_length),
+            maximum_length: unless_zero(self.maximum_length),
+            maximum_shared_prefix_length: unless_zero(self.maximum_shared_prefix_length),
+            minimum_edit_distance: unless_zero(self.minimum_edit_distance),
+            print_first: unless_zero(self.print_first),
+            print_rand: unless_zero(self.print_rand),
+            locale: "en-US".to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Options that don't change the list, besides removing blank lines and
+/// duplicates
+#[no_mangle]
+pub extern "C" fn tidy_default_options() -> TidyOptions {
+    TidyOptions::default()
+}
+
+/// Tidies `input_len` bytes of UTF-8 text at `input`, with one word per
+/// line, the way `options` asks. Returns the tidied list as a
+/// NUL-terminated string with a line feed after each word, which must be
+/// freed with `tidy_free_string`.
+///
+/// Returns a null pointer if `input` isn't valid UTF-8 or the options are
+/// invalid.
+///
+/// # Safety
+///
+/// `input` must point to at least `input_len` readable bytes, and
+/// `options` must point to a `TidyOptions`.
+#[no_mangle]
+pub unsafe extern "C" fn tidy_run(
+    input: *const u8,
+    input_len: usize,
+    options: *const TidyOptions,
+) -> *mut c_char {
+    if input.is_null() || options.is_null() {
+        return std::ptr::null_mut();
+    }
+    let bytes = slice::from_raw_parts(input, input_len);
+    let req = (*options).to_request();
+    // Panics can't unwind into C
+    let tidied = panic::catch_unwind(AssertUnwindSafe(|| {
+        let text = std::str::from_utf8(bytes).ok()?;
+        let lines: Vec<&str> = text.lines().collect();
+        let (list, _report) = tidy_lines_explained(&lines, &req).ok()?;
+        let output: String = list.iter().map(|word| format!("{}\n", word)).collect();
+        // Words can't have NUL bytes in them and still be passed back to C
+        CString::new(output).ok()
+    }));
+    match tidied {
+        Ok(Some(output)) => output.into_raw(),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+/// Frees a string returned by `tidy_run`.
+///
+/// # Safety
+///
+/// `s` must be a pointer returned by `tidy_run` that hasn't been freed
+/// yet, or null.
+#[no_mangle]
+pub unsafe extern "C" fn tidy_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
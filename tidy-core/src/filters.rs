@@ -0,0 +1,375 @@
+//! Steps of a caller's own, run at a chosen place in the tidying pipeline.
+//!
+//! A `WordFilter` decides whether to keep each word, a `WordTransform`
+//! changes each word, and a `ListTransform` changes the list as a whole.
+//! Wrapped in a `CustomStep`, any of them can be put in a
+//! `TidyRequest`'s `custom_steps`, to run right after one of Tidy's own
+//! `PipelineStep`s, so that programs using this crate can add to what Tidy
+//! does without changing it.
+//!
+//! Tidy's own word steps go through the same traits: see `BuiltinWordStep`.
+use crate::list_manipulations::*;
+use crate::pipeline::PipelineStep;
+use crate::{count_characters, is_requested, parse_delimiter, Metadata, TidyRequest};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Decides which words to keep
+pub trait WordFilter: Send + Sync {
+    /// A short name for the filter, for logs
+    fn name(&self) -> &str;
+    /// Whether to keep the word
+    fn keep(&self, word: &str) -> bool;
+    /// Like `keep`, but also given the metadata of the word's line, if it
+    /// has any. Ignores the metadata unless overridden.
+    fn keep_with_metadata(&self, word: &str, _metadata: Option<&str>) -> bool {
+        self.keep(word)
+    }
+}
+
+/// Changes each word, like lowercasing it
+pub trait WordTransform: Send + Sync {
+    /// A short name for the transform, for logs
+    fn name(&self) -> &str;
+    /// The word, changed. Words changed to nothing are removed.
+    fn transform(&self, word: &str) -> String;
+    /// Like `transform`, but also given the metadata of the word's line, if
+    /// it has any. Ignores the metadata unless overridden.
+    fn transform_with_metadata(&self, word: &str, _metadata: Option<&str>) -> String {
+        self.transform(word)
+    }
+}
+
+/// Changes the list as a whole, like by handing it to another program
+pub trait ListTransform: Send + Sync {
+    /// A short name for the transform, for logs
+    fn name(&self) -> &str;
+    /// The new list
+    fn transform_list(&self, list: &[String]) -> Vec<String>;
+}
+
+/// A filter or transform to run as a step of the pipeline
+/// ```
+/// use tidy_core::filters::CustomStep;
+/// let no_q = CustomStep::filter("no-q", |word: &str| !word.contains('q'));
+/// assert_eq!(no_q.apply("queen"), None);
+/// let shout = CustomStep::transform("shout", |word: &str| word.to_uppercase());
+/// assert_eq!(shout.apply("hello"), Some("HELLO".to_string()));
+/// ```
+#[derive(Clone)]
+pub enum CustomStep {
+    Filter(Arc<dyn WordFilter>),
+    Transform(Arc<dyn WordTransform>),
+    /// Can only be run after a step that depends on the whole list, like
+    /// dedup. `apply` gives it a list of just the one word.
+    List(Arc<dyn ListTransform>),
+}
+
+impl CustomStep {
+    /// Makes a filter step from a name and a function that says whether to
+    /// keep a word
+    pub fn filter(name: &str, keep: impl Fn(&str) -> bool + Send + Sync + 'static) -> CustomStep {
+        CustomStep::Filter(Arc::new(FilterFn {
+            name: name.to_string(),
+            keep,
+        }))
+    }
+
+    /// Makes a transform step from a name and a function that changes a word
+    pub fn transform(
+        name: &str,
+        transform: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> CustomStep {
+        CustomStep::Transform(Arc::new(TransformFn {
+            name: name.to_string(),
+            transform,
+        }))
+    }
+
+    /// Makes a transform step from a name and a function that changes a
+    /// word, given the word and the metadata of its line, if it has any
+    pub fn transform_with_metadata(
+        name: &str,
+        transform: impl Fn(&str, Option<&str>) -> String + Send + Sync + 'static,
+    ) -> CustomStep {
+        CustomStep::Transform(Arc::new(MetadataTransformFn {
+            name: name.to_string(),
+            transform,
+        }))
+    }
+
+    /// Makes a step from a name and a function that changes the whole list
+    pub fn list(
+        name: &str,
+        transform_list: impl Fn(&[String]) -> Vec<String> + Send + Sync + 'static,
+    ) -> CustomStep {
+        CustomStep::List(Arc::new(ListTransformFn {
+            name: name.to_string(),
+            transform_list,
+        }))
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            CustomStep::Filter(filter) => filter.name(),
+            CustomStep::Transform(transform) => transform.name(),
+            CustomStep::List(transform) => transform.name(),
+        }
+    }
+
+    /// Runs the step on a word: returns the (possibly changed) word, or
+    /// `None` if the word is removed
+    pub fn apply(&self, word: &str) -> Option<String> {
+        self.apply_with_metadata(word, None)
+    }
+
+    /// Like `apply`, for a word from a line with the given metadata
+    pub fn apply_with_metadata(&self, word: &str, metadata: Option<&str>) -> Option<String> {
+        let new_word = match self {
+            CustomStep::Filter(filter) if filter.keep_with_metadata(word, metadata) => {
+                word.to_string()
+            }
+            CustomStep::Filter(_) => return None,
+            CustomStep::Transform(transform) => transform
+                .transform_with_metadata(word, metadata)
+                .trim()
+                .to_string(),
+            CustomStep::List(transform) => {
+                match transform.transform_list(&[word.to_string()]).first() {
+                    Some(new_word) => new_word.trim().to_string(),
+                    None => return None,
+                }
+            }
+        };
+        if new_word.is_empty() {
+            None
+        } else {
+            Some(new_word)
+        }
+    }
+
+    /// Runs the step on each word of a list, keeping the list's order.
+    /// `metadata_of_words` has the metadata of words that have any.
+    pub fn apply_to_list(
+        &self,
+        list: &[String],
+        metadata_of_words: &HashMap<String, Metadata>,
+    ) -> Vec<String> {
+        match self {
+            CustomStep::List(transform) => transform
+                .transform_list(list)
+                .iter()
+                .map(|word| word.trim().to_string())
+                .filter(|word| !word.is_empty())
+                .collect(),
+            _ => list
+                .iter()
+                .filter_map(|word| {
+                    let metadata = metadata_of_words.get(word);
+                    self.apply_with_metadata(word, metadata.map(|m| m.text.as_str()))
+                })
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Debug for CustomStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CustomStep::Filter(filter) => write!(f, "Filter({})", filter.name()),
+            CustomStep::Transform(transform) => write!(f, "Transform({})", transform.name()),
+            CustomStep::List(transform) => write!(f, "List({})", transform.name()),
+        }
+    }
+}
+
+/// One of Tidy's own word steps, done as the request asks. Steps that
+/// change words, like lowercase, are `WordTransform`s; steps that remove
+/// words, like reject, are `WordFilter`s. A step does nothing if the request
+/// didn't ask for it.
+/// ```
+/// use tidy_core::filters::{BuiltinWordStep, WordFilter, WordTransform};
+/// use tidy_core::pipeline::PipelineStep;
+/// use tidy_core::TidyRequest;
+/// let req = TidyRequest {
+///     to_lowercase: true,
+///     minimum_length: Some(4),
+///     ..Default::default()
+/// };
+/// let lowercase = BuiltinWordStep::new(PipelineStep::Lowercase, &req);
+/// assert_eq!(lowercase.transform("Hello"), "hello");
+/// let minimum_length = BuiltinWordStep::new(PipelineStep::MinimumLength, &req);
+/// assert!(!minimum_length.keep("cat"));
+/// ```
+pub struct BuiltinWordStep<'a> {
+    step: PipelineStep,
+    req: &'a TidyRequest,
+}
+
+impl<'a> BuiltinWordStep<'a> {
+    /// Panics if `step` isn't a word step
+    pub fn new(step: PipelineStep, req: &'a TidyRequest) -> BuiltinWordStep<'a> {
+        assert!(step.is_word_step(), "{} is not a word step", step.name());
+        BuiltinWordStep { step, req }
+    }
+}
+
+impl WordFilter for BuiltinWordStep<'_> {
+    fn name(&self) -> &str {
+        self.step.name()
+    }
+
+    fn keep(&self, word: &str) -> bool {
+        let req = self.req;
+        if !is_requested(self.step, req) {
+            return true;
+        }
+        match self.step {
+            // https://doc.rust-lang.org/std/primitive.char.html#method.is_ascii
+            PipelineStep::RemoveNonascii => word.is_ascii(),
+            PipelineStep::RemoveNonalphanumeric => word.chars().all(|c| c.is_alphanumeric()),
+            PipelineStep::RemoveNonalphabetic => word.chars().all(|c| c.is_alphabetic()),
+            PipelineStep::RemoveNonLatinAlphabetic => {
+                word.chars().all(|chr| is_latin_alphabetic(chr as u16))
+            }
+            PipelineStep::RemoveMixedScript => !is_mixed_script(word),
+            PipelineStep::RemoveMultiword => !word.chars().any(|c| c.is_whitespace()),
+            PipelineStep::RemoveIntegers => !word.chars().any(|c| c.is_numeric()),
+            PipelineStep::RemoveConfusableChars => {
+                !has_confusable_chars(word, req.confusable_chars.as_ref().unwrap())
+            }
+            PipelineStep::RemoveDoubledLetters => !has_doubled_letter(word),
+            PipelineStep::RemoveConsonantClusters => {
+                !has_consonant_cluster(word, req.consonant_clusters.as_ref().unwrap())
+            }
+            PipelineStep::RemoveGibberish => {
+                gibberish_score(word) < req.gibberish_threshold.unwrap()
+            }
+            PipelineStep::Reject => !req.reject_list.as_ref().unwrap().contains(word),
+            PipelineStep::Approve => req.approved_list.as_ref().unwrap().contains(word),
+            PipelineStep::MinimumLength => count_characters(word) >= req.minimum_length.unwrap(),
+            PipelineStep::MaximumLength => count_characters(word) <= req.maximum_length.unwrap(),
+            // Word modifications keep every word
+            _ => true,
+        }
+    }
+}
+
+impl WordTransform for BuiltinWordStep<'_> {
+    fn name(&self) -> &str {
+        self.step.name()
+    }
+
+    fn transform(&self, word: &str) -> String {
+        let req = self.req;
+        if !is_requested(self.step, req) {
+            return word.to_string();
+        }
+        match self.step {
+            PipelineStep::Field => take_field(
+                word,
+                req.field_separator.and_then(parse_delimiter),
+                req.field_number.unwrap(),
+            )
+            .to_string(),
+            PipelineStep::DeleteBefore => {
+                let delimiter = req.should_delete_before_first_delimiter.unwrap();
+                delete_before_char(
+                    word,
+                    parse_delimiter(delimiter).unwrap(),
+                    req.delimiter_occurrence,
+                )
+                .to_string()
+            }
+            PipelineStep::DeleteAfter => {
+                let delimiter = req.should_delete_after_first_delimiter.unwrap();
+                delete_after_char(
+                    word,
+                    parse_delimiter(delimiter).unwrap(),
+                    req.delimiter_occurrence,
+                )
+                .to_string()
+            }
+            PipelineStep::DeleteIntegers if word.chars().any(|c| c.is_numeric()) => {
+                delete_integers(word.to_string())
+            }
+            PipelineStep::DeleteNonalphanumeric if word.chars().any(|c| c.is_alphanumeric()) => {
+                delete_nonalphanumeric(word.to_string())
+            }
+            PipelineStep::Lowercase => word.to_ascii_lowercase(),
+            PipelineStep::StraightenQuotes => straighten_quotes(word),
+            PipelineStep::DeleteApostrophes => delete_apostrophes(word),
+            PipelineStep::NormalizeHyphens => normalize_hyphens(word),
+            PipelineStep::JoinMultiword => {
+                join_multiword(word, req.join_multiword_separator.as_ref().unwrap())
+            }
+            // Word removals leave words as they are
+            _ => word.to_string(),
+        }
+    }
+}
+
+struct FilterFn<F> {
+    name: String,
+    keep: F,
+}
+
+impl<F: Fn(&str) -> bool + Send + Sync> WordFilter for FilterFn<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn keep(&self, word: &str) -> bool {
+        (self.keep)(word)
+    }
+}
+
+struct TransformFn<F> {
+    name: String,
+    transform: F,
+}
+
+impl<F: Fn(&str) -> String + Send + Sync> WordTransform for TransformFn<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn transform(&self, word: &str) -> String {
+        (self.transform)(word)
+    }
+}
+
+struct MetadataTransformFn<F> {
+    name: String,
+    transform: F,
+}
+
+impl<F: Fn(&str, Option<&str>) -> String + Send + Sync> WordTransform for MetadataTransformFn<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn transform(&self, word: &str) -> String {
+        (self.transform)(word, None)
+    }
+
+    fn transform_with_metadata(&self, word: &str, metadata: Option<&str>) -> String {
+        (self.transform)(word, metadata)
+    }
+}
+
+struct ListTransformFn<F> {
+    name: String,
+    transform_list: F,
+}
+
+impl<F: Fn(&[String]) -> Vec<String> + Send + Sync> ListTransform for ListTransformFn<F> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn transform_list(&self, list: &[String]) -> Vec<String> {
+        (self.transform_list)(list)
+    }
+}
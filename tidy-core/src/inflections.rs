@@ -0,0 +1,107 @@
+//! Find probable inflections of other words on a list, like "cats" (of
+//! "cat") or "running" (of "run"), using simple English suffix rules
+use std::collections::HashSet;
+
+/// Guesses the uninflected forms a word could have, using simple English
+/// suffix rules for plurals and the -ed and -ing forms of verbs. Most
+/// guesses aren't real words; callers should check them against a list.
+/// ```
+/// use tidy_core::inflections::guess_base_forms;
+/// assert!(guess_base_forms("ponies").contains(&"pony".to_string()));
+/// assert!(guess_base_forms("running").contains(&"run".to_string()));
+/// assert!(guess_base_forms("baked").contains(&"bake".to_string()));
+/// ```
+pub fn guess_base_forms(word: &str) -> Vec<String> {
+    let mut guesses = vec![];
+    let mut add_guess = |stem: &str, ending: &str| {
+        // Very short stems are too likely to be coincidences
+        if stem.chars().count() >= 2 {
+            guesses.push(format!("{}{}", stem, ending));
+        }
+    };
+    if let Some(stem) = word.strip_suffix("ies") {
+        add_guess(stem, "y"); // ponies -> pony
+    }
+    if let Some(stem) = word.strip_suffix("es") {
+        add_guess(stem, ""); // boxes -> box
+    }
+    if let Some(stem) = word.strip_suffix('s') {
+        if !stem.ends_with('s') {
+            add_guess(stem, ""); // cats -> cat
+        }
+    }
+    for suffix in ["ing", "ed"] {
+        if let Some(stem) = word.strip_suffix(suffix) {
+            add_guess(stem, ""); // jumping -> jump
+            add_guess(stem, "e"); // making -> make
+            if let Some(undoubled) = strip_doubled_consonant(stem) {
+                add_guess(undoubled, ""); // running -> run
+            }
+        }
+    }
+    if let Some(stem) = word.strip_suffix("ied") {
+        add_guess(stem, "y"); // carried -> carry
+    }
+    guesses
+}
+
+/// If a stem ends in a doubled consonant, like "runn", returns it without
+/// the last one
+fn strip_doubled_consonant(stem: &str) -> Option<&str> {
+    let mut chars = stem.chars().rev();
+    match (chars.next(), chars.next()) {
+        (Some(last), Some(second_to_last))
+            if last == second_to_last && !"aeiou".contains(last) && last.is_alphabetic() =>
+        {
+            Some(&stem[..stem.len() - last.len_utf8()])
+        }
+        _ => None,
+    }
+}
+
+/// Finds words on the list that are probably inflections of another word on
+/// the list. Returns pairs of the base word and its inflection, in the order
+/// the inflections appear on the list.
+/// ```
+/// use tidy_core::inflections::find_inflections;
+/// let list: Vec<String> = ["cat", "cats", "run", "running", "corner", "corn"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// assert_eq!(
+///     find_inflections(&list),
+///     vec![
+///         ("cat".to_string(), "cats".to_string()),
+///         ("run".to_string(), "running".to_string())
+///     ]
+/// );
+/// ```
+pub fn find_inflections(list: &[String]) -> Vec<(String, String)> {
+    let words: HashSet<&str> = list.iter().map(|word| word.as_str()).collect();
+    list.iter()
+        .filter_map(|word| {
+            guess_base_forms(word)
+                .into_iter()
+                .find(|guess| guess != word && words.contains(guess.as_str()))
+                .map(|base| (base, word.to_string()))
+        })
+        .collect()
+}
+
+/// Removes words that are probably inflections of another word on the list
+/// (see `find_inflections`), keeping the base forms.
+/// ```
+/// use tidy_core::inflections::remove_inflections;
+/// let list: Vec<String> = ["cats", "cat", "boxes", "ponies"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(remove_inflections(&list), vec!["cat", "boxes", "ponies"]);
+/// ```
+pub fn remove_inflections(list: &[String]) -> Vec<String> {
+    let inflections: HashSet<String> = find_inflections(list)
+        .into_iter()
+        .map(|(_base, inflection)| inflection)
+        .collect();
+    list.iter()
+        .filter(|word| !inflections.contains(*word))
+        .cloned()
+        .collect()
+}
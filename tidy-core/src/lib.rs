@@ -0,0 +1,1714 @@
+//! Combine and clean word lists.
+//!
+//! The simplest way to use Tidy as a library is to fill out a `TidyRequest`
+//! and pass it to `tidy_list`. But each of the operations Tidy does is also a
+//! public function that can be used on its own:
+//!
+//! - Word modifications, like `delete_integers` and `straighten_quotes`, and
+//!   list operations, like `remove_prefix_words`,
+//!   `enforce_minimum_edit_distance`, and `schlinkert_prune`, are in
+//!   `list_manipulations`.
+//! - `split_off_metadata` and `rejoin_metadata` handle lines with metadata,
+//!   like dice rolls or word frequencies, around the word.
+//! - `dice::print_as_dice` and `cards::print_as_cards` make the dice rolls
+//!   and card codes that can be printed before each word.
+//! - `filters` has the `WordFilter` and `WordTransform` traits, for running
+//!   steps of your own as part of a `TidyRequest`.
+//! - `words::tidy_words` tidies typed `Word`s, which keep their frequency,
+//!   source list, and original line through tidying.
+//!
+//! Unlike the `tidy` command-line tool, this crate doesn't print anything or
+//! read any files, so it can be built for WebAssembly (see `tidy_bytes`).
+//!
+//! ```
+//! use tidy_core::list_manipulations::{delete_integers, remove_prefix_words};
+//! let list: Vec<String> = ["news1", "newspaper2", "paper3"]
+//!     .iter()
+//!     .map(|word| delete_integers(word.to_string()))
+//!     .collect();
+//! assert_eq!(remove_prefix_words(&list), vec!["newspaper", "paper"]);
+//! ```
+use icu::locid::Locale;
+use rand::seq::SliceRandom;
+pub mod assertions;
+pub mod bip39;
+pub mod bloom_filter;
+pub mod cards;
+pub mod checkpoint;
+pub mod checksum;
+pub mod coins;
+pub mod compare;
+pub mod compound_collisions;
+pub mod dice;
+pub mod distributions;
+pub mod edit_distance;
+pub mod encodings;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod filters;
+pub mod inflections;
+pub mod list_manipulations;
+pub mod pipeline;
+pub mod progress;
+pub mod sampling;
+pub mod sardinas_patterson_pruning;
+pub mod stemming;
+pub mod suggestions;
+mod timing;
+pub mod uniquely_decodable;
+pub mod words;
+use crate::bloom_filter::dedup_lines_with_bloom_filter;
+use crate::checkpoint::{CheckpointCallback, WhittleCheckpoint};
+use crate::compound_collisions::remove_compound_collisions;
+use crate::edit_distance::find_edit_distance;
+use crate::error::TidyError;
+use crate::filters::{BuiltinWordStep, CustomStep, WordFilter, WordTransform};
+use crate::inflections::remove_inflections;
+use crate::list_manipulations::*;
+use crate::pipeline::{PipelineStep, DEFAULT_PIPELINE};
+use crate::progress::ProgressCallback;
+use crate::sampling::{make_rng, sample, RandStrategy};
+use crate::sardinas_patterson_pruning::SchlinkertStrategy;
+use crate::stemming::{collapse_by_stem, validate_stemmer_language};
+use crate::timing::Instant;
+use crate::uniquely_decodable::{check_decodability, find_ambiguous_concatenations};
+use log::{debug, info, warn};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+#[derive(Default, Debug, Clone)]
+pub struct TidyRequest {
+    pub list: Vec<String>,
+    pub take_first: Option<usize>,
+    pub take_rand: Option<usize>,
+    /// The weight of each line of `list`, like the weight of the file it
+    /// came from. `take_rand` is more likely to take lines with higher
+    /// weights.
+    pub line_weights: Option<Vec<f64>>,
+    /// Order to sort the finished list in
+    pub sort_by: SortOrder,
+    /// Reverse the order of the finished list, after any sorting
+    pub reverse_sort: bool,
+    /// Before tidying words, drop repeated lines, using a Bloom filter to
+    /// find them (see `bloom_filter::dedup_lines_with_bloom_filter`)
+    pub should_bloom_dedup: bool,
+    /// When de-duplicating, also treat words that only differ in case as
+    /// duplicates, keeping the given one
+    pub dedup_ignore_case: Option<CanonicalCase>,
+    /// Which of a word's lines to keep, with its metadata, when the same
+    /// word comes up more than once
+    pub dedup_keep: DedupKeep,
+    pub ignore_after_delimiter: Option<char>,
+    pub ignore_before_delimiter: Option<char>,
+    /// Keep all of the metadata after each word, even if it has more
+    /// delimiters in it, like a frequency count followed by a part of
+    /// speech. Otherwise only the metadata up to the next delimiter is kept.
+    /// Only affects metadata after words (see `ignore_after_delimiter`).
+    pub keep_metadata: bool,
+    pub normalization_form: Option<String>,
+    pub locale: String, // defaults to en-US
+    pub to_lowercase: bool,
+    /// How to capitalize the words of the list, after de-duplicating
+    pub capitalization: Capitalization,
+    pub should_straighten_quotes: bool,
+    pub should_delete_apostrophes: bool,
+    pub should_normalize_hyphens: bool,
+    pub should_remove_prefix_words: bool,
+    pub should_remove_suffix_words: bool,
+    pub should_remove_infix_words: bool,
+    /// Remove words until no word, or pair of words, spells the same thing
+    /// as another pair of words, like "input" and "in" + "put"
+    pub should_remove_compound_collisions: bool,
+    pub should_schlinkert_prune: bool,
+    pub schlinkert_strategy: SchlinkertStrategy,
+    /// Give up on a Schlinkert prune that takes longer than this, and remove
+    /// prefix words instead. Ignored on WebAssembly in the browser, which
+    /// has no clock (see `timing`).
+    pub schlinkert_timeout: Option<Duration>,
+    /// Before a Schlinkert prune, find up to this many examples of strings
+    /// that can be split into words from the list in two ways, for the
+    /// report (see `TidyReport::ambiguities`)
+    pub ambiguity_examples: Option<usize>,
+    pub should_best_prune: bool,
+    pub should_remove_nonalphanumeric: bool,
+    pub should_delete_nonalphanumeric: bool,
+    pub should_remove_nonalphabetic: bool,
+    pub should_remove_non_latin_alphabetic: bool,
+    pub should_remove_nonascii: bool,
+    /// Remove words that mix letters of more than one script, like Latin
+    /// and Cyrillic
+    pub should_remove_mixed_script: bool,
+    /// Remove entries with whitespace in them, like "ice cream"
+    pub should_remove_multiword: bool,
+    /// Join the words of entries with whitespace in them with this
+    /// separator, like "ice-cream" or "icecream"
+    pub join_multiword_separator: Option<String>,
+    pub should_remove_integers: bool,
+    pub should_delete_integers: bool,
+    /// Remove words with any of these characters, or sequences of
+    /// characters, in them (see `list_manipulations::DEFAULT_CONFUSABLE_CHARS`)
+    pub confusable_chars: Option<Vec<String>>,
+    /// Remove words with the same letter twice in a row, like "balloon"
+    pub should_remove_doubled_letters: bool,
+    /// Remove words with any of these clusters of consonants in them, like
+    /// `DEFAULT_CONSONANT_CLUSTERS`
+    pub consonant_clusters: Option<Vec<String>>,
+    /// Remove words that look like random junk rather than words, like
+    /// base64 blobs, with a `gibberish_score` at or above this threshold
+    pub gibberish_threshold: Option<f64>,
+    /// Take only this column of each line as the word, counting from 1
+    /// (see `list_manipulations::take_field`)
+    pub field_number: Option<usize>,
+    /// What separates the columns of `field_number`. If `None`, columns are
+    /// separated by runs of whitespace.
+    pub field_separator: Option<char>,
+    pub should_delete_after_first_delimiter: Option<char>,
+    pub should_delete_before_first_delimiter: Option<char>,
+    /// Which instance of the delimiter to delete before or after, on lines
+    /// with more than one
+    pub delimiter_occurrence: DelimiterOccurrence,
+    pub reject_list: Option<HashSet<String>>,
+    pub approved_list: Option<HashSet<String>>,
+    /// Words to put on the finished list whatever other steps would do with
+    /// them, though the list is still de-duplicated. Truncating the list
+    /// with `print_first` or `print_rand` keeps them.
+    pub always_include: Option<Vec<String>>,
+    /// The words of an earlier version of the list, in order. Words still
+    /// on the new list keep their positions, and truncating the list with
+    /// `print_first` or `print_rand` keeps them, where it can.
+    pub previous_list: Option<Vec<String>>,
+    pub homophones_list: Option<Vec<(String, String)>>,
+    /// Keep only the first of each set of words that are anagrams of each
+    /// other, like "lemon" and "melon"
+    pub should_remove_anagrams: bool,
+    /// Only count anagrams as colliding if they're within this edit
+    /// distance of each other
+    pub anagram_edit_distance: Option<usize>,
+    /// Remove words that are leetspeak variants of other words on the list,
+    /// or of words on the reject list, like "p4ss" of "pass", undoing these
+    /// substitutions of a character for a letter to find them
+    pub leet_substitutions: Option<Vec<(char, char)>>,
+    /// Remove words that are probably inflections of another word on the
+    /// list, like "cats" when "cat" is on the list
+    pub should_remove_inflections: bool,
+    /// Keep only the first of the words that share a stem in this language
+    /// (see `stemming::collapse_by_stem`). Needs the `stemming` feature.
+    pub lemmatize_language: Option<String>,
+    pub minimum_length: Option<usize>,
+    pub maximum_length: Option<usize>,
+    pub maximum_shared_prefix_length: Option<usize>,
+    /// Make words unique in this many leading characters, then cut them
+    /// down to just those characters
+    pub truncate_to_unique_length: Option<usize>,
+    pub minimum_edit_distance: Option<usize>,
+    pub print_rand: Option<usize>,
+    pub print_first: Option<usize>,
+    /// Cut the list down to the largest power of this base that it has
+    /// enough words for, like 7,776 (6^5) for base 6
+    pub cut_to_power: Option<usize>,
+    /// Which words `cut_to_power` keeps
+    pub cut_strategy: CutStrategy,
+    /// How `take_rand`, `print_rand`, and a random `cut_to_power` pick
+    /// words. Lines with `line_weights` are always picked by weight.
+    pub rand_strategy: RandStrategy,
+    /// Seed for every random choice, so the same request picks the same
+    /// words each time. If `None`, choices differ each time.
+    pub rand_seed: Option<u64>,
+    /// Keep at most this many words starting with each letter
+    pub max_per_first_letter: Option<usize>,
+    /// Limits on the share of the list taken up by words of some lengths
+    pub length_quotas: Vec<LengthQuota>,
+    /// Which words are removed first to meet `length_quotas`
+    pub quota_priority: QuotaPriority,
+    /// Have `print_rand`, `print_first`, and `cut_to_power` give each first
+    /// letter as even a share of the words as they can
+    pub balance_first_letters: bool,
+    /// The order to run steps in. If `None`, uses `DEFAULT_PIPELINE`. Steps
+    /// missing from a custom pipeline are not run. See `complete_pipeline`.
+    pub pipeline: Option<Vec<PipelineStep>>,
+    /// Filters and transforms of the caller's own, each with the step of the
+    /// pipeline to run it right after. They run in the order given, even if
+    /// the step they follow isn't requested, but not if a custom pipeline
+    /// leaves that step out.
+    pub custom_steps: Vec<(PipelineStep, CustomStep)>,
+    /// Called to report progress through slow operations, like a Schlinkert
+    /// prune or whittling.
+    pub progress: Option<ProgressCallback>,
+    /// Set (like by a Ctrl-C handler) to stop tidying early. Tidy then
+    /// returns `TidyError::Interrupted` with a report on the steps it did.
+    pub interrupted: Option<Arc<AtomicBool>>,
+    /// When whittling, where an earlier whittle of the same list to the same
+    /// length left off. Ignored if it doesn't fit this whittle.
+    pub whittle_resume_from: Option<WhittleCheckpoint>,
+    /// When whittling, called with where the whittle stands after each
+    /// guess, so it can be saved and resumed
+    pub whittle_checkpoint: Option<CheckpointCallback>,
+    /// When no number of words to take makes a whittled list of exactly the
+    /// requested length, cut the shortest list that's too long down to
+    /// length, either at random or by keeping the words taken first
+    pub whittle_finish: Option<CutStrategy>,
+}
+
+/// The name of the stage of word modifications and removals in a
+/// `TidyReport`'s `step_timings`
+pub const WORD_STEPS_STAGE: &str = "word-steps";
+
+/// What Tidy did to make a list, for callers to report to users
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct TidyReport {
+    /// How many words were left after each requested step of the pipeline,
+    /// in the order they ran. The first count is of the (non-blank)
+    /// inputted words.
+    pub step_counts: Vec<(&'static str, usize)>,
+    /// How long each stage of tidying took, in the order they ran. Word
+    /// modifications and removals are done a word at a time, all together,
+    /// so they're timed as one stage, named `WORD_STEPS_STAGE`, followed by
+    /// the time each of them took over every word. When
+    /// whittling, a "whittle" stage has the time spent on the guesses whose
+    /// lists weren't kept.
+    pub step_timings: Vec<(&'static str, Duration)>,
+    /// If a best prune was done, the method whose list was kept, and how
+    /// many words each method kept
+    pub best_prune: Option<(&'static str, Vec<(&'static str, usize)>)>,
+    /// Inputted lines without the delimiter of the metadata the request asked
+    /// to ignore
+    pub lines_without_metadata: Vec<String>,
+    /// When whittling, each number of words taken and the length of the list
+    /// it made, in the order they were tried
+    pub whittle_guesses: Vec<(usize, usize)>,
+    /// Ways the words the request always includes break the list's other
+    /// requested constraints, like "sun is a prefix of sunny"
+    pub always_include_conflicts: Vec<String>,
+    /// If updating a previous version of the list, each position where the
+    /// word changed, with the previous word and the new one (see
+    /// `list_manipulations::keep_previous_positions`)
+    pub previous_list_changes: Vec<ListChange>,
+    /// Whether a Schlinkert prune took longer than the request's timeout, so
+    /// prefix words were removed instead
+    pub schlinkert_timed_out: bool,
+    /// If the request asked for ambiguity examples, strings that could be
+    /// split into words from the list in two ways just before its Schlinkert
+    /// prune, as both sequences of words
+    pub ambiguities: Vec<(Vec<String>, Vec<String>)>,
+}
+
+/// A word split off of its line, along with the line's metadata, if any.
+/// Tidy carries these through its steps, so that steps only see the word,
+/// and re-attaches the metadata once the list is done.
+type WordAndMetadata = (String, Option<Metadata>);
+
+/// A line of a tidied list, along with the inputted line its word was made
+/// from, if it was made from one (words to always include aren't)
+type LineAndOrigin<'a> = (String, Option<&'a str>);
+
+/// Where metadata sits on a line, relative to the word
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MetadataPosition {
+    /// Metadata comes before the word, like the dice rolls on a diceware list
+    Start,
+    /// Metadata comes after the word, like a word frequency count
+    End,
+}
+
+/// Metadata split off of a line by `split_off_metadata`, along with what's
+/// needed to re-attach it to the word with `rejoin_metadata`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Metadata {
+    pub text: String,
+    pub delimiter: char,
+    pub position: MetadataPosition,
+}
+
+/// Splits a line into its word and any metadata, separated by the first
+/// `delimiter` on the line. Returns no metadata if the delimiter isn't on the
+/// line. If the line has more than one delimiter, only the text up to the
+/// second one is kept.
+/// ```
+/// use tidy_core::{split_off_metadata, rejoin_metadata, MetadataPosition};
+/// let (word, metadata) = split_off_metadata("11111\tabacus", '\t', MetadataPosition::Start);
+/// assert_eq!(word, "abacus");
+/// assert_eq!(rejoin_metadata("ABACUS", &metadata.unwrap()), "11111\tABACUS");
+///
+/// let (word, metadata) = split_off_metadata("abacus", ',', MetadataPosition::End);
+/// assert_eq!(word, "abacus");
+/// assert_eq!(metadata, None);
+/// ```
+pub fn split_off_metadata(
+    line: &str,
+    delimiter: char,
+    position: MetadataPosition,
+) -> (String, Option<Metadata>) {
+    let split_vec = split_and_vectorize(line, &delimiter.to_string());
+    if split_vec.len() == 1 {
+        return (line.to_string(), None);
+    }
+    let (word, metadata) = match position {
+        MetadataPosition::End => (split_vec[0], split_vec[1]),
+        MetadataPosition::Start => (split_vec[1], split_vec[0]),
+    };
+    (
+        word.to_string(),
+        Some(Metadata {
+            text: metadata.to_string(),
+            delimiter,
+            position,
+        }),
+    )
+}
+
+/// Re-attaches metadata split off by `split_off_metadata` to a (possibly
+/// modified) word.
+pub fn rejoin_metadata(word: &str, metadata: &Metadata) -> String {
+    match metadata.position {
+        MetadataPosition::End => format!("{}{}{}", word, metadata.delimiter, metadata.text),
+        MetadataPosition::Start => format!("{}{}{}", metadata.text, metadata.delimiter, word),
+    }
+}
+
+/// Simple helper function that splits a `str` by a given substring `str`,
+/// Then returns a Vector of `str`s.
+/// ```
+/// use tidy_core::split_and_vectorize;
+/// assert_eq!(split_and_vectorize("a:b:c",":"), vec!["a","b","c"]);
+/// ```
+/// I find this a handy general helper function.
+pub fn split_and_vectorize<'a>(string_to_split: &'a str, splitter: &str) -> Vec<&'a str> {
+    string_to_split.split(splitter).collect()
+}
+
+/// This is the large, key function of the program. It takes
+/// a `TidyRequest` object -- which includes the word list --
+/// and performs whatever functions the user has requesteed to
+/// perform on the list.
+///
+/// Returns an error if the request is invalid (see `validate_tidy_request`).
+/// Use `tidy_list_explained` to also get a report of what was done.
+pub fn tidy_list(req: &TidyRequest) -> Result<Vec<String>, TidyError> {
+    let (list, _report) = tidy_list_explained(req)?;
+    Ok(list)
+}
+
+/// Tidies text with one word per line, like the contents of a word list
+/// file, the way `req` asks, ignoring the request's `list`. Returns the
+/// tidied list as text, with a line feed after each word. Meant for
+/// callers that only have text to pass in, like JavaScript calling Tidy
+/// built for WebAssembly (with the `wasm` feature).
+///
+/// Returns an error if the request is invalid, like `tidy_list`.
+/// ```
+/// use tidy_core::{tidy_bytes, TidyRequest};
+/// let req = TidyRequest {
+///     to_lowercase: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(tidy_bytes("Zoo\r\napple\nzoo\n", &req).unwrap(), "zoo\napple\n");
+/// ```
+pub fn tidy_bytes(input: &str, req: &TidyRequest) -> Result<String, TidyError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let (list, _report) = tidy_lines_explained(&lines, req)?;
+    Ok(list.iter().map(|word| format!("{}\n", word)).collect())
+}
+
+/// Tidies the list like `tidy_list`, and also returns a report of what was
+/// done to it, including how many words were left after each step.
+/// ```
+/// use tidy_core::{tidy_list_explained, TidyRequest};
+/// let req = TidyRequest {
+///     list: vec!["Apple".to_string(), "apple".to_string(), "b".to_string()],
+///     to_lowercase: true,
+///     minimum_length: Some(2),
+///     ..Default::default()
+/// };
+/// let (list, report) = tidy_list_explained(&req).unwrap();
+/// assert_eq!(list, vec!["apple"]);
+/// assert_eq!(
+///     report.step_counts,
+///     vec![("input", 3), ("lowercase", 3), ("minimum-length", 2), ("dedup", 1)]
+/// );
+/// // Word modifications and removals are timed together, then one by one
+/// let timed_stages: Vec<&str> = report.step_timings.iter().map(|(stage, _elapsed)| *stage).collect();
+/// assert_eq!(
+///     timed_stages,
+///     vec!["word-steps", "lowercase", "minimum-length", "dedup"]
+/// );
+/// ```
+pub fn tidy_list_explained(req: &TidyRequest) -> Result<(Vec<String>, TidyReport), TidyError> {
+    let lines: Vec<&str> = req.list.iter().map(String::as_str).collect();
+    tidy_lines_explained(&lines, req)
+}
+
+/// Like `tidy_list_explained`, but tidies the given borrowed lines rather
+/// than the request's `list`, which is ignored. Lines are only copied into
+/// owned `String`s once they've made it through the word modifications and
+/// removals, so callers can pass slices of a very large file (like a
+/// memory-mapped one) without first copying every line of it.
+/// ```
+/// use tidy_core::{tidy_lines_explained, TidyRequest};
+/// let text = "Apple\napple\nb";
+/// let lines: Vec<&str> = text.lines().collect();
+/// let req = TidyRequest {
+///     to_lowercase: true,
+///     minimum_length: Some(2),
+///     ..Default::default()
+/// };
+/// let (list, _report) = tidy_lines_explained(&lines, &req).unwrap();
+/// assert_eq!(list, vec!["apple"]);
+/// ```
+pub fn tidy_lines_explained(
+    lines: &[&str],
+    req: &TidyRequest,
+) -> Result<(Vec<String>, TidyReport), TidyError> {
+    let (list, report) = tidy_lines_with_origins(lines, req)?;
+    Ok((without_origins(list), report))
+}
+
+/// Like `tidy_lines_explained`, but each line of the tidied list comes with
+/// the inputted line it was made from. Used by `words::tidy_words` to keep
+/// what's known about each word through tidying.
+pub(crate) fn tidy_lines_with_origins<'a>(
+    lines: &[&'a str],
+    req: &TidyRequest,
+) -> Result<(Vec<LineAndOrigin<'a>>, TidyReport), TidyError> {
+    validate_tidy_request(req)?;
+    let mut report = TidyReport::default();
+    // First, we need to do the two truncations
+    let mut list_to_tidy: Vec<&'a str> = lines.to_vec();
+    report
+        .step_counts
+        .push(("input", count_nonblank_lines(&list_to_tidy)));
+    list_to_tidy = match req.take_first {
+        Some(amount_to_take) => {
+            list_to_tidy.truncate(amount_to_take);
+            report
+                .step_counts
+                .push(("take-first", count_nonblank_lines(&list_to_tidy)));
+            list_to_tidy
+        }
+        None => list_to_tidy,
+    };
+    list_to_tidy = match req.take_rand {
+        Some(amount_to_take) => {
+            match req.line_weights {
+                Some(ref weights) => {
+                    list_to_tidy = take_weighted_random(
+                        &list_to_tidy,
+                        weights,
+                        amount_to_take,
+                        &mut make_rng(req.rand_seed),
+                    );
+                }
+                None => {
+                    list_to_tidy = sample(
+                        &list_to_tidy,
+                        amount_to_take,
+                        req.rand_strategy,
+                        &mut make_rng(req.rand_seed),
+                    );
+                }
+            }
+            report
+                .step_counts
+                .push(("take-rand", count_nonblank_lines(&list_to_tidy)));
+            list_to_tidy
+        }
+        None => list_to_tidy,
+    };
+    if req.should_bloom_dedup {
+        let started = Instant::now();
+        list_to_tidy = dedup_lines_with_bloom_filter(&list_to_tidy);
+        let elapsed = started.elapsed();
+        debug!("Bloom filter de-duplication took {:.2?}", elapsed);
+        report
+            .step_counts
+            .push(("bloom-dedup", count_nonblank_lines(&list_to_tidy)));
+        report.step_timings.push(("bloom-dedup", elapsed));
+    }
+    report.lines_without_metadata = find_lines_without_metadata(&list_to_tidy, req);
+    // Now we go word-by-word
+    let started = Instant::now();
+    let mut word_step_timings = HashMap::new();
+    let tidied_words: Vec<Result<WordAndMetadata, Option<PipelineStep>>> = list_to_tidy
+        .iter()
+        .map(|word| tidy_word_timed(word, req, Some(&mut word_step_timings)))
+        .collect::<Result<_, TidyError>>()?;
+    let word_steps_elapsed = started.elapsed();
+    debug!(
+        "Word modifications and removals took {:.2?}",
+        word_steps_elapsed
+    );
+    report
+        .step_timings
+        .push((WORD_STEPS_STAGE, word_steps_elapsed));
+    time_word_steps(&word_step_timings, req, &mut report.step_timings);
+    count_word_steps(&tidied_words, req, &mut report.step_counts);
+    let tidied_list = tidied_words
+        .into_iter()
+        .zip(list_to_tidy)
+        .filter_map(|(result, line)| Some((result.ok()?, line)))
+        .collect();
+    let tidied_list = tidy_whole_list(tidied_list, req, &mut report)?;
+    if is_interrupted(req) {
+        return Err(TidyError::Interrupted(Box::new(report)));
+    }
+    Ok((tidied_list, report))
+}
+
+/// Checks a request for options Tidy can't carry out, like a locale it
+/// can't parse.
+pub fn validate_tidy_request(req: &TidyRequest) -> Result<(), TidyError> {
+    if req.ignore_after_delimiter.is_some() && req.ignore_before_delimiter.is_some() {
+        return Err(TidyError::IgnoringMetadataOnBothSides);
+    }
+    if let Some(ref nf) = req.normalization_form {
+        if normalize_unicode("", nf).is_err() {
+            return Err(TidyError::InvalidNormalizationForm(nf.to_string()));
+        }
+    }
+    let sorts_alphabetically = matches!(
+        req.sort_by,
+        SortOrder::Alphabetical | SortOrder::LengthThenAlphabetical
+    );
+    if sorts_alphabetically && req.locale.parse::<Locale>().is_err() {
+        return Err(TidyError::InvalidLocale(req.locale.to_string()));
+    }
+    if let Some(ref language) = req.lemmatize_language {
+        validate_stemmer_language(language)?;
+    }
+    if req.sort_by == SortOrder::Frequency && req.ignore_after_delimiter.is_none() {
+        return Err(TidyError::FrequencySortWithoutFrequencies);
+    }
+    for (step, custom) in &req.custom_steps {
+        if step.is_word_step() && matches!(custom, CustomStep::List(_)) {
+            return Err(TidyError::ListStepAfterWordStep {
+                custom: custom.name().to_string(),
+                step: step.name().to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+fn count_nonblank_lines<S: AsRef<str>>(list: &[S]) -> usize {
+    list.iter()
+        .filter(|line| !line.as_ref().trim().is_empty())
+        .count()
+}
+
+/// Finds (non-blank) lines that don't have the delimiter of the metadata the
+/// request asks to ignore.
+fn find_lines_without_metadata(list: &[&str], req: &TidyRequest) -> Vec<String> {
+    match req.ignore_after_delimiter.or(req.ignore_before_delimiter) {
+        Some(delimiter) => {
+            let delimiter = parse_delimiter(delimiter).unwrap();
+            list.iter()
+                .filter(|line| !line.trim().is_empty() && !line.contains(delimiter))
+                .map(|line| line.to_string())
+                .collect()
+        }
+        None => vec![],
+    }
+}
+
+/// Counts how many words are left after each word step, given the result of
+/// `tidy_word_explained` for each inputted line.
+fn count_word_steps(
+    tidied_words: &[Result<WordAndMetadata, Option<PipelineStep>>],
+    req: &TidyRequest,
+    counts: &mut Vec<(&'static str, usize)>,
+) {
+    let mut words_left = tidied_words
+        .iter()
+        .filter(|result| **result != Err(None))
+        .count();
+    let mut removed_by: HashMap<PipelineStep, usize> = HashMap::new();
+    for step in tidied_words.iter().filter_map(|result| match result {
+        Err(Some(step)) => Some(step),
+        _ => None,
+    }) {
+        *removed_by.entry(*step).or_insert(0) += 1;
+    }
+    for step in pipeline_steps(req) {
+        if step.is_word_step() && runs(*step, req) {
+            let removed = removed_by.get(step).unwrap_or(&0);
+            words_left -= removed;
+            info!(
+                "{}: {} words left ({} removed)",
+                step.name(),
+                words_left,
+                removed
+            );
+            counts.push((step.name(), words_left));
+        }
+    }
+}
+
+/// Adds how long each word step took, over every word, to `timings`, in the
+/// order the steps ran. `word_step_timings` has the totals from
+/// `tidy_word_timed`.
+fn time_word_steps(
+    word_step_timings: &HashMap<PipelineStep, Duration>,
+    req: &TidyRequest,
+    timings: &mut Vec<(&'static str, Duration)>,
+) {
+    for step in pipeline_steps(req) {
+        if let Some(elapsed) = word_step_timings.get(step) {
+            debug!("{} took {:.2?}", step.name(), elapsed);
+            timings.push((step.name(), *elapsed));
+        }
+    }
+}
+
+/// Logs what a step done to the whole list did, and how long it took
+fn log_list_step(step: PipelineStep, words_before: usize, words_after: usize, elapsed: Duration) {
+    info!(
+        "{}: {} words left ({} removed)",
+        step.name(),
+        words_after,
+        words_before - words_after
+    );
+    debug!("{} took {:.2?}", step.name(), elapsed);
+}
+
+/// Passes progress through a slow task to the request's callback, if it has one
+fn report_progress(req: &TidyRequest, task: &str, done: usize, total: usize) {
+    if let Some(ref progress) = req.progress {
+        progress.report(task, done, total);
+    }
+}
+
+/// Whether the request's interrupted flag has been set, like by a Ctrl-C
+fn is_interrupted(req: &TidyRequest) -> bool {
+    match req.interrupted {
+        Some(ref interrupted) => interrupted.load(Ordering::SeqCst),
+        None => false,
+    }
+}
+
+/// Whether a step of the pipeline does anything: either it was requested,
+/// or the request has custom steps to run right after it
+fn runs(step: PipelineStep, req: &TidyRequest) -> bool {
+    is_requested(step, req) || custom_steps_after(step, req).next().is_some()
+}
+
+/// The request's custom steps to run right after the given step, in order
+fn custom_steps_after(step: PipelineStep, req: &TidyRequest) -> impl Iterator<Item = &CustomStep> {
+    req.custom_steps
+        .iter()
+        .filter(move |(after, _custom)| *after == step)
+        .map(|(_after, custom)| custom)
+}
+
+/// The order to run the steps of the pipeline in: either the custom order
+/// from the request or the default one.
+fn pipeline_steps(req: &TidyRequest) -> &[PipelineStep] {
+    match req.pipeline {
+        Some(ref pipeline) => pipeline,
+        None => &DEFAULT_PIPELINE,
+    }
+}
+
+/// Whether the user asked for the given step of the pipeline to be done.
+/// Steps that aren't requested are skipped.
+pub fn is_requested(step: PipelineStep, req: &TidyRequest) -> bool {
+    match step {
+        PipelineStep::Field => req.field_number.is_some(),
+        PipelineStep::DeleteBefore => req.should_delete_before_first_delimiter.is_some(),
+        PipelineStep::DeleteAfter => req.should_delete_after_first_delimiter.is_some(),
+        PipelineStep::DeleteIntegers => req.should_delete_integers,
+        PipelineStep::DeleteNonalphanumeric => req.should_delete_nonalphanumeric,
+        PipelineStep::Lowercase => req.to_lowercase,
+        PipelineStep::StraightenQuotes => req.should_straighten_quotes,
+        PipelineStep::RemoveNonascii => req.should_remove_nonascii,
+        PipelineStep::RemoveMixedScript => req.should_remove_mixed_script,
+        PipelineStep::RemoveMultiword => req.should_remove_multiword,
+        PipelineStep::JoinMultiword => req.join_multiword_separator.is_some(),
+        PipelineStep::DeleteApostrophes => req.should_delete_apostrophes,
+        PipelineStep::NormalizeHyphens => req.should_normalize_hyphens,
+        PipelineStep::RemoveConfusableChars => req.confusable_chars.is_some(),
+        PipelineStep::RemoveDoubledLetters => req.should_remove_doubled_letters,
+        PipelineStep::RemoveConsonantClusters => req.consonant_clusters.is_some(),
+        PipelineStep::RemoveGibberish => req.gibberish_threshold.is_some(),
+        PipelineStep::RemoveNonalphanumeric => req.should_remove_nonalphanumeric,
+        PipelineStep::RemoveNonalphabetic => req.should_remove_nonalphabetic,
+        PipelineStep::RemoveNonLatinAlphabetic => req.should_remove_non_latin_alphabetic,
+        PipelineStep::RemoveIntegers => req.should_remove_integers,
+        PipelineStep::Reject => req.reject_list.is_some(),
+        PipelineStep::Approve => req.approved_list.is_some(),
+        PipelineStep::MinimumLength => req.minimum_length.is_some(),
+        PipelineStep::MaximumLength => req.maximum_length.is_some(),
+        PipelineStep::Homophones => req.homophones_list.is_some(),
+        PipelineStep::RemoveAnagrams => req.should_remove_anagrams,
+        PipelineStep::RemoveLeetVariants => req.leet_substitutions.is_some(),
+        PipelineStep::RemoveInflections => req.should_remove_inflections,
+        PipelineStep::Lemmatize => req.lemmatize_language.is_some(),
+        PipelineStep::SharedPrefix => req.maximum_shared_prefix_length.is_some(),
+        PipelineStep::TruncateToUnique => req.truncate_to_unique_length.is_some(),
+        PipelineStep::EditDistance => req.minimum_edit_distance.is_some(),
+        PipelineStep::RemoveSuffixWords => req.should_remove_suffix_words,
+        PipelineStep::RemovePrefixWords => req.should_remove_prefix_words,
+        PipelineStep::RemoveInfixWords => req.should_remove_infix_words,
+        PipelineStep::RemoveCompoundCollisions => req.should_remove_compound_collisions,
+        PipelineStep::BestPrune => req.should_best_prune,
+        PipelineStep::SchlinkertPrune => req.should_schlinkert_prune,
+        // Duplicates are always removed
+        PipelineStep::Dedup => true,
+        PipelineStep::Capitalize => req.capitalization != Capitalization::None,
+        PipelineStep::FirstLetters => req.max_per_first_letter.is_some(),
+        PipelineStep::LengthQuota => !req.length_quotas.is_empty(),
+        PipelineStep::AlwaysInclude => req.always_include.is_some(),
+        PipelineStep::UpdateFrom => req.previous_list.is_some(),
+        PipelineStep::PrintFirst => req.print_first.is_some(),
+        PipelineStep::PrintRand => req.print_rand.is_some(),
+        PipelineStep::CutToPower => req.cut_to_power.is_some(),
+        PipelineStep::Sort => req.sort_by != SortOrder::Unsorted || req.reverse_sort,
+    }
+}
+
+/// Performs all of the word modifications and removals the user requested on a
+/// single word (or line, if it has metadata). Returns `None` if the word
+/// should be removed from the list, or an error if the request is invalid.
+///
+/// These operations don't depend on the other words of the list, so, unlike
+/// those in `tidy_whole_list`, their results can be re-used.
+///
+/// Any metadata the request asks to ignore is left as it was, and only the
+/// word is modified.
+/// ```
+/// use tidy_core::{tidy_word, TidyRequest};
+/// let req = TidyRequest {
+///     ignore_after_delimiter: Some(','),
+///     to_lowercase: true,
+///     should_delete_integers: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(tidy_word("Apple2,300", &req), Ok(Some("apple,300".to_string())));
+/// ```
+pub fn tidy_word(word: &str, req: &TidyRequest) -> Result<Option<String>, TidyError> {
+    Ok(tidy_word_explained(word, req)?
+        .ok()
+        .map(|(word, metadata)| attach_metadata(word, metadata.as_ref())))
+}
+
+/// Finds which of the given source lists each word of a tidied list came
+/// from, by tidying each line of each source the way `req` asks. Returns
+/// the index of the first source with the word, or `None` if no source has
+/// it, like a word that was always included.
+/// ```
+/// use tidy_core::{find_word_sources, TidyRequest};
+/// let req = TidyRequest {
+///     to_lowercase: true,
+///     ..Default::default()
+/// };
+/// let sources = vec![vec!["Apple".to_string()], vec!["apple".to_string(), "pear".to_string()]];
+/// let list = vec!["apple".to_string(), "pear".to_string(), "plum".to_string()];
+/// assert_eq!(find_word_sources(&list, &sources, &req), Ok(vec![Some(0), Some(1), None]));
+/// ```
+pub fn find_word_sources(
+    list: &[String],
+    sources: &[Vec<String>],
+    req: &TidyRequest,
+) -> Result<Vec<Option<usize>>, TidyError> {
+    let mut word_sources: HashMap<String, usize> = HashMap::new();
+    // Lines are matched by their words, since a word's metadata may have
+    // come from a line of another source
+    for (source, lines) in sources.iter().enumerate() {
+        for line in lines {
+            if let Some(word) = listed_word(line, req)? {
+                word_sources.entry(word).or_insert(source);
+            }
+        }
+    }
+    list.iter()
+        .map(|line| Ok(word_sources.get(split_line(line, req)?.0.trim()).copied()))
+        .collect()
+}
+
+/// The word a line becomes on the finished list, unless it's removed: the
+/// line tidied by `tidy_word_explained`, then capitalized as the request
+/// asks. Used to match words of a finished list to the lines they came from.
+pub(crate) fn listed_word(line: &str, req: &TidyRequest) -> Result<Option<String>, TidyError> {
+    Ok(tidy_word_explained(line, req)?
+        .ok()
+        .map(|(word, _metadata)| capitalize(&word, req.capitalization)))
+}
+
+/// Like `tidy_word`, but returns the word and its metadata separately. If
+/// the word should be removed, returns the pipeline step that removed it, or
+/// `None` if the line was blank (or just whitespace).
+fn tidy_word_explained(
+    word: &str,
+    req: &TidyRequest,
+) -> Result<Result<WordAndMetadata, Option<PipelineStep>>, TidyError> {
+    tidy_word_timed(word, req, None)
+}
+
+/// Like `tidy_word_explained`, but if given `timings`, adds the time each
+/// word step took (along with any custom steps after it) to the step's total
+fn tidy_word_timed(
+    word: &str,
+    req: &TidyRequest,
+    mut timings: Option<&mut HashMap<PipelineStep, Duration>>,
+) -> Result<Result<WordAndMetadata, Option<PipelineStep>>, TidyError> {
+    // METADATA-IGNORING WORD REMOVALS
+    // If user chose to ignore metadata, split the line into the word and the metadata
+    // based on given delimiter. Note that metadata may come before or after the word.
+    // We'll then do modifications and removals on the "word", ignoring metadata.
+    // The metadata is re-attached to the word once the whole list is tidied.
+    let (mut new_word, metadata) = split_line(word, req)?;
+
+    // Trim new word, then normalize unicode if user gave an
+    // nromalization form to use
+    new_word = match &req.normalization_form {
+        Some(nf) => normalize_unicode(new_word.trim(), nf)
+            .map_err(|_e| TidyError::InvalidNormalizationForm(nf.to_string()))?,
+        None => {
+            // still need to trim
+            new_word.trim().to_string()
+        }
+    };
+    if new_word.is_empty() {
+        return Ok(Err(None));
+    }
+
+    // WORD MODIFICATIONS AND REMOVALS
+    // By default, Tidy performs all word modifications BEFORE it runs word
+    // removals, but users can change this order with a custom pipeline.
+    for step in pipeline_steps(req)
+        .iter()
+        .filter(|step| step.is_word_step())
+    {
+        if !runs(*step, req) {
+            continue;
+        }
+        let started = timings.is_some().then(Instant::now);
+        // Tidy's own word steps run through the same traits as custom ones
+        let builtin = BuiltinWordStep::new(*step, req);
+        let mut keep = if step.is_word_modification() {
+            new_word = builtin.transform(&new_word);
+            true
+        } else {
+            builtin.keep(&new_word)
+        };
+        if keep {
+            for custom in custom_steps_after(*step, req) {
+                match custom
+                    .apply_with_metadata(&new_word, metadata.as_ref().map(|m| m.text.as_str()))
+                {
+                    Some(word) => new_word = word,
+                    None => {
+                        keep = false;
+                        break;
+                    }
+                }
+            }
+        }
+        new_word = new_word.trim().to_string();
+        if let (Some(timings), Some(started)) = (timings.as_deref_mut(), started) {
+            *timings.entry(*step).or_default() += started.elapsed();
+        }
+        // A modification could have left nothing of the word
+        if !keep || new_word.is_empty() {
+            return Ok(Err(Some(*step)));
+        }
+    }
+
+    // trim whitespace
+    new_word = new_word.trim().to_string();
+    // The trim call could have made new_word empty
+    // so need to check again
+    if new_word.is_empty() {
+        Ok(Err(None))
+    } else {
+        Ok(Ok((new_word, metadata)))
+    }
+}
+
+/// Drops the inputted line each line of a tidied list was made from
+fn without_origins(list: Vec<LineAndOrigin>) -> Vec<String> {
+    list.into_iter().map(|(line, _origin)| line).collect()
+}
+
+/// Re-attaches a word's metadata, if it has any
+fn attach_metadata(word: String, metadata: Option<&Metadata>) -> String {
+    match metadata {
+        Some(metadata) => rejoin_metadata(&word, metadata).trim().to_string(),
+        None => word,
+    }
+}
+
+/// Performs the operations that depend on the list as a whole, like removing
+/// prefix words, on a list of words that have each been through `tidy_word`.
+/// Then does any requested truncation and sorting, and re-attaches each
+/// word's metadata. Records how many words are left after each step, and any
+/// best prune comparison, in `report`.
+///
+/// Each word is given along with the inputted line it was made from, and
+/// each line of the finished list comes with the inputted line its word was
+/// made from, if any.
+fn tidy_whole_list<'a>(
+    words: Vec<(WordAndMetadata, &'a str)>,
+    req: &TidyRequest,
+    report: &mut TidyReport,
+) -> Result<Vec<LineAndOrigin<'a>>, TidyError> {
+    // These steps only ever see the words. Each word keeps the metadata of
+    // the line it came from that `dedup_keep` prefers (by default, the
+    // first), just as de-duplicating keeps one of a word's lines. It's also
+    // said to be made from that line.
+    let mut metadata_of_words: HashMap<String, Metadata> = HashMap::new();
+    let mut origin_of_words: HashMap<String, &'a str> = HashMap::new();
+    let mut tidied_list: Vec<String> = words
+        .into_iter()
+        .map(|((word, metadata), origin)| {
+            if let Some(metadata) = metadata {
+                match metadata_of_words.entry(word.clone()) {
+                    Entry::Vacant(entry) => {
+                        entry.insert(metadata);
+                        origin_of_words.insert(word.clone(), origin);
+                    }
+                    Entry::Occupied(mut entry) => {
+                        if prefers_duplicate(
+                            req.dedup_keep,
+                            (&word, Some(entry.get())),
+                            (&word, Some(&metadata)),
+                        ) {
+                            entry.insert(metadata);
+                            origin_of_words.insert(word.clone(), origin);
+                        }
+                    }
+                }
+            }
+            origin_of_words.entry(word.clone()).or_insert(origin);
+            word
+        })
+        .collect();
+    for step in pipeline_steps(req) {
+        if step.is_word_step() || !runs(*step, req) {
+            continue;
+        }
+        // Leave the rest of the steps undone, so the report only counts
+        // steps that finished
+        if is_interrupted(req) {
+            break;
+        }
+        let (words_before, started) = (tidied_list.len(), Instant::now());
+        tidied_list = match step {
+            // Only here for the custom steps that follow it
+            _ if !is_requested(*step, req) => tidied_list,
+            PipelineStep::Homophones => {
+                remove_homophones(&tidied_list, req.homophones_list.as_ref().unwrap())
+            }
+            PipelineStep::RemoveAnagrams => {
+                remove_anagrams(&tidied_list, req.anagram_edit_distance)
+            }
+            PipelineStep::RemoveLeetVariants => remove_leet_variants(
+                &tidied_list,
+                req.reject_list.as_ref(),
+                req.leet_substitutions.as_ref().unwrap(),
+            ),
+            PipelineStep::RemoveInflections => remove_inflections(&tidied_list),
+            PipelineStep::Lemmatize => {
+                collapse_by_stem(&tidied_list, req.lemmatize_language.as_ref().unwrap())?
+            }
+            PipelineStep::SharedPrefix => guarantee_maximum_prefix_length(
+                &tidied_list,
+                req.maximum_shared_prefix_length.unwrap(),
+            ),
+            PipelineStep::TruncateToUnique => {
+                let length = req.truncate_to_unique_length.unwrap();
+                // Like truncate_to_unique_prefix, but the metadata and
+                // origin follow each word to its truncated form
+                let kept = guarantee_maximum_prefix_length(&tidied_list, length);
+                metadata_of_words = kept
+                    .iter()
+                    .filter_map(|word| {
+                        let metadata = metadata_of_words.get(word)?;
+                        Some((get_prefix(word, length), metadata.clone()))
+                    })
+                    .collect();
+                origin_of_words = kept
+                    .iter()
+                    .filter_map(|word| {
+                        Some((get_prefix(word, length), *origin_of_words.get(word)?))
+                    })
+                    .collect();
+                kept.iter().map(|word| get_prefix(word, length)).collect()
+            }
+            PipelineStep::EditDistance => enforce_minimum_edit_distance_with_progress(
+                &tidied_list,
+                req.minimum_edit_distance.unwrap(),
+                &|done, total| report_progress(req, "Enforcing minimum edit distance", done, total),
+            ),
+            PipelineStep::RemoveSuffixWords => {
+                remove_suffix_words(&dedup_without_sorting(&tidied_list))
+            }
+            PipelineStep::RemovePrefixWords => {
+                remove_prefix_words(&dedup_without_sorting(&tidied_list))
+            }
+            PipelineStep::RemoveInfixWords => {
+                remove_infix_words(&dedup_without_sorting(&tidied_list))
+            }
+            PipelineStep::RemoveCompoundCollisions => {
+                remove_compound_collisions(&dedup_without_sorting(&tidied_list))
+            }
+            PipelineStep::BestPrune => {
+                let (method, new_list, comparison) = best_prune(
+                    &dedup_without_sorting(&tidied_list),
+                    req.schlinkert_strategy,
+                );
+                info!("Best prune kept the list from: {}", method);
+                report.best_prune = Some((method, comparison));
+                new_list
+            }
+            PipelineStep::SchlinkertPrune => {
+                let list = dedup_without_sorting(&tidied_list);
+                if let Some(limit) = req.ambiguity_examples {
+                    report.ambiguities = find_ambiguous_concatenations(&list, limit);
+                }
+                let timed_out = || match req.schlinkert_timeout {
+                    Some(timeout) => started.elapsed() > timeout,
+                    None => false,
+                };
+                match schlinkert_prune_until(
+                    &list,
+                    req.schlinkert_strategy,
+                    &|done, total| report_progress(req, "Schlinkert pruning", done, total),
+                    &|| timed_out() || is_interrupted(req),
+                ) {
+                    Some(new_list) => new_list,
+                    None if is_interrupted(req) => break,
+                    None => {
+                        // Removing prefix words also makes the list
+                        // uniquely decodable, just with more words removed
+                        warn!(
+                            "Schlinkert prune took longer than {:.2?}; removing prefix words instead",
+                            req.schlinkert_timeout.unwrap()
+                        );
+                        report.schlinkert_timed_out = true;
+                        remove_prefix_words(&list)
+                    }
+                }
+            }
+            // Remove duplicate words
+            PipelineStep::Dedup => match req.dedup_ignore_case {
+                Some(keep) => dedup_ignoring_case_preferring(&tidied_list, keep, &|kept, later| {
+                    prefers_duplicate(
+                        req.dedup_keep,
+                        (kept, metadata_of_words.get(kept)),
+                        (later, metadata_of_words.get(later)),
+                    )
+                }),
+                None => dedup_without_sorting(&tidied_list),
+            },
+            // Capitalizing can make two words the same, like "apple" and
+            // "Apple", so they're de-duplicated again at the end
+            PipelineStep::Capitalize => {
+                let capitalized: Vec<String> = tidied_list
+                    .iter()
+                    .map(|word| capitalize(word, req.capitalization))
+                    .collect();
+                // Words made the same keep the metadata and origin of the
+                // first of them
+                let mut capitalized_metadata: HashMap<String, Metadata> = HashMap::new();
+                let mut capitalized_origins: HashMap<String, &str> = HashMap::new();
+                for (word, capitalized_word) in tidied_list.iter().zip(&capitalized) {
+                    if let Some(metadata) = metadata_of_words.get(word) {
+                        capitalized_metadata
+                            .entry(capitalized_word.to_string())
+                            .or_insert_with(|| metadata.clone());
+                    }
+                    if let Some(origin) = origin_of_words.get(word) {
+                        capitalized_origins
+                            .entry(capitalized_word.to_string())
+                            .or_insert(*origin);
+                    }
+                }
+                metadata_of_words = capitalized_metadata;
+                origin_of_words = capitalized_origins;
+                capitalized
+            }
+            PipelineStep::FirstLetters => {
+                cap_words_per_first_letter(&tidied_list, req.max_per_first_letter.unwrap())
+            }
+            PipelineStep::LengthQuota => enforce_length_quotas(
+                &tidied_list,
+                &req.length_quotas,
+                req.quota_priority,
+                &mut make_rng(req.rand_seed),
+            ),
+            // Words to always include go at the front, so truncations keep them
+            PipelineStep::AlwaysInclude => {
+                let mut always_include = always_included_words(req);
+                always_include.extend(tidied_list);
+                dedup_without_sorting(&always_include)
+            }
+            // User can choose to print a limited number of words from nearly finished (but still
+            // unsorted) list.
+            // Can do so from the beginning of the nearly finished list...
+            PipelineStep::PrintFirst => cut_list(
+                tidied_list,
+                req.print_first.unwrap(),
+                CutStrategy::First,
+                req,
+            )?,
+            // And/or can do so randomly
+            PipelineStep::PrintRand => cut_list(
+                tidied_list,
+                req.print_rand.unwrap(),
+                CutStrategy::Random,
+                req,
+            )?,
+            // Or to however many words make a clean power, like 6^5
+            PipelineStep::CutToPower => {
+                let length = largest_power_at_most(req.cut_to_power.unwrap(), tidied_list.len());
+                cut_list(tidied_list, length, req.cut_strategy, req)?
+            }
+            PipelineStep::Sort => sort_list(tidied_list, &metadata_of_words, req)?,
+            PipelineStep::UpdateFrom => {
+                let (updated_list, changes) =
+                    keep_previous_positions(&tidied_list, &previous_words(req)?);
+                report.previous_list_changes = changes;
+                updated_list
+            }
+            _ => unreachable!("{} is a word step", step.name()),
+        };
+        for custom in custom_steps_after(*step, req) {
+            tidied_list = custom.apply_to_list(&tidied_list, &metadata_of_words);
+        }
+        let elapsed = started.elapsed();
+        log_list_step(*step, words_before, tidied_list.len(), elapsed);
+        report.step_counts.push((step.name(), tidied_list.len()));
+        report.step_timings.push((step.name(), elapsed));
+    }
+    // And remove duplicates one more time, in case a custom pipeline did
+    // any steps after de-duplicating
+    let tidied_list = dedup_without_sorting(&tidied_list);
+    if req.always_include.is_some() {
+        report.always_include_conflicts = find_always_include_conflicts(&tidied_list, req);
+    }
+    Ok(tidied_list
+        .into_iter()
+        .map(|word| {
+            let origin = origin_of_words.get(&word).copied();
+            let metadata = metadata_of_words.get(&word);
+            (attach_metadata(word, metadata), origin)
+        })
+        .collect())
+}
+
+/// Cuts the list down to `length` words, keeping either a random selection
+/// (picked by the request's `rand_strategy`) or the first words. Words to
+/// always include, then words on a previous version of the list, are kept
+/// if they can be.
+fn cut_list(
+    mut list: Vec<String>,
+    length: usize,
+    strategy: CutStrategy,
+    req: &TidyRequest,
+) -> Result<Vec<String>, TidyError> {
+    if strategy == CutStrategy::Random {
+        if req.balance_first_letters {
+            // Balancing needs every word, in random order, to choose from
+            list.shuffle(&mut make_rng(req.rand_seed));
+        } else {
+            // Words to keep aren't left to chance, so the random selection
+            // is only of the other words
+            let keep_first: HashSet<String> = words_to_keep_first(req)?.into_iter().collect();
+            let (mut kept, others): (Vec<String>, Vec<String>) =
+                list.into_iter().partition(|word| keep_first.contains(word));
+            let room = length.saturating_sub(kept.len());
+            kept.extend(sample(
+                &others,
+                room,
+                req.rand_strategy,
+                &mut make_rng(req.rand_seed),
+            ));
+            list = kept;
+        }
+    }
+    if req.balance_first_letters {
+        list = interleave_by_first_letter(&list);
+    }
+    list = move_to_front(list, &words_to_keep_first(req)?);
+    list.truncate(length);
+    Ok(list)
+}
+
+/// The request's words to always include, trimmed, without blank lines
+fn always_included_words(req: &TidyRequest) -> Vec<String> {
+    let words: Vec<String> = req
+        .always_include
+        .iter()
+        .flatten()
+        .map(|word| word.trim().to_string())
+        .filter(|word| !word.is_empty())
+        .collect();
+    dedup_without_sorting(&words)
+}
+
+/// Words that truncating the list should keep, if it can: the words to
+/// always include, then the words of any previous version of the list
+fn words_to_keep_first(req: &TidyRequest) -> Result<Vec<String>, TidyError> {
+    let mut words = always_included_words(req);
+    words.extend(previous_words(req)?);
+    Ok(words)
+}
+
+/// The words of any previous version of the list, without their metadata
+fn previous_words(req: &TidyRequest) -> Result<Vec<String>, TidyError> {
+    req.previous_list
+        .iter()
+        .flatten()
+        .map(|line| Ok(split_line(line, req)?.0.trim().to_string()))
+        .collect()
+}
+
+/// Describes the ways the request's always-included words, now on the
+/// finished list, break other constraints the request asked for, like
+/// having no prefix words or a minimum edit distance.
+fn find_always_include_conflicts(list: &[String], req: &TidyRequest) -> Vec<String> {
+    let mut conflicts = vec![];
+    let always_included = always_included_words(req);
+    for (i, word) in always_included.iter().enumerate() {
+        // Pairs of always-included words are only checked once
+        let others = list
+            .iter()
+            .filter(|other| *other != word && !always_included[..i].contains(other));
+        for other in others {
+            if req.should_remove_prefix_words {
+                if other.starts_with(word.as_str()) {
+                    conflicts.push(format!("{} is a prefix of {}", word, other));
+                } else if word.starts_with(other.as_str()) {
+                    conflicts.push(format!("{} has the prefix {}", word, other));
+                }
+            }
+            if req.should_remove_suffix_words {
+                if other.ends_with(word.as_str()) {
+                    conflicts.push(format!("{} is a suffix of {}", word, other));
+                } else if word.ends_with(other.as_str()) {
+                    conflicts.push(format!("{} has the suffix {}", word, other));
+                }
+            }
+            if req.should_remove_infix_words {
+                if other.contains(word.as_str()) {
+                    conflicts.push(format!("{} is inside {}", word, other));
+                } else if word.contains(other.as_str()) {
+                    conflicts.push(format!("{} has {} inside it", word, other));
+                }
+            }
+            if let Some(length) = req.maximum_shared_prefix_length {
+                if get_prefix(word, length) == get_prefix(other, length) {
+                    conflicts.push(format!(
+                        "{} and {} share their first {} characters",
+                        word, other, length
+                    ));
+                }
+            }
+            if let Some(minimum) = req.minimum_edit_distance {
+                if find_edit_distance(word, other) < minimum as u32 {
+                    conflicts.push(format!(
+                        "{} and {} are closer than the minimum edit distance of {}",
+                        word, other, minimum
+                    ));
+                }
+            }
+        }
+    }
+    if (req.should_schlinkert_prune || req.should_best_prune) && !check_decodability(list) {
+        conflicts
+            .push("the list with the always-included words is not uniquely decodable".to_string());
+    }
+    conflicts
+}
+
+/// Splits a line into its word and any metadata the request asks to ignore
+fn split_line(line: &str, req: &TidyRequest) -> Result<(String, Option<Metadata>), TidyError> {
+    match (req.ignore_after_delimiter, req.ignore_before_delimiter) {
+        (Some(delimiter), None) => {
+            // Parse delimiter. Currently this converts 's' to ' '
+            // and 't' to '\t'.
+            let delimiter = parse_delimiter(delimiter).unwrap();
+            if !req.keep_metadata {
+                return Ok(split_off_metadata(line, delimiter, MetadataPosition::End));
+            }
+            // Everything after the first delimiter, including any more
+            // columns of metadata
+            match line.split_once(delimiter) {
+                Some((word, text)) => Ok((
+                    word.to_string(),
+                    Some(Metadata {
+                        text: text.to_string(),
+                        delimiter,
+                        position: MetadataPosition::End,
+                    }),
+                )),
+                None => Ok((line.to_string(), None)),
+            }
+        }
+        (None, Some(delimiter)) => {
+            let delimiter = parse_delimiter(delimiter).unwrap();
+            Ok(split_off_metadata(line, delimiter, MetadataPosition::Start))
+        }
+        (Some(ref _delimiter1), Some(ref _delimiter2)) => {
+            // This situation should be caught by validate_tidy_request, so
+            // this is really just in case.
+            Err(TidyError::IgnoringMetadataOnBothSides)
+        }
+        (None, None) => Ok((line.to_string(), None)),
+    }
+}
+
+/// Whether to keep a later line of duplicate words, given as the word and its
+/// metadata, rather than the line kept so far
+fn prefers_duplicate(
+    keep: DedupKeep,
+    kept: (&str, Option<&Metadata>),
+    later: (&str, Option<&Metadata>),
+) -> bool {
+    let line_length = |(word, metadata): (&str, Option<&Metadata>)| {
+        count_characters(word) + metadata.map_or(0, |metadata| count_characters(&metadata.text))
+    };
+    match keep {
+        DedupKeep::First => false,
+        DedupKeep::Last => true,
+        DedupKeep::HighestFrequency => frequency(later.1) > frequency(kept.1),
+        DedupKeep::Shortest => line_length(later) < line_length(kept),
+    }
+}
+
+/// The frequency count at the start of a word's metadata, or negative
+/// infinity if it doesn't have one
+fn frequency(metadata: Option<&Metadata>) -> f64 {
+    metadata
+        .and_then(|metadata| metadata.text.split(metadata.delimiter).next())
+        .and_then(|text| text.trim().parse::<f64>().ok())
+        .filter(|frequency| frequency.is_finite())
+        .unwrap_or(f64::NEG_INFINITY)
+}
+
+/// Sorts the finished list of words in the requested order, then reverses it
+/// if requested
+fn sort_list(
+    list: Vec<String>,
+    metadata_of_words: &HashMap<String, Metadata>,
+    req: &TidyRequest,
+) -> Result<Vec<String>, TidyError> {
+    // We used to just be content to run tidied_list.sort() here,
+    // but that doesn't support non-English languages and
+    // accented characters very well.
+    let locale = || -> Result<Locale, TidyError> {
+        req.locale
+            .parse()
+            .map_err(|_e| TidyError::InvalidLocale(req.locale.to_string()))
+    };
+    let mut sorted = match req.sort_by {
+        SortOrder::Unsorted => list,
+        SortOrder::Alphabetical => sort_carefully(list, locale()?)?,
+        SortOrder::Length => sort_by_length(list),
+        // Sorting by length keeps the alphabetical order of words of the
+        // same length
+        SortOrder::LengthThenAlphabetical => sort_by_length(sort_carefully(list, locale()?)?),
+        SortOrder::Frequency => {
+            let mut by_frequency: Vec<(f64, String)> = list
+                .into_iter()
+                .map(|word| {
+                    // The frequency is the first column of the metadata
+                    (frequency(metadata_of_words.get(&word)), word)
+                })
+                .collect();
+            by_frequency.sort_by(|a, b| b.0.total_cmp(&a.0));
+            by_frequency.into_iter().map(|(_, word)| word).collect()
+        }
+        SortOrder::Random => {
+            let mut list = list;
+            list.shuffle(&mut make_rng(req.rand_seed));
+            list
+        }
+    };
+    if req.reverse_sort {
+        sorted.reverse();
+    }
+    Ok(sorted)
+}
+
+fn sort_by_length(mut list: Vec<String>) -> Vec<String> {
+    list.sort_by_cached_key(|word| count_characters(word));
+    list
+}
+
+use unicode_segmentation::UnicodeSegmentation;
+/// When counting characters of a word, we want to count all accented character as 1,
+/// regardless of the Unicode, to better approximate how humans would count the number
+/// of characters in a word.
+/// An alternate approach would be to convert each character to NFC before counting `word.nfc().count()`
+/// but I don't think this handles emoji as well as grapheme cluster counting.
+pub fn count_characters(word: &str) -> usize {
+    word.graphemes(true).count()
+}
+
+/// Little helper function that allows users to write out whitespace
+/// delimiters "s" and "t", rather than having to enter the whitespace
+/// characters literally.
+pub fn parse_delimiter(delimiter: char) -> Option<char> {
+    if delimiter == 's' {
+        Some(' ')
+    } else if delimiter == 't' {
+        Some('\t')
+    } else {
+        Some(delimiter)
+    }
+}
+
+/// Tidies the list, taking only as many words from the start of the inputted
+/// list as needed to make a list of exactly `length_to_whittle_to` words. Useful
+/// when the inputted list is sorted by desirability (e.g. word frequency).
+///
+/// Word modifications and removals are done once for every word. Then a binary
+/// search, starting from `starting_point`, finds how many words to take, only
+/// re-running the operations that depend on the whole list (see
+/// `tidy_whole_list`) for each guess. Returns the list and a report on it,
+/// including each guess.
+///
+/// Errors if no number of words to take results in a list of exactly the
+/// requested length.
+pub fn whittle_list(
+    req: &TidyRequest,
+    length_to_whittle_to: usize,
+    starting_point: usize,
+) -> Result<(Vec<String>, TidyReport), TidyError> {
+    validate_tidy_request(req)?;
+    let started = Instant::now();
+    let mut word_step_timings = HashMap::new();
+    let tidied_words: Vec<Result<WordAndMetadata, Option<PipelineStep>>> = req
+        .list
+        .iter()
+        .map(|word| tidy_word_timed(word, req, Some(&mut word_step_timings)))
+        .collect::<Result<_, TidyError>>()?;
+    let word_steps_elapsed = started.elapsed();
+    debug!(
+        "Word modifications and removals took {:.2?}",
+        word_steps_elapsed
+    );
+    let mut results: HashMap<usize, (Vec<String>, TidyReport)> = HashMap::new();
+    // Pick up where an earlier whittle left off, if asked to
+    let resume_from = match req.whittle_resume_from {
+        Some(ref checkpoint) if checkpoint.fits(length_to_whittle_to, req.list.len()) => {
+            info!(
+                "Resuming whittle, taking between {} and {} words",
+                checkpoint.low, checkpoint.high
+            );
+            Some(checkpoint.clone())
+        }
+        Some(_) => {
+            warn!("Whittle checkpoint is from a different list or length; starting over");
+            None
+        }
+        None => None,
+    };
+    let mut whittle_guesses = vec![];
+    let mut guesses_elapsed = Duration::ZERO;
+    // A binary search takes about log2(n) guesses, plus a final check
+    let expected_guesses = (usize::BITS - req.list.len().leading_zeros()) as usize + 1;
+    let mut tidy_first = |amount_to_take: usize| -> Result<usize, TidyError> {
+        let (list, _report) = match results.entry(amount_to_take) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let started = Instant::now();
+                let words = with_origins(&tidied_words[..amount_to_take], &req.list);
+                let mut report = TidyReport::default();
+                let list = without_origins(tidy_whole_list(words, req, &mut report)?);
+                guesses_elapsed += started.elapsed();
+                entry.insert((list, report))
+            }
+        };
+        info!(
+            "Taking {} words, whittled list to {}.",
+            amount_to_take,
+            list.len()
+        );
+        whittle_guesses.push((amount_to_take, list.len()));
+        report_progress(req, "Whittling", whittle_guesses.len(), expected_guesses);
+        Ok(list.len())
+    };
+
+    // Find the fewest words to take that make a list at least as long as
+    // requested
+    let (mut low, mut high) = match resume_from {
+        Some(ref checkpoint) => (checkpoint.low, checkpoint.high),
+        None => (0, req.list.len()),
+    };
+    // Each guess must be in the range [low, high)
+    let mut guess = match resume_from {
+        Some(_) => low + (high - low) / 2,
+        None => starting_point.min(high.saturating_sub(1)),
+    };
+    while low < high {
+        let this_list_length = tidy_first(guess)?;
+        // A list cut short by an interruption says nothing about the guess
+        if is_interrupted(req) {
+            break;
+        }
+        if this_list_length < length_to_whittle_to {
+            low = guess + 1;
+        } else {
+            high = guess;
+        }
+        if let Some(ref checkpoint) = req.whittle_checkpoint {
+            checkpoint.save(&WhittleCheckpoint {
+                length_to_whittle_to,
+                list_length: req.list.len(),
+                low,
+                high,
+            });
+        }
+        guess = low + (high - low) / 2;
+    }
+    let this_list_length = if is_interrupted(req) {
+        0
+    } else {
+        tidy_first(low)?
+    };
+    report_progress(req, "Whittling", expected_guesses, expected_guesses);
+    if is_interrupted(req) {
+        let mut report = TidyReport {
+            step_counts: vec![("input", count_nonblank_lines(&req.list))],
+            step_timings: vec![(WORD_STEPS_STAGE, word_steps_elapsed)],
+            whittle_guesses,
+            ..Default::default()
+        };
+        count_word_steps(&tidied_words, req, &mut report.step_counts);
+        time_word_steps(&word_step_timings, req, &mut report.step_timings);
+        return Err(TidyError::Interrupted(Box::new(report)));
+    }
+    let finishes = req.whittle_finish.is_some() && this_list_length > length_to_whittle_to;
+    if this_list_length == length_to_whittle_to || finishes {
+        let (list, whole_list_report) = if finishes {
+            finish_whittle(&tidied_words[..low], length_to_whittle_to, req)?
+        } else {
+            results.remove(&low).unwrap()
+        };
+        let taken_lines: Vec<&str> = req.list[..low].iter().map(String::as_str).collect();
+        // The steps of the kept guess are timed one by one below
+        let kept_elapsed: Duration = if finishes {
+            Duration::ZERO
+        } else {
+            whole_list_report
+                .step_timings
+                .iter()
+                .map(|(_stage, elapsed)| *elapsed)
+                .sum()
+        };
+        let mut report = TidyReport {
+            step_counts: vec![
+                ("input", count_nonblank_lines(&req.list)),
+                ("whittle", count_nonblank_lines(&taken_lines)),
+            ],
+            step_timings: vec![
+                (WORD_STEPS_STAGE, word_steps_elapsed),
+                ("whittle", guesses_elapsed.saturating_sub(kept_elapsed)),
+            ],
+            best_prune: whole_list_report.best_prune,
+            lines_without_metadata: find_lines_without_metadata(&taken_lines, req),
+            whittle_guesses,
+            always_include_conflicts: whole_list_report.always_include_conflicts,
+            previous_list_changes: whole_list_report.previous_list_changes,
+            schlinkert_timed_out: whole_list_report.schlinkert_timed_out,
+            ambiguities: whole_list_report.ambiguities,
+        };
+        time_word_steps(&word_step_timings, req, &mut report.step_timings);
+        count_word_steps(&tidied_words[..low], req, &mut report.step_counts);
+        report.step_counts.extend(whole_list_report.step_counts);
+        report.step_timings.extend(whole_list_report.step_timings);
+        Ok((list, report))
+    } else if this_list_length < length_to_whittle_to {
+        Err(TidyError::WhittleListTooShort {
+            requested: length_to_whittle_to,
+            longest: this_list_length,
+        })
+    } else {
+        // The closest length on the other side, for users to pick from
+        let shorter_length = if low > 0 { tidy_first(low - 1)? } else { 0 };
+        // Taking fewer words should never make a longer list, but if a step
+        // like a Schlinkert prune does, the search may have skipped over a
+        // number of words to take that works
+        if let Some(((fewer_taken, longer), (more_taken, shorter))) =
+            find_non_monotonic_guesses(&whittle_guesses)
+        {
+            warn!(
+                "Taking {} words made a list of {} words, but taking {} words made a shorter list of {} words, so whittling may have missed a number of words to take that works",
+                fewer_taken, longer, more_taken, shorter
+            );
+        }
+        Err(TidyError::WhittleLengthUnreachable {
+            requested: length_to_whittle_to,
+            words_taken: low,
+            length: this_list_length,
+            shorter_length,
+            guesses: whittle_guesses.len(),
+        })
+    }
+}
+
+/// Makes a list of exactly `length_to_whittle_to` words from words that
+/// make a list that's too long, cutting it down just before it's sorted, like
+/// `print_first` or `print_rand` would, depending on `req.whittle_finish`
+fn finish_whittle(
+    tidied_words: &[Result<WordAndMetadata, Option<PipelineStep>>],
+    length_to_whittle_to: usize,
+    req: &TidyRequest,
+) -> Result<(Vec<String>, TidyReport), TidyError> {
+    info!(
+        "Cutting whittled list down to {} words ({})",
+        length_to_whittle_to,
+        match req.whittle_finish {
+            Some(CutStrategy::First) => "first",
+            _ => "random",
+        }
+    );
+    let finish_req = match req.whittle_finish {
+        Some(CutStrategy::First) => TidyRequest {
+            print_first: Some(length_to_whittle_to),
+            ..req.clone()
+        },
+        _ => TidyRequest {
+            print_rand: Some(length_to_whittle_to),
+            ..req.clone()
+        },
+    };
+    let words = with_origins(tidied_words, &req.list);
+    let mut report = TidyReport::default();
+    let list = without_origins(tidy_whole_list(words, &finish_req, &mut report)?);
+    Ok((list, report))
+}
+
+/// Pairs each word that made it through the word steps with the line of the
+/// list it was made from
+fn with_origins<'a>(
+    tidied_words: &[Result<WordAndMetadata, Option<PipelineStep>>],
+    lines: &'a [String],
+) -> Vec<(WordAndMetadata, &'a str)> {
+    tidied_words
+        .iter()
+        .zip(lines)
+        .filter_map(|(result, line)| Some((result.clone().ok()?, line.as_str())))
+        .collect()
+}
+
+/// Finds two whittle guesses where taking more words made a shorter list,
+/// if there are any. Returns both guesses, as the number of words taken and
+/// the length of the list made, the guess taking fewer words first.
+fn find_non_monotonic_guesses(
+    guesses: &[(usize, usize)],
+) -> Option<((usize, usize), (usize, usize))> {
+    let mut guesses = guesses.to_vec();
+    guesses.sort_unstable();
+    guesses.dedup();
+    guesses
+        .windows(2)
+        .find(|pair| pair[1].1 < pair[0].1)
+        .map(|pair| (pair[0], pair[1]))
+}
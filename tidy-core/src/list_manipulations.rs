@@ -0,0 +1,1778 @@
+use crate::count_characters;
+use crate::edit_distance::find_edit_distance;
+use crate::error::TidyError;
+use crate::sardinas_patterson_pruning::get_sardinas_patterson_final_intersection_until;
+use crate::sardinas_patterson_pruning::SchlinkertStrategy;
+use crate::uniquely_decodable::find_ambiguous_concatenation;
+use memchr::memchr;
+use std::str::FromStr;
+use unicode_normalization::UnicodeNormalization;
+
+/// Normalize the Unicode of a string
+/// See https://docs.rs/unicode-normalization/latest/unicode_normalization/trait.UnicodeNormalization.html#tymethod.nfc
+pub fn normalize_unicode(word: &str, nf: &str) -> Result<String, String> {
+    match nf.to_lowercase().as_str() {
+        "nfc" => Ok(word.nfc().collect()),
+        "nfd" => Ok(word.nfd().collect()),
+        "nfkc" => Ok(word.nfkc().collect()),
+        "nfkd" => Ok(word.nfkd().collect()),
+        _ => Err("Unknown Unicode Normalization Form received in arguments.\nPlease use one of the following normalization forms: nfc, nfd, nfkc, or nfkd.".to_string()),
+    }
+}
+
+/// Orders the finished list can be sorted in
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SortOrder {
+    /// Keep the order of the inputted list
+    #[default]
+    Unsorted,
+    /// Alphabetically, according to the request's locale (see
+    /// `sort_carefully`)
+    Alphabetical,
+    /// Shortest words first. Words of the same length keep their order.
+    Length,
+    /// Shortest words first, and alphabetically among words of the same
+    /// length
+    LengthThenAlphabetical,
+    /// Most frequent words first, according to the frequency counts after
+    /// each word. Words without a frequency count go last.
+    Frequency,
+    /// In a random order
+    Random,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(SortOrder::Unsorted),
+            "alpha" | "alphabetical" => Ok(SortOrder::Alphabetical),
+            "length" => Ok(SortOrder::Length),
+            "length-then-alpha" => Ok(SortOrder::LengthThenAlphabetical),
+            "frequency" => Ok(SortOrder::Frequency),
+            "random" => Ok(SortOrder::Random),
+            _ => Err(format!(
+                "Unknown sort order {}. Use alpha, length, length-then-alpha, frequency, random, or none.",
+                s
+            )),
+        }
+    }
+}
+
+use icu::collator::*;
+use icu::locid::Locale;
+use icu_collator::Collator;
+use icu_collator::CollatorOptions;
+/// Sort a Vector of words a bit more carefully than Rust's
+/// default .sort(), treating capitalized letters and accented letters a
+/// bit more smart.
+/// `.sorted()` words -> ["Zambia", "abbey", "eager", "enlever", "ezra", "zoo", "énigme"]
+/// sort_carefully words -> ["abbey", "eager", "énigme", "enlever", "ezra", "Zambia", "zoo"]
+///
+/// Returns an error if there are no sorting rules for the locale.
+pub fn sort_carefully(list: Vec<String>, locale: Locale) -> Result<Vec<String>, TidyError> {
+    // let given_locale: Locale = match given_locale {
+    //     Some(given_locale) => locale!(given_locale),
+    //     None => locale!("en"),
+    // };
+    // let given_locale = locale!("en");
+    let mut options_l2 = CollatorOptions::new();
+    options_l2.strength = Some(Strength::Secondary);
+    let locale_name = locale.to_string();
+    let collator_l2: Collator =
+        Collator::try_new_unstable(&icu_testdata::unstable(), &locale.into(), options_l2)
+            .map_err(|_e| TidyError::UnsortableLocale(locale_name))?;
+    let mut newly_sorted_list = list;
+    newly_sorted_list.sort_by(|a, b| collator_l2.compare(a, b));
+    Ok(newly_sorted_list)
+}
+
+/// Given a String (a word), delete all integers from the word.
+pub fn delete_integers(mut word: String) -> String {
+    word.retain(|c| !c.is_numeric());
+    word
+}
+
+/// Given a String (a word), delete all characters that are not
+/// alphanumeric
+/// ```
+/// use tidy_core::list_manipulations::delete_nonalphanumeric;
+/// assert_eq!(delete_nonalphanumeric("Hello!".to_string()), "Hello");
+/// assert_eq!(delete_nonalphanumeric("world824...".to_string()), "world824");
+/// ```
+pub fn delete_nonalphanumeric(mut word: String) -> String {
+    word.retain(|c| c.is_alphanumeric());
+    word
+}
+
+/// Deletes apostrophes, straight or curly, from a word, so "don't"
+/// becomes "dont".
+/// ```
+/// use tidy_core::list_manipulations::delete_apostrophes;
+/// assert_eq!(delete_apostrophes("don't"), "dont");
+/// assert_eq!(delete_apostrophes("o\u{2019}clock"), "oclock");
+/// ```
+pub fn delete_apostrophes(word: &str) -> String {
+    word.chars()
+        .filter(|c| !matches!(c, '\'' | '\u{2018}' | '\u{2019}' | '\u{02BC}'))
+        .collect()
+}
+
+/// Replaces hyphens and dashes of other kinds, like the Unicode hyphen and
+/// the en dash, with ASCII hyphens, and deletes soft hyphens.
+/// ```
+/// use tidy_core::list_manipulations::normalize_hyphens;
+/// assert_eq!(normalize_hyphens("well\u{2010}known"), "well-known");
+/// assert_eq!(normalize_hyphens("hy\u{AD}phen"), "hyphen");
+/// ```
+pub fn normalize_hyphens(word: &str) -> String {
+    word.chars()
+        .filter(|c| *c != '\u{AD}')
+        .map(|c| match c {
+            '\u{2010}'..='\u{2015}' | '\u{2212}' | '\u{FE63}' | '\u{FF0D}' => '-',
+            _ => c,
+        })
+        .collect()
+}
+
+/// Delete all characters through and including the first appearance
+/// of character `ch` in inputted `&str` `s`. Program uses this to
+/// remove character through first tab or first space, a common task
+/// when dealing with diceware passphrase word lists that have dice roll
+/// numbers before each word. The
+/// [EFF long list](https://www.eff.org/files/2016/07/18/eff_large_wordlist.txt)
+/// is one such example.
+///
+/// Uses [memchr library](https://docs.rs/memchr/latest/memchr/)
+/// to find this character a bit quicker than standard function.
+///
+/// I outlined other approaches to this function in
+/// [a separate repo](https://github.com/sts10/splitter/blob/main/src/lib.rs).
+pub fn delete_before_first_char(s: &str, ch: char) -> &str {
+    match memchr(ch as u8, s.as_bytes()) {
+        None => s, // not found => return the whole string
+        Some(pos) => &s[pos + 1..],
+    }
+}
+
+/// Delete all characters after and including the first appearance
+/// of character `ch` in inputted `&str` `s`.
+///
+/// Uses [memchr library](https://docs.rs/memchr/latest/memchr/)
+/// to find this character a bit quicker than standard function.
+///
+/// I outlined other approaches to this function in
+/// [a separate repo](https://github.com/sts10/splitter/blob/main/src/lib.rs).
+pub fn delete_after_first_char(s: &str, ch: char) -> &str {
+    match memchr(ch as u8, s.as_bytes()) {
+        None => s, // not found => return the whole string
+        Some(pos) => &s[0..pos],
+    }
+}
+
+/// Takes the `number`th column of a line, counting from 1, like awk's `$N`.
+/// Columns are separated by `separator`, or, if it's `None`, by runs of
+/// whitespace. Returns an empty string if the line doesn't have that many
+/// columns.
+/// ```
+/// use tidy_core::list_manipulations::take_field;
+/// assert_eq!(take_field("11111\tabacus\tnoun", Some('\t'), 2), "abacus");
+/// assert_eq!(take_field("abacus  300", None, 2), "300");
+/// assert_eq!(take_field("abacus", Some(','), 2), "");
+/// ```
+pub fn take_field(line: &str, separator: Option<char>, number: usize) -> &str {
+    let index = match number.checked_sub(1) {
+        Some(index) => index,
+        None => return "",
+    };
+    let field = match separator {
+        Some(separator) => line.split(separator).nth(index),
+        None => line.split_whitespace().nth(index),
+    };
+    field.unwrap_or("")
+}
+
+/// Which instance of a delimiter on a line the delete-before and
+/// delete-after options act on, for lines with more than one, like
+/// "123\tword\ttag"
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DelimiterOccurrence {
+    /// The first instance
+    #[default]
+    First,
+    /// The last instance
+    Last,
+    /// Every instance: the delimiters split the line into columns, and only
+    /// the column next to the deleted part is kept. So deleting before a tab
+    /// makes "123\tword\ttag" into "word".
+    All,
+}
+
+impl FromStr for DelimiterOccurrence {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(DelimiterOccurrence::First),
+            "last" => Ok(DelimiterOccurrence::Last),
+            "all" => Ok(DelimiterOccurrence::All),
+            _ => Err(format!(
+                "Unknown delimiter occurrence {}. Use first, last, or all.",
+                s
+            )),
+        }
+    }
+}
+
+/// Which words a cut to a power (see `largest_power_at_most`) keeps
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CutStrategy {
+    /// A random selection of words, like `print_rand`
+    #[default]
+    Random,
+    /// The words at the beginning of the list, like `print_first`
+    First,
+}
+
+impl FromStr for CutStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "random" | "rand" => Ok(CutStrategy::Random),
+            "first" => Ok(CutStrategy::First),
+            _ => Err(format!("Unknown cut strategy {}. Use random or first.", s)),
+        }
+    }
+}
+
+/// The largest power of `base` that's no more than `list_length`, like
+/// 1,296 (6^4) for a list of 7,000 words and base 6. Since 1 is a power of
+/// every base, only an empty list gets 0. Bases below 2 have no powers
+/// besides 1, so leave the list's length as it is.
+/// ```
+/// use tidy_core::list_manipulations::largest_power_at_most;
+/// assert_eq!(largest_power_at_most(6, 7_000), 1_296);
+/// assert_eq!(largest_power_at_most(6, 7_776), 7_776);
+/// assert_eq!(largest_power_at_most(2, 3_000), 2_048);
+/// assert_eq!(largest_power_at_most(6, 5), 1);
+/// assert_eq!(largest_power_at_most(6, 0), 0);
+/// ```
+pub fn largest_power_at_most(base: usize, list_length: usize) -> usize {
+    if base < 2 || list_length == 0 {
+        return list_length;
+    }
+    let mut power = 1;
+    while let Some(next_power) = power.checked_mul(base) {
+        if next_power > list_length {
+            break;
+        }
+        power = next_power;
+    }
+    power
+}
+
+/// Delete all characters before and including the given `occurrence` of
+/// character `ch` in `s`.
+/// ```
+/// use tidy_core::list_manipulations::{delete_before_char, DelimiterOccurrence};
+/// let line = "123\tword\ttag";
+/// assert_eq!(delete_before_char(line, '\t', DelimiterOccurrence::First), "word\ttag");
+/// assert_eq!(delete_before_char(line, '\t', DelimiterOccurrence::Last), "tag");
+/// assert_eq!(delete_before_char(line, '\t', DelimiterOccurrence::All), "word");
+/// ```
+pub fn delete_before_char(s: &str, ch: char, occurrence: DelimiterOccurrence) -> &str {
+    match occurrence {
+        DelimiterOccurrence::First => delete_before_first_char(s, ch),
+        DelimiterOccurrence::Last => match s.rfind(ch) {
+            None => s,
+            Some(pos) => &s[pos + ch.len_utf8()..],
+        },
+        DelimiterOccurrence::All => delete_after_first_char(delete_before_first_char(s, ch), ch),
+    }
+}
+
+/// Delete all characters after and including the given `occurrence` of
+/// character `ch` in `s`. Deleting after every instance is the same as
+/// deleting after the first.
+/// ```
+/// use tidy_core::list_manipulations::{delete_after_char, DelimiterOccurrence};
+/// let line = "word\t123\ttag";
+/// assert_eq!(delete_after_char(line, '\t', DelimiterOccurrence::First), "word");
+/// assert_eq!(delete_after_char(line, '\t', DelimiterOccurrence::Last), "word\t123");
+/// ```
+pub fn delete_after_char(s: &str, ch: char, occurrence: DelimiterOccurrence) -> &str {
+    match occurrence {
+        DelimiterOccurrence::First | DelimiterOccurrence::All => delete_after_first_char(s, ch),
+        DelimiterOccurrence::Last => match s.rfind(ch) {
+            None => s,
+            Some(pos) => &s[..pos],
+        },
+    }
+}
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+/// This function removes words from the given word list
+/// such that the resulting, outputted list has a guaranteed
+/// maximum prefix length.  
+///
+/// As an example, if `maximum_shared_prefix_length == 4`, this
+/// means that on the resulting list, knowing the first 4 characters
+/// of any word on the generated list is sufficient to know which
+/// word it is. In this case, we'd know that if a word starts with
+/// "radi", we know it must be the word "radius" (if "radical" had been
+/// on the list, this function would have removed it).
+///
+/// This is useful if you intend the list to be used by software that
+/// uses auto-complete. In the case described above, a user will only have to type the
+/// first 4 characters of any word before a program could successfully
+/// auto-complete the entire word.
+pub fn guarantee_maximum_prefix_length(
+    list: &[String],
+    maximum_shared_prefix_length: usize,
+) -> Vec<String> {
+    let mut prefix_hashmap: HashMap<String, String> = HashMap::new();
+    for this_word in list {
+        // If this word is too short just skip it.
+        if count_characters(this_word) < maximum_shared_prefix_length {
+            continue;
+        }
+        prefix_hashmap
+            .entry(get_prefix(this_word, maximum_shared_prefix_length))
+            .and_modify(|existing_word| {
+                // Prefer shorter words, as a stand-in for simplicity (though that
+                // is debatable...)
+                if count_characters(this_word) < count_characters(existing_word) {
+                    *existing_word = this_word.to_string()
+                }
+            })
+            .or_insert_with(|| this_word.to_string());
+    }
+    let new_vec: Vec<(&String, &String)> = prefix_hashmap.iter().collect();
+    let mut new_word_list = vec![];
+    for t in new_vec {
+        new_word_list.push(t.1.to_string());
+    }
+    new_word_list
+}
+
+/// Makes the list's words unique in their first `length` characters (see
+/// `guarantee_maximum_prefix_length`), then cuts every word down to just
+/// those characters, like the 4-character prefixes of the BIP39 list.
+/// ```
+/// use tidy_core::list_manipulations::truncate_to_unique_prefix;
+/// let list: Vec<String> = ["radius", "radical", "abandon", "ab"].iter().map(|w| w.to_string()).collect();
+/// let mut truncated = truncate_to_unique_prefix(&list, 4);
+/// truncated.sort();
+/// assert_eq!(truncated, vec!["aban", "radi"]);
+/// ```
+pub fn truncate_to_unique_prefix(list: &[String], length: usize) -> Vec<String> {
+    guarantee_maximum_prefix_length(list, length)
+        .iter()
+        .map(|word| get_prefix(word, length))
+        .collect()
+}
+
+/// Executes Schlinkert prune. Attempts to make list uniquely decodable
+/// by removing the fewest number of code words possible. Adapted from
+/// Sardinas-Patterson algorithm. Which words are removed depends on the
+/// given `strategy` (see `SchlinkertStrategy`).
+pub fn schlinkert_prune(list: &[String], strategy: SchlinkertStrategy) -> Vec<String> {
+    schlinkert_prune_with_progress(list, strategy, &|_done, _total| {})
+}
+
+/// Like `schlinkert_prune`, but calls `on_progress` with how many words
+/// have been checked (or removed, depending on the strategy) and how many
+/// there are to check in total. The total may grow as the prune goes on.
+pub fn schlinkert_prune_with_progress(
+    list: &[String],
+    strategy: SchlinkertStrategy,
+    on_progress: &dyn Fn(usize, usize),
+) -> Vec<String> {
+    schlinkert_prune_until(list, strategy, on_progress, &|| false).unwrap()
+}
+
+/// Like `schlinkert_prune_with_progress`, but checks `should_stop` as it
+/// goes, and gives up, returning `None`, if it returns true (like when a
+/// timeout passes, or the user presses Ctrl-C).
+pub fn schlinkert_prune_until(
+    list: &[String],
+    strategy: SchlinkertStrategy,
+    on_progress: &dyn Fn(usize, usize),
+    should_stop: &dyn Fn() -> bool,
+) -> Option<Vec<String>> {
+    let mut new_list = list.to_owned();
+    match strategy {
+        SchlinkertStrategy::All => {
+            on_progress(0, 1);
+            let offenders_to_remove: HashSet<String> =
+                get_sardinas_patterson_final_intersection_until(list, should_stop)?
+                    .into_iter()
+                    .collect();
+            new_list.retain(|x| !offenders_to_remove.contains(x));
+            on_progress(1, 1);
+        }
+        SchlinkertStrategy::Longest | SchlinkertStrategy::LowestFrequency => loop {
+            let offenders: HashSet<String> =
+                get_sardinas_patterson_final_intersection_until(&new_list, should_stop)?
+                    .into_iter()
+                    .collect();
+            let words_removed = list.len() - new_list.len();
+            on_progress(words_removed, words_removed + offenders.len());
+            // Offenders are in no particular order, so find the position of
+            // each in the list
+            let to_remove = new_list
+                .iter()
+                .enumerate()
+                .filter(|(_i, word)| offenders.contains(*word))
+                .max_by_key(|(i, word)| match strategy {
+                    SchlinkertStrategy::Longest => (count_characters(word), *i),
+                    _ => (0, *i),
+                });
+            match to_remove {
+                Some((i, _word)) => {
+                    new_list.remove(i);
+                }
+                None => break,
+            }
+        },
+        SchlinkertStrategy::Fewest => {
+            // Start from the list with every flagged word removed, which is
+            // uniquely decodable, then try adding each flagged word back in,
+            // keeping it if the list is still uniquely decodable.
+            let offenders: HashSet<String> =
+                get_sardinas_patterson_final_intersection_until(list, should_stop)?
+                    .into_iter()
+                    .collect();
+            new_list.retain(|x| !offenders.contains(x));
+            for (i, word) in list
+                .iter()
+                .filter(|word| offenders.contains(*word))
+                .enumerate()
+            {
+                if should_stop() {
+                    return None;
+                }
+                on_progress(i, offenders.len());
+                new_list.push(word.to_string());
+                if find_ambiguous_concatenation(&new_list).is_some() {
+                    new_list.pop();
+                }
+            }
+            on_progress(offenders.len(), offenders.len());
+            // Put words back in their original order
+            let kept_words: HashSet<&String> = new_list.iter().collect();
+            new_list = list
+                .iter()
+                .filter(|word| kept_words.contains(word))
+                .map(|word| word.to_string())
+                .collect();
+        }
+    }
+    Some(new_list)
+}
+
+use unicode_segmentation::UnicodeSegmentation;
+/// Given a word and a `usize` of `length`, this function returns
+/// the first `length` characters of that word. This length is
+/// measured in grapheme clusters to better handle accented
+/// characters and emoji.
+/// ```
+/// use tidy_core::list_manipulations::get_prefix;
+/// assert_eq!(get_prefix("hello world", 4), "hell");
+/// assert_eq!(get_prefix("sécréter", 5), "sécré");
+/// assert_eq!(get_prefix("😀😃😄😁😆", 2), "😀😃");
+/// ```
+pub fn get_prefix(word: &str, length: usize) -> String {
+    word.graphemes(true).take(length).collect::<String>()
+}
+
+/// Splits running text, like the lines of a book, into its words, following
+/// Unicode's rules for word boundaries, so punctuation is dropped but words
+/// like "can't" stay whole. Returns each word once, with the number of
+/// times it appears, most common first (and, of words that appear equally
+/// often, in the order they first appear).
+/// ```
+/// use tidy_core::list_manipulations::tokenize_prose;
+/// let lines = vec![
+///     "The cat sat. \"Can't you see the cat?\"".to_string(),
+///     "-- the end".to_string(),
+/// ];
+/// assert_eq!(
+///     tokenize_prose(&lines),
+///     vec![
+///         ("cat".to_string(), 2),
+///         ("the".to_string(), 2),
+///         ("The".to_string(), 1),
+///         ("sat".to_string(), 1),
+///         ("Can't".to_string(), 1),
+///         ("you".to_string(), 1),
+///         ("see".to_string(), 1),
+///         ("end".to_string(), 1),
+///     ]
+/// );
+/// ```
+pub fn tokenize_prose(lines: &[String]) -> Vec<(String, usize)> {
+    tokenize_prose_into_ngrams(lines, 1)
+}
+
+/// Like `tokenize_prose`, but splits running text into phrases of `n`
+/// words in a row (n-grams), like "of the", joined with a space. Phrases
+/// don't run across punctuation or from one line to the next, so "times,
+/// it" isn't a phrase. With an `n` of 1, this is `tokenize_prose`.
+/// ```
+/// use tidy_core::list_manipulations::tokenize_prose_into_ngrams;
+/// let lines = vec![
+///     "Ice cream, ice cream! We all scream for ice cream".to_string(),
+/// ];
+/// assert_eq!(
+///     tokenize_prose_into_ngrams(&lines, 2),
+///     vec![
+///         ("ice cream".to_string(), 2),
+///         ("Ice cream".to_string(), 1),
+///         ("We all".to_string(), 1),
+///         ("all scream".to_string(), 1),
+///         ("scream for".to_string(), 1),
+///         ("for ice".to_string(), 1),
+///     ]
+/// );
+/// ```
+pub fn tokenize_prose_into_ngrams(lines: &[String], n: usize) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = vec![];
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    for line in lines {
+        // The words since the last punctuation mark
+        let mut words: Vec<&str> = vec![];
+        for segment in line.split_word_bounds() {
+            // Like `unicode_words`, count segments with a letter or number
+            // in them as words
+            if segment.chars().any(char::is_alphanumeric) {
+                words.push(segment);
+            } else {
+                if !segment.trim().is_empty() {
+                    words.clear();
+                }
+                continue;
+            }
+            if words.len() < n {
+                continue;
+            }
+            let phrase = words[words.len() - n..].join(" ");
+            match positions.get(&phrase) {
+                Some(&position) => counts[position].1 += 1,
+                None => {
+                    positions.insert(phrase.clone(), counts.len());
+                    counts.push((phrase, 1));
+                }
+            }
+        }
+    }
+    // A stable sort keeps words that appear equally often in the order they
+    // first appear
+    counts.sort_by(|a, b| b.1.cmp(&a.1));
+    counts
+}
+
+/// Helper function to determine if a given char as `u16` is a
+/// Latin letter (A through Z or a through z, no diacritics).
+/// ```
+/// use tidy_core::list_manipulations::is_latin_alphabetic;
+/// assert_eq!(is_latin_alphabetic('h' as u16), true);
+/// assert_eq!(is_latin_alphabetic('A' as u16), true);
+/// assert_eq!(is_latin_alphabetic('1' as u16), false);
+/// assert_eq!(is_latin_alphabetic(',' as u16), false);
+/// assert_eq!(is_latin_alphabetic('é' as u16), false);
+/// assert_eq!(is_latin_alphabetic('ő' as u16), false);
+/// ```
+pub fn is_latin_alphabetic(chr: u16) -> bool {
+    (65..=90).contains(&chr) || (97..=122).contains(&chr)
+}
+
+use icu::properties::maps::{self, CodePointMapData};
+use icu::properties::Script;
+thread_local! {
+    static SCRIPT_DATA: CodePointMapData<Script> =
+        maps::load_script(&icu_testdata::unstable()).expect("Script data should be valid");
+}
+
+/// Finds the scripts, like Latin or Cyrillic, of the letters in a word.
+/// Characters shared by many scripts, like digits and punctuation, don't
+/// count, and Han, Hiragana, Katakana, Hangul, and Bopomofo count as one
+/// script (Han), since words in Chinese, Japanese, and Korean mix them.
+/// ```
+/// use icu::properties::Script;
+/// use tidy_core::list_manipulations::find_scripts;
+/// assert_eq!(find_scripts("paypal"), vec![Script::Latin]);
+/// // The second "а" is Cyrillic
+/// assert_eq!(find_scripts("pаypal"), vec![Script::Latin, Script::Cyrillic]);
+/// assert_eq!(find_scripts("ひらがな漢字"), vec![Script::Han]);
+/// ```
+pub fn find_scripts(word: &str) -> Vec<Script> {
+    SCRIPT_DATA.with(|data| {
+        let data = data.as_borrowed();
+        let mut scripts = vec![];
+        for ch in word.chars() {
+            let script = match data.get(ch) {
+                Script::Common | Script::Inherited | Script::Unknown => continue,
+                Script::Hiragana | Script::Katakana | Script::Hangul | Script::Bopomofo => {
+                    Script::Han
+                }
+                script => script,
+            };
+            if !scripts.contains(&script) {
+                scripts.push(script);
+            }
+        }
+        scripts
+    })
+}
+
+/// Whether a word mixes letters from more than one script, like Latin and
+/// Cyrillic (see `find_scripts`). Such words are often spoofs, using
+/// letters that look like other letters.
+pub fn is_mixed_script(word: &str) -> bool {
+    find_scripts(word).len() > 1
+}
+
+/// Joins the words of an entry with whitespace in it, like "ice cream",
+/// with the given separator.
+/// ```
+/// use tidy_core::list_manipulations::join_multiword;
+/// assert_eq!(join_multiword("ice  cream", "-"), "ice-cream");
+/// assert_eq!(join_multiword("ice cream", ""), "icecream");
+/// ```
+pub fn join_multiword(entry: &str, separator: &str) -> String {
+    entry
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(separator)
+}
+
+/// Replaces curly or smart quotes with straight quotes.
+pub fn straighten_quotes(input: &str) -> String {
+    let mut result = String::new();
+    for c in input.chars() {
+        match c {
+            '\u{201C}' => result.push('\"'), // left double quotation mark
+            '\u{201D}' => result.push('\"'), // right double quotation mark
+            '\u{2018}' => result.push('\''), // left single quotation mark
+            '\u{2019}' => result.push('\''), // right single quotation mark
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+use itertools::Itertools;
+/// De-duplicates a Vector of `String`s while maintaining list order.
+/// ```
+/// use tidy_core::list_manipulations::dedup_without_sorting;
+/// let list: Vec<String> = ["b", "a", "b"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(dedup_without_sorting(&list), vec!["b", "a"]);
+/// ```
+pub fn dedup_without_sorting(list: &[String]) -> Vec<String> {
+    let dedup: Vec<String> = list.iter().unique().map(|s| s.to_string()).collect();
+    dedup.to_vec()
+}
+
+/// Which of the words that only differ in case to keep when de-duplicating
+/// without regard to case
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CanonicalCase {
+    /// The word that comes first on the list
+    #[default]
+    First,
+    /// An all-lowercase word, if there is one
+    Lowercase,
+    /// A word with capital letters, if there is one
+    Capitalized,
+}
+
+impl FromStr for CanonicalCase {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(CanonicalCase::First),
+            "lowercase" => Ok(CanonicalCase::Lowercase),
+            "capitalized" => Ok(CanonicalCase::Capitalized),
+            _ => Err(format!(
+                "Unknown canonical case {}. Use first, lowercase, or capitalized.",
+                s
+            )),
+        }
+    }
+}
+
+/// Which of a word's lines to keep when the same word comes up more than
+/// once, like in two inputted lists with different frequency counts. The
+/// kept line's metadata (and, when de-duplicating without regard to case,
+/// its spelling) goes at the position of the word's first line.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum DedupKeep {
+    /// The line that comes first
+    #[default]
+    First,
+    /// The line that comes last
+    Last,
+    /// The line whose metadata starts with the highest number, like a word
+    /// frequency count. Lines without one are kept last.
+    HighestFrequency,
+    /// The shortest line, counting both the word and its metadata
+    Shortest,
+}
+
+impl FromStr for DedupKeep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "first" => Ok(DedupKeep::First),
+            "last" => Ok(DedupKeep::Last),
+            "highest-frequency" => Ok(DedupKeep::HighestFrequency),
+            "shortest" => Ok(DedupKeep::Shortest),
+            _ => Err(format!(
+                "Unknown line to keep {}. Use first, last, highest-frequency, or shortest.",
+                s
+            )),
+        }
+    }
+}
+
+/// De-duplicates a list treating words that only differ in case, like
+/// "Apple" and "apple", as duplicates. Doesn't change the case of any word:
+/// of each set of duplicates, keeps the one preferred by `keep`, at the
+/// position of the first of them.
+/// ```
+/// use tidy_core::list_manipulations::{dedup_ignoring_case, CanonicalCase};
+/// let list: Vec<String> = ["Apple", "zoo", "apple", "NASA"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(dedup_ignoring_case(&list, CanonicalCase::First), vec!["Apple", "zoo", "NASA"]);
+/// assert_eq!(dedup_ignoring_case(&list, CanonicalCase::Lowercase), vec!["apple", "zoo", "NASA"]);
+/// ```
+pub fn dedup_ignoring_case(list: &[String], keep: CanonicalCase) -> Vec<String> {
+    dedup_ignoring_case_preferring(list, keep, &|_kept, _word| false)
+}
+
+/// Like `dedup_ignoring_case`, but when `keep` doesn't prefer either of two
+/// duplicates (always, for `CanonicalCase::First`), keeps the later one if
+/// `prefer(kept, later)` is true.
+/// ```
+/// use tidy_core::list_manipulations::{dedup_ignoring_case_preferring, CanonicalCase};
+/// let list: Vec<String> = ["Apple", "zoo", "APPLE"].iter().map(|w| w.to_string()).collect();
+/// let keep_last = |_kept: &str, _later: &str| true;
+/// assert_eq!(
+///     dedup_ignoring_case_preferring(&list, CanonicalCase::First, &keep_last),
+///     vec!["APPLE", "zoo"]
+/// );
+/// ```
+pub fn dedup_ignoring_case_preferring(
+    list: &[String],
+    keep: CanonicalCase,
+    prefer: &dyn Fn(&str, &str) -> bool,
+) -> Vec<String> {
+    let has_capitals = |word: &str| word.chars().any(|ch| ch.is_uppercase());
+    let mut positions: HashMap<String, usize> = HashMap::new();
+    let mut deduped: Vec<String> = vec![];
+    for word in list {
+        match positions.get(&word.to_lowercase()) {
+            Some(&position) => {
+                let kept = &deduped[position];
+                let prefer_word = match (keep, has_capitals(kept), has_capitals(word)) {
+                    (CanonicalCase::Lowercase, true, false) => true,
+                    (CanonicalCase::Lowercase, false, true) => false,
+                    (CanonicalCase::Capitalized, false, true) => true,
+                    (CanonicalCase::Capitalized, true, false) => false,
+                    _ => prefer(kept, word),
+                };
+                if prefer_word {
+                    deduped[position] = word.to_string();
+                }
+            }
+            None => {
+                positions.insert(word.to_lowercase(), deduped.len());
+                deduped.push(word.to_string());
+            }
+        }
+    }
+    deduped
+}
+
+/// How to capitalize the words of a finished list
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Capitalization {
+    /// Leave words as they are
+    #[default]
+    None,
+    /// Capitalize the first letter of each entry, like "Ice cream"
+    First,
+    /// Capitalize the first letter of every word of each entry, like "Ice
+    /// Cream" (Title Case)
+    All,
+}
+
+impl FromStr for Capitalization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Capitalization::None),
+            "first" => Ok(Capitalization::First),
+            "all" => Ok(Capitalization::All),
+            _ => Err(format!(
+                "Unknown capitalization {}. Use first, all, or none.",
+                s
+            )),
+        }
+    }
+}
+
+/// Capitalizes an entry's first letter or, with `Capitalization::All`, the
+/// first letter of each of its words, which are separated by whitespace or
+/// hyphens. Letters are uppercased by Unicode's rules, so accented letters
+/// are capitalized too. The rest of the letters are left as they are.
+/// ```
+/// use tidy_core::list_manipulations::{capitalize, Capitalization};
+/// assert_eq!(capitalize("ice cream", Capitalization::First), "Ice cream");
+/// assert_eq!(capitalize("ice cream", Capitalization::All), "Ice Cream");
+/// assert_eq!(capitalize("jack-in-the-box", Capitalization::All), "Jack-In-The-Box");
+/// assert_eq!(capitalize("élan", Capitalization::First), "Élan");
+/// assert_eq!(capitalize("'twas", Capitalization::First), "'Twas");
+/// assert_eq!(capitalize("ice cream", Capitalization::None), "ice cream");
+/// ```
+pub fn capitalize(word: &str, capitalization: Capitalization) -> String {
+    let mut capitalized = String::with_capacity(word.len());
+    let mut at_start_of_word = capitalization != Capitalization::None;
+    for c in word.chars() {
+        if at_start_of_word && c.is_alphanumeric() {
+            capitalized.extend(c.to_uppercase());
+            at_start_of_word = false;
+        } else {
+            capitalized.push(c);
+            if capitalization == Capitalization::All && (c.is_whitespace() || c == '-') {
+                at_start_of_word = true;
+            }
+        }
+    }
+    capitalized
+}
+
+/// Characters, and sequences of characters, that are easy to confuse with
+/// others when handwritten or printed: l, 1, and I; O and 0; rn and m
+pub const DEFAULT_CONFUSABLE_CHARS: [&str; 6] = ["l", "1", "I", "O", "0", "rn"];
+
+/// Whether the word has any of the given confusable characters, or
+/// sequences of characters, in it.
+/// ```
+/// use tidy_core::list_manipulations::has_confusable_chars;
+/// let confusables: Vec<String> = ["l", "1", "rn"].iter().map(|c| c.to_string()).collect();
+/// assert!(has_confusable_chars("barn", &confusables));
+/// assert!(has_confusable_chars("lake", &confusables));
+/// assert!(!has_confusable_chars("bark", &confusables));
+/// ```
+pub fn has_confusable_chars(word: &str, confusables: &[String]) -> bool {
+    confusables
+        .iter()
+        .any(|confusable| word.contains(confusable.as_str()))
+}
+
+/// Whether the word has the same letter twice in a row, ignoring case, like
+/// the "ll" of "balloon". Doubled letters are easy to miss when a word is
+/// spoken aloud.
+/// ```
+/// use tidy_core::list_manipulations::has_doubled_letter;
+/// assert!(has_doubled_letter("balloon"));
+/// assert!(has_doubled_letter("Aardvark"));
+/// assert!(!has_doubled_letter("banana"));
+/// ```
+pub fn has_doubled_letter(word: &str) -> bool {
+    let letters: Vec<char> = word.to_lowercase().chars().collect();
+    letters
+        .windows(2)
+        .any(|pair| pair[0] == pair[1] && pair[0].is_alphabetic())
+}
+
+/// Clusters of consonants that are hard to say, or to make out when
+/// spoken, especially for non-native speakers of English, like the "ths"
+/// of "months" and the "sts" of "tests"
+pub const DEFAULT_CONSONANT_CLUSTERS: [&str; 11] = [
+    "sts", "sks", "sps", "xts", "ths", "fth", "xth", "mpts", "rlds", "phth", "nkth",
+];
+
+/// Whether the word has any of the given clusters of consonants in it,
+/// ignoring case.
+/// ```
+/// use tidy_core::list_manipulations::has_consonant_cluster;
+/// let clusters: Vec<String> = ["sts", "ngths"].iter().map(|c| c.to_string()).collect();
+/// assert!(has_consonant_cluster("Lengths", &clusters));
+/// assert!(has_consonant_cluster("tests", &clusters));
+/// assert!(!has_consonant_cluster("length", &clusters));
+/// ```
+pub fn has_consonant_cluster(word: &str, clusters: &[String]) -> bool {
+    let word = word.to_lowercase();
+    clusters
+        .iter()
+        .any(|cluster| word.contains(&cluster.to_lowercase()))
+}
+
+/// The `gibberish_score` at or above which `--remove-gibberish` removes a
+/// word, unless told otherwise. Real words rarely score above 5.
+pub const DEFAULT_GIBBERISH_THRESHOLD: f64 = 5.5;
+
+/// How random a word looks, for spotting junk that isn't a word, like
+/// base64 blobs and hex strings: the Shannon entropy of its characters, in
+/// bits per character, scaled up by how often it switches between
+/// lowercase letters, uppercase letters, and digits (up to three times, for
+/// a word that switches between every pair of characters). Entropy alone is
+/// high for plenty of real words, like "algorithm", whose letters are all
+/// different, but real words rarely switch kinds of characters, other than
+/// to start with a capital letter.
+/// ```
+/// use tidy_core::list_manipulations::gibberish_score;
+/// assert!(gibberish_score("algorithm") < 3.2);
+/// assert!(gibberish_score("McDonalds") < 5.0);
+/// assert!(gibberish_score("aGVsbG8g") > 6.0);
+/// assert!(gibberish_score("9f86d081884c") > 5.5);
+/// ```
+pub fn gibberish_score(word: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in word.chars() {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+    let length = word.chars().count() as f64;
+    let entropy: f64 = counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / length;
+            -p * p.log2()
+        })
+        .sum();
+    // Other characters, like hyphens and spaces, don't count as switches
+    let kinds: Vec<u8> = word
+        .chars()
+        .filter_map(|c| {
+            if c.is_lowercase() {
+                Some(0)
+            } else if c.is_uppercase() {
+                Some(1)
+            } else if c.is_numeric() {
+                Some(2)
+            } else {
+                None
+            }
+        })
+        .collect();
+    let pairs = kinds.len().saturating_sub(1);
+    if pairs == 0 {
+        return entropy;
+    }
+    let switches = kinds
+        .windows(2)
+        .enumerate()
+        // A capital first letter isn't a switch
+        .filter(|(i, pair)| pair[0] != pair[1] && !(*i == 0 && pair[0] == 1 && pair[1] == 0))
+        .count();
+    entropy * (1.0 + 2.0 * switches as f64 / pairs as f64)
+}
+
+/// The first letter of a word, lowercased, used to group words by initial
+fn first_letter(word: &str) -> String {
+    word.chars()
+        .next()
+        .map(|ch| ch.to_lowercase().collect())
+        .unwrap_or_default()
+}
+
+/// Keeps at most `max` of the words that start with each letter (ignoring
+/// case), the ones that come first on the list.
+/// ```
+/// use tidy_core::list_manipulations::cap_words_per_first_letter;
+/// let list: Vec<String> = ["sun", "sea", "Sky", "moon"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(cap_words_per_first_letter(&list, 2), vec!["sun", "sea", "moon"]);
+/// ```
+pub fn cap_words_per_first_letter(list: &[String], max: usize) -> Vec<String> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    list.iter()
+        .filter(|word| {
+            let count = counts.entry(first_letter(word)).or_insert(0);
+            *count += 1;
+            *count <= max
+        })
+        .cloned()
+        .collect()
+}
+
+/// A limit on the share of the list taken up by words in a range of
+/// lengths, like at most 20 percent of words having 8 or more characters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LengthQuota {
+    pub minimum_length: usize,
+    /// If `None`, words of any length from `minimum_length` up count
+    pub maximum_length: Option<usize>,
+    pub maximum_percent: f64,
+}
+
+impl LengthQuota {
+    /// Whether a word counts toward this quota
+    pub fn applies_to(&self, word: &str) -> bool {
+        let length = count_characters(word);
+        let under_maximum = match self.maximum_length {
+            Some(maximum_length) => length <= maximum_length,
+            None => true,
+        };
+        length >= self.minimum_length && under_maximum
+    }
+}
+
+impl FromStr for LengthQuota {
+    type Err = String;
+
+    /// Parses a quota written as lengths and a percent, separated by a colon:
+    /// "8+:20" for at most 20 percent of words with 8 or more characters,
+    /// "3-4:10" for lengths 3 to 4, or "5:30" for length 5 alone.
+    /// ```
+    /// use tidy_core::list_manipulations::LengthQuota;
+    /// assert_eq!(
+    ///     "8+:20".parse(),
+    ///     Ok(LengthQuota {
+    ///         minimum_length: 8,
+    ///         maximum_length: None,
+    ///         maximum_percent: 20.0
+    ///     })
+    /// );
+    /// assert!("8:120".parse::<LengthQuota>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            format!(
+                "Unable to parse length quota {}. Use lengths and a percent, like 8+:20 or 3-4:10.",
+                s
+            )
+        };
+        let (lengths, percent) = s.split_once(':').ok_or_else(error)?;
+        let maximum_percent = percent
+            .trim()
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| error())?;
+        if !(0.0..=100.0).contains(&maximum_percent) {
+            return Err(error());
+        }
+        let lengths = lengths.trim();
+        let (minimum_length, maximum_length) = if let Some(minimum) = lengths.strip_suffix('+') {
+            (minimum.parse().map_err(|_| error())?, None)
+        } else if let Some((minimum, maximum)) = lengths.split_once('-') {
+            (
+                minimum.parse().map_err(|_| error())?,
+                Some(maximum.parse().map_err(|_| error())?),
+            )
+        } else {
+            let length = lengths.parse().map_err(|_| error())?;
+            (length, Some(length))
+        };
+        Ok(LengthQuota {
+            minimum_length,
+            maximum_length,
+            maximum_percent,
+        })
+    }
+}
+
+/// Which words over a `LengthQuota` are removed first
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum QuotaPriority {
+    /// The words closest to the end of the list. Assumes the inputted list
+    /// is sorted by word frequency.
+    #[default]
+    LowestFrequency,
+    /// The longest words, then those closest to the end of the list
+    Longest,
+    /// Words picked at random
+    Random,
+}
+
+impl FromStr for QuotaPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "lowest-frequency" | "frequency" => Ok(QuotaPriority::LowestFrequency),
+            "longest" => Ok(QuotaPriority::Longest),
+            "random" => Ok(QuotaPriority::Random),
+            _ => Err(format!(
+                "Unknown quota priority {}. Use lowest-frequency, longest, or random.",
+                s
+            )),
+        }
+    }
+}
+
+/// Removes words until no quota's range of lengths takes up more than its
+/// share of the list, choosing which words to remove by `priority`.
+/// Since removing words shrinks the list, and so every quota, quotas are
+/// checked again until all of them are met.
+/// ```
+/// use tidy_core::list_manipulations::{enforce_length_quotas, LengthQuota, QuotaPriority};
+/// use tidy_core::sampling::make_rng;
+/// let list: Vec<String> = ["cat", "dog", "elephant", "kangaroo", "giraffe"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// let quotas = ["7+:50".parse::<LengthQuota>().unwrap()];
+/// assert_eq!(
+///     enforce_length_quotas(&list, &quotas, QuotaPriority::LowestFrequency, &mut make_rng(None)),
+///     vec!["cat", "dog", "elephant", "kangaroo"]
+/// );
+/// ```
+pub fn enforce_length_quotas<R: Rng>(
+    list: &[String],
+    quotas: &[LengthQuota],
+    priority: QuotaPriority,
+    rng: &mut R,
+) -> Vec<String> {
+    let mut list = list.to_vec();
+    loop {
+        let mut removed_any = false;
+        for quota in quotas {
+            // Positions of the words this quota applies to
+            let mut in_quota: Vec<usize> = (0..list.len())
+                .filter(|i| quota.applies_to(&list[*i]))
+                .collect();
+            let share = quota.maximum_percent / 100.0;
+            let excess = in_quota.len() as f64 - share * list.len() as f64;
+            if excess <= 0.0 {
+                continue;
+            }
+            // Removing a word shrinks the list too, so more words than the
+            // excess may need to go
+            let to_remove = if share >= 1.0 {
+                0
+            } else {
+                // Less a little, so floating-point error can't round up
+                // to an extra word
+                ((excess / (1.0 - share)) - 1e-9).ceil() as usize
+            }
+            .min(in_quota.len());
+            // Order the words so the ones to remove come last
+            match priority {
+                QuotaPriority::LowestFrequency => (),
+                QuotaPriority::Longest => {
+                    in_quota.sort_by_key(|i| (count_characters(&list[*i]), *i));
+                }
+                QuotaPriority::Random => in_quota.shuffle(rng),
+            }
+            let removals: HashSet<usize> = in_quota
+                .split_off(in_quota.len() - to_remove)
+                .into_iter()
+                .collect();
+            if !removals.is_empty() {
+                removed_any = true;
+                list = list
+                    .into_iter()
+                    .enumerate()
+                    .filter(|(i, _word)| !removals.contains(i))
+                    .map(|(_i, word)| word)
+                    .collect();
+            }
+        }
+        if !removed_any {
+            return list;
+        }
+    }
+}
+
+/// Reorders the list to take turns between first letters (ignoring case),
+/// keeping the order of words that share a first letter. Taking words from
+/// the start of the result gives each letter as even a share as it can.
+/// ```
+/// use tidy_core::list_manipulations::interleave_by_first_letter;
+/// let list: Vec<String> = ["sun", "sea", "sky", "moon", "air"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(
+///     interleave_by_first_letter(&list),
+///     vec!["sun", "moon", "air", "sea", "sky"]
+/// );
+/// ```
+pub fn interleave_by_first_letter(list: &[String]) -> Vec<String> {
+    let mut letters: Vec<String> = vec![];
+    let mut groups: HashMap<String, Vec<&String>> = HashMap::new();
+    for word in list {
+        let letter = first_letter(word);
+        if !groups.contains_key(&letter) {
+            letters.push(letter.clone());
+        }
+        groups.entry(letter).or_default().push(word);
+    }
+    let longest_group = groups.values().map(|group| group.len()).max().unwrap_or(0);
+    let mut interleaved = vec![];
+    for round in 0..longest_group {
+        for letter in &letters {
+            if let Some(word) = groups[letter].get(round) {
+                interleaved.push(word.to_string());
+            }
+        }
+    }
+    interleaved
+}
+
+/// Moves the given words to the front of the list, keeping the order of
+/// the words within the front and the rest. Used to make sure truncating
+/// the list keeps the words.
+/// ```
+/// use tidy_core::list_manipulations::move_to_front;
+/// let list: Vec<String> = ["a", "b", "c", "d"].iter().map(|w| w.to_string()).collect();
+/// let words: Vec<String> = ["d", "b"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(move_to_front(list, &words), vec!["b", "d", "a", "c"]);
+/// ```
+pub fn move_to_front(list: Vec<String>, words: &[String]) -> Vec<String> {
+    let words: HashSet<&String> = words.iter().collect();
+    let (mut front, rest): (Vec<String>, Vec<String>) =
+        list.into_iter().partition(|word| words.contains(word));
+    front.extend(rest);
+    front
+}
+
+/// Moves each pinned word to its given (zero-indexed) position on the
+/// list, shifting the other words down to make room, so that the pinned
+/// words keep the dice rolls they have on an earlier version of the list.
+/// Errors if a pinned word isn't on the list, or a position is taken twice
+/// or is past the end of the list.
+/// ```
+/// use tidy_core::list_manipulations::pin_words;
+/// let list: Vec<String> = ["a", "b", "c", "d"].iter().map(|w| w.to_string()).collect();
+/// let pins = vec![(0, "c".to_string()), (3, "a".to_string())];
+/// assert_eq!(pin_words(list, &pins).unwrap(), vec!["c", "b", "d", "a"]);
+/// ```
+pub fn pin_words(list: Vec<String>, pins: &[(usize, String)]) -> Result<Vec<String>, String> {
+    let mut pins = pins.to_vec();
+    pins.sort();
+    for (i, (position, word)) in pins.iter().enumerate() {
+        if !list.contains(word) {
+            return Err(format!("Pinned word {} isn't on the new list", word));
+        }
+        if *position >= list.len() {
+            return Err(format!(
+                "Can't pin {} to position {}, past the end of the {}-word list",
+                word,
+                position + 1,
+                list.len()
+            ));
+        }
+        if let Some((_, other)) = pins[..i]
+            .iter()
+            .find(|(other_position, other)| other_position == position || other == word)
+        {
+            return Err(format!(
+                "Pinned words {} and {} conflict; pin each word once, to its own position",
+                other, word
+            ));
+        }
+    }
+    let mut pinned_list: Vec<String> = list
+        .into_iter()
+        .filter(|word| !pins.iter().any(|(_, pinned)| pinned == word))
+        .collect();
+    for (position, word) in pins {
+        pinned_list.insert(position, word);
+    }
+    Ok(pinned_list)
+}
+
+/// A change to a position of a list: the position, the word that was
+/// there, and the word there now
+pub type ListChange = (usize, Option<String>, Option<String>);
+
+/// Orders a new version of a list so that every word that was on the
+/// previous version keeps its position (and so its dice roll), filling the
+/// positions of words that are gone with the new words, in order. Any new
+/// words left over go at the end. If there aren't enough new words to fill
+/// every gap, the words after an unfilled gap move up.
+///
+/// Also returns the changes made to the previous list, as (zero-indexed)
+/// positions on the previous list, or on the new list for words added at
+/// the end, with the word that was there and the word there now.
+/// ```
+/// use tidy_core::list_manipulations::keep_previous_positions;
+/// let previous: Vec<String> = ["ant", "bee", "cat"].iter().map(|w| w.to_string()).collect();
+/// let list: Vec<String> = ["ant", "cat", "dog"].iter().map(|w| w.to_string()).collect();
+/// let (updated, changes) = keep_previous_positions(&list, &previous);
+/// assert_eq!(updated, vec!["ant", "dog", "cat"]);
+/// assert_eq!(changes, vec![(1, Some("bee".to_string()), Some("dog".to_string()))]);
+/// ```
+pub fn keep_previous_positions(
+    list: &[String],
+    previous: &[String],
+) -> (Vec<String>, Vec<ListChange>) {
+    let previous = dedup_without_sorting(previous);
+    let words: HashSet<&String> = list.iter().collect();
+    let previous_words: HashSet<&String> = previous.iter().collect();
+    let mut new_words = list.iter().filter(|word| !previous_words.contains(word));
+    let mut updated = vec![];
+    let mut changes = vec![];
+    for (position, old_word) in previous.iter().enumerate() {
+        if words.contains(old_word) {
+            updated.push(old_word.to_string());
+        } else {
+            let new_word = new_words.next().cloned();
+            changes.push((position, Some(old_word.to_string()), new_word.clone()));
+            updated.extend(new_word);
+        }
+    }
+    for new_word in new_words {
+        changes.push((updated.len(), None, Some(new_word.to_string())));
+        updated.push(new_word.to_string());
+    }
+    (updated, changes)
+}
+
+/// Combines lists into one, taking lines from each list in proportion to
+/// its weight, so that any number of lines from the start of the combined
+/// list has more lines of the lists with higher weights. Lists with a
+/// weight of 0 come last. Returns each line with the index of its list.
+/// ```
+/// use tidy_core::list_manipulations::merge_weighted;
+/// let lists = vec![
+///     vec!["a1".to_string(), "a2".to_string(), "a3".to_string()],
+///     vec!["b1".to_string(), "b2".to_string()],
+/// ];
+/// let merged: Vec<String> = merge_weighted(&lists, &[2.0, 1.0])
+///     .into_iter()
+///     .map(|(_list, line)| line)
+///     .collect();
+/// assert_eq!(merged, vec!["a1", "a2", "b1", "a3", "b2"]);
+/// ```
+pub fn merge_weighted(lists: &[Vec<String>], weights: &[f64]) -> Vec<(usize, String)> {
+    let mut taken = vec![0; lists.len()];
+    let mut merged = vec![];
+    loop {
+        // Take from the list furthest behind its share
+        let next = (0..lists.len())
+            .filter(|&i| taken[i] < lists[i].len() && weights[i] > 0.0)
+            .min_by(|&a, &b| {
+                let share = |i: usize| (taken[i] + 1) as f64 / weights[i];
+                share(a).total_cmp(&share(b))
+            });
+        match next {
+            Some(i) => {
+                merged.push((i, lists[i][taken[i]].to_string()));
+                taken[i] += 1;
+            }
+            None => break,
+        }
+    }
+    for (i, list) in lists.iter().enumerate() {
+        merged.extend(list[taken[i]..].iter().map(|line| (i, line.to_string())));
+    }
+    merged
+}
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+/// Picks `amount` items at random, where items with higher weights are
+/// more likely to be picked (a weighted random sample without replacement,
+/// using the method of Efraimidis and Spirakis). Like a shuffle, the picked
+/// items come in random order.
+pub fn take_weighted_random<T: Clone, R: Rng>(
+    items: &[T],
+    weights: &[f64],
+    amount: usize,
+    rng: &mut R,
+) -> Vec<T> {
+    let mut keys: Vec<(f64, usize)> = weights
+        .iter()
+        .take(items.len())
+        .enumerate()
+        .map(|(i, weight)| (rng.gen::<f64>().powf(1.0 / weight), i))
+        .collect();
+    keys.sort_by(|a, b| b.0.total_cmp(&a.0));
+    keys.into_iter()
+        .take(amount)
+        .map(|(_key, i)| items[i].clone())
+        .collect()
+}
+
+/// Remove prefix words from the given Vector of `String`s.
+///
+/// A brief example: If both "news" and "newspaper" are on the inputted list
+/// we may, for security reasons, want to remove the prefix word,
+/// which is "news" in this case.
+/// ```
+/// use tidy_core::list_manipulations::remove_prefix_words;
+/// let list: Vec<String> = ["news", "newspaper", "paper"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(remove_prefix_words(&list), vec!["newspaper", "paper"]);
+/// ```
+pub fn remove_prefix_words(list: &[String]) -> Vec<String> {
+    let prefix_words = find_prefix_words(list);
+    list.iter()
+        .filter(|word| !prefix_words.contains(*word))
+        .cloned()
+        .collect()
+}
+
+/// Remove suffix words from the given Vector of `String`s.
+///
+/// A brief example: If both "news" and "newspaper" are on the inputted list
+/// we may, for security reasons, want to remove the suffix word,
+/// which is "paper" in this case.
+/// ```
+/// use tidy_core::list_manipulations::remove_suffix_words;
+/// let list: Vec<String> = ["news", "newspaper", "paper"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(remove_suffix_words(&list), vec!["news", "newspaper"]);
+/// ```
+pub fn remove_suffix_words(list: &[String]) -> Vec<String> {
+    let suffix_words = find_suffix_words(list);
+    list.iter()
+        .filter(|word| !suffix_words.contains(*word))
+        .cloned()
+        .collect()
+}
+
+/// Finds every word on the list that is a prefix of another word on the list.
+///
+/// Rather than compare every word to every other word, this sorts (a copy of)
+/// the list. Any words that start with a given word come right after it in
+/// sorted order, so we only need to compare each word to the next.
+/// ```
+/// use tidy_core::list_manipulations::find_prefix_words;
+/// let list: Vec<String> = ["newspaper", "paper", "news"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(find_prefix_words(&list).into_iter().collect::<Vec<_>>(), vec!["news"]);
+/// ```
+pub fn find_prefix_words(list: &[String]) -> HashSet<String> {
+    let mut sorted_list: Vec<&str> = list.iter().map(|word| word.as_str()).collect();
+    sorted_list.sort_unstable();
+    sorted_list.dedup();
+    sorted_list
+        .windows(2)
+        .filter(|pair| pair[1].starts_with(pair[0]))
+        .map(|pair| pair[0].to_string())
+        .collect()
+}
+
+/// Finds every word on the list that is a suffix of another word on the list,
+/// by finding prefix words among the words spelled backwards.
+/// ```
+/// use tidy_core::list_manipulations::find_suffix_words;
+/// let list: Vec<String> = ["newspaper", "paper", "news"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(find_suffix_words(&list).into_iter().collect::<Vec<_>>(), vec!["paper"]);
+/// ```
+pub fn find_suffix_words(list: &[String]) -> HashSet<String> {
+    let reversed_list: Vec<String> = list.iter().map(|word| reverse(word)).collect();
+    find_prefix_words(&reversed_list)
+        .iter()
+        .map(|word| reverse(word))
+        .collect()
+}
+
+/// Removes infix words from the list: words found anywhere inside another
+/// word on the list. This includes prefix words and suffix words.
+/// ```
+/// use tidy_core::list_manipulations::remove_infix_words;
+/// let list: Vec<String> = ["and", "candy", "can", "dye"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(remove_infix_words(&list), vec!["candy", "dye"]);
+/// ```
+pub fn remove_infix_words(list: &[String]) -> Vec<String> {
+    let infix_words = find_infix_words(list);
+    list.iter()
+        .filter(|word| !infix_words.contains(*word))
+        .cloned()
+        .collect()
+}
+
+/// Finds every word on the list that appears anywhere inside another word on
+/// the list, including at its start or end.
+///
+/// Like `find_prefix_words`, this sorts rather than comparing every word to
+/// every other word. A word is inside another word when it's a prefix of one
+/// of that word's suffixes, so we sort every suffix of every word: the
+/// suffixes starting with a given word all come together, and can be found
+/// with a binary search.
+/// ```
+/// use tidy_core::list_manipulations::find_infix_words;
+/// let list: Vec<String> = ["bandage", "and", "age", "cage"].iter().map(|w| w.to_string()).collect();
+/// let mut infix_words: Vec<String> = find_infix_words(&list).into_iter().collect();
+/// infix_words.sort();
+/// assert_eq!(infix_words, vec!["age", "and"]);
+/// ```
+pub fn find_infix_words(list: &[String]) -> HashSet<String> {
+    let mut words: Vec<&str> = list.iter().map(|word| word.as_str()).collect();
+    words.sort_unstable();
+    words.dedup();
+    // Every suffix of every word, with the index of the word it came from
+    let mut suffixes: Vec<(&str, usize)> = words
+        .iter()
+        .enumerate()
+        .flat_map(|(owner, word)| {
+            word.char_indices()
+                .map(move |(i, _c)| (word.split_at(i).1, owner))
+        })
+        .collect();
+    suffixes.sort_unstable();
+    words
+        .iter()
+        .enumerate()
+        .filter(|(owner, word)| {
+            let start = suffixes.partition_point(|(suffix, _other)| suffix < *word);
+            suffixes[start..]
+                .iter()
+                .take_while(|(suffix, _other)| suffix.starts_with(**word))
+                .any(|(_suffix, other)| other != owner)
+        })
+        .map(|(_owner, word)| word.to_string())
+        .collect()
+}
+
+fn reverse(word: &str) -> String {
+    word.chars().rev().collect()
+}
+
+/// Only retain words that are the given `minimum_edit_distance` away from all
+/// other words on the list.
+///
+/// Calulates edit distance using a function in the edit_distance module.
+/// ```
+/// use tidy_core::list_manipulations::enforce_minimum_edit_distance;
+/// let list: Vec<String> = ["cat", "bat", "horse"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(enforce_minimum_edit_distance(&list, 2), vec!["horse"]);
+/// ```
+pub fn enforce_minimum_edit_distance(list: &[String], minimum_edit_distance: usize) -> Vec<String> {
+    enforce_minimum_edit_distance_with_progress(list, minimum_edit_distance, &|_done, _total| {})
+}
+
+/// Like `enforce_minimum_edit_distance`, but calls `on_progress` with how
+/// many words have been checked and how many there are in total.
+pub fn enforce_minimum_edit_distance_with_progress(
+    list: &[String],
+    minimum_edit_distance: usize,
+    on_progress: &dyn Fn(usize, usize),
+) -> Vec<String> {
+    let minimum_edit_distance: u32 = minimum_edit_distance.try_into().unwrap();
+    let mut words_checked = 0;
+    let mut list_to_read = list.to_vec();
+    // Sort short words first to prefer them
+    list_to_read.sort_by_key(|a| count_characters(a));
+
+    let mut new_list = list.to_vec();
+    new_list.retain(|potential_too_close_word| {
+        on_progress(words_checked, list.len());
+        words_checked += 1;
+        for word in &list_to_read {
+            // Skip if we're looking at the same word
+            if word == potential_too_close_word {
+                continue;
+            }
+            if find_edit_distance(word, potential_too_close_word) < minimum_edit_distance {
+                // This potential_too_close_word is too close to another word on the list,
+                // so we do NOT want to retain it.
+                // return false to the retain
+                return false;
+            } else {
+                // This particular word is not too close to this potential_too_close_word.
+                // keep looping
+                continue;
+            };
+        }
+        // If we've made it here, we can be sure that potential_too_close_word is NOT too
+        // close to another  word. So we want to retain it for the new_list.
+        // To do this, we return true to the retain.
+        true
+    });
+    on_progress(list.len(), list.len());
+    new_list
+}
+
+/// Deprecated misspelling of `enforce_minimum_edit_distance`.
+#[deprecated(note = "Use enforce_minimum_edit_distance")]
+pub fn enfore_minimum_edit_distance(
+    list: Vec<String>,
+    minimum_edit_distance: usize,
+) -> Vec<String> {
+    enforce_minimum_edit_distance(&list, minimum_edit_distance)
+}
+
+/// Takes the inputted word list and a Vector of tuples of Strings,
+/// each representing a pair of homophones, e.g. `("there", "their")`.
+/// The function outputs a new list in which, if both homophones
+/// are detected, the second homophone is removed.
+/// ```
+/// use tidy_core::list_manipulations::remove_homophones;
+/// let list: Vec<String> = ["sun", "son", "moon"].iter().map(|w| w.to_string()).collect();
+/// let homophones = vec![("sun".to_string(), "son".to_string())];
+/// assert_eq!(remove_homophones(&list, &homophones), vec!["sun", "moon"]);
+/// ```
+pub fn remove_homophones(list: &[String], homophones: &[(String, String)]) -> Vec<String> {
+    let words_on_list: HashSet<&String> = list.iter().collect();
+    let mut words_to_remove = HashSet::new();
+    for pair_of_homophones in homophones {
+        if words_on_list.contains(&pair_of_homophones.0)
+            && words_on_list.contains(&pair_of_homophones.1)
+            && !(words_to_remove.contains(&pair_of_homophones.0)
+                || words_to_remove.contains(&pair_of_homophones.1))
+        {
+            words_to_remove.insert(&pair_of_homophones.1);
+        }
+    }
+    let mut new_list = list.to_vec();
+    new_list.retain(|w| !words_to_remove.contains(w));
+    new_list
+}
+
+/// The letters of a word, lowercased and sorted, which anagrams of each
+/// other share
+fn anagram_key(word: &str) -> Vec<char> {
+    let mut letters: Vec<char> = word.to_lowercase().chars().collect();
+    letters.sort_unstable();
+    letters
+}
+
+/// Keeps only the first of each set of words that are anagrams of each
+/// other, like "lemon" and "melon". If given a `maximum_edit_distance`, a
+/// word is only removed if it's that close to an anagram of it kept
+/// earlier in the list.
+/// ```
+/// use tidy_core::list_manipulations::remove_anagrams;
+/// let list: Vec<String> = ["lemon", "melon", "night", "thing"].iter().map(|w| w.to_string()).collect();
+/// assert_eq!(remove_anagrams(&list, None), vec!["lemon", "night"]);
+/// // "melon" is 2 edits from "lemon", but "thing" is 5 from "night"
+/// assert_eq!(remove_anagrams(&list, Some(2)), vec!["lemon", "night", "thing"]);
+/// ```
+pub fn remove_anagrams(list: &[String], maximum_edit_distance: Option<usize>) -> Vec<String> {
+    let mut kept_by_key: HashMap<Vec<char>, Vec<&String>> = HashMap::new();
+    let mut new_list = vec![];
+    for word in list {
+        let kept_anagrams = kept_by_key.entry(anagram_key(word)).or_default();
+        let collides = match maximum_edit_distance {
+            Some(maximum_edit_distance) => kept_anagrams
+                .iter()
+                .any(|kept| find_edit_distance(kept, word) as usize <= maximum_edit_distance),
+            None => !kept_anagrams.is_empty(),
+        };
+        if !collides {
+            kept_anagrams.push(word);
+            new_list.push(word.to_string());
+        }
+    }
+    new_list
+}
+
+/// Common leetspeak substitutions, each a character and the letter it
+/// stands for, like the "4" of "p4ss"
+pub const DEFAULT_LEET_SUBSTITUTIONS: [(char, char); 10] = [
+    ('4', 'a'),
+    ('@', 'a'),
+    ('8', 'b'),
+    ('3', 'e'),
+    ('1', 'i'),
+    ('!', 'i'),
+    ('0', 'o'),
+    ('5', 's'),
+    ('$', 's'),
+    ('7', 't'),
+];
+
+/// Undoes the given leetspeak substitutions in a word, and lowercases it,
+/// so that variants of a word can be compared to it.
+/// ```
+/// use tidy_core::list_manipulations::{undo_leet, DEFAULT_LEET_SUBSTITUTIONS};
+/// assert_eq!(undo_leet("P4$$w0rd", &DEFAULT_LEET_SUBSTITUTIONS), "password");
+/// ```
+pub fn undo_leet(word: &str, substitutions: &[(char, char)]) -> String {
+    word.chars()
+        .map(|c| {
+            substitutions
+                .iter()
+                .find(|(leet, _letter)| *leet == c)
+                .map_or(c, |(_leet, letter)| *letter)
+        })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// Removes words that are leetspeak variants of other words, like "p4ss" of
+/// "pass": words with any of the substituted characters in them that read
+/// as a word on the list, or a word on the reject list, once the
+/// substitutions are undone (ignoring case). Of variants of a word that
+/// isn't on either list, like "p4ss" and "pa55" without "pass", the first
+/// is kept.
+/// ```
+/// use std::collections::HashSet;
+/// use tidy_core::list_manipulations::{remove_leet_variants, DEFAULT_LEET_SUBSTITUTIONS};
+/// let list: Vec<String> = ["p4ss", "pass", "pa$$", "b0ss", "h3llo", "h3ll0", "w0rd"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// let reject_list: HashSet<String> = HashSet::from(["boss".to_string()]);
+/// assert_eq!(
+///     remove_leet_variants(&list, Some(&reject_list), &DEFAULT_LEET_SUBSTITUTIONS),
+///     vec!["pass", "h3llo", "w0rd"]
+/// );
+/// ```
+pub fn remove_leet_variants(
+    list: &[String],
+    reject_list: Option<&HashSet<String>>,
+    substitutions: &[(char, char)],
+) -> Vec<String> {
+    let has_substitutions = |word: &str| {
+        word.chars()
+            .any(|c| substitutions.iter().any(|(leet, _letter)| *leet == c))
+    };
+    // Words without substitutions are never removed, so any variant of one
+    // is removed, even a variant that comes before it on the list
+    let mut taken: HashSet<String> = list
+        .iter()
+        .filter(|word| !has_substitutions(word))
+        .map(|word| word.to_lowercase())
+        .collect();
+    if let Some(reject_list) = reject_list {
+        taken.extend(
+            reject_list
+                .iter()
+                .map(|word| undo_leet(word, substitutions)),
+        );
+    }
+    list.iter()
+        .filter(|word| !has_substitutions(word) || taken.insert(undo_leet(word, substitutions)))
+        .map(|word| word.to_string())
+        .collect()
+}
+
+/// Tries removing prefix words, removing suffix words, and doing a Schlinkert
+/// prune on the given list -- each of which make the list uniquely decodable
+/// -- and returns the name of the method that kept the most words, along with
+/// the resulting list. Also returns the number of words each method kept, for
+/// comparison. Ties go to the simpler method.
+/// ```
+/// use tidy_core::list_manipulations::best_prune;
+/// use tidy_core::sardinas_patterson_pruning::SchlinkertStrategy;
+/// let list: Vec<String> = ["news", "newspaper", "paper", "elephant"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// let (method, new_list, comparison) = best_prune(&list, SchlinkertStrategy::All);
+/// assert_eq!(method, "remove prefix words");
+/// assert_eq!(new_list, vec!["newspaper", "paper", "elephant"]);
+/// assert_eq!(comparison.len(), 3);
+/// ```
+pub fn best_prune(
+    list: &[String],
+    schlinkert_strategy: SchlinkertStrategy,
+) -> (&'static str, Vec<String>, Vec<(&'static str, usize)>) {
+    let candidates = [
+        ("remove prefix words", remove_prefix_words(list)),
+        ("remove suffix words", remove_suffix_words(list)),
+        (
+            "Schlinkert prune",
+            schlinkert_prune(list, schlinkert_strategy),
+        ),
+    ];
+    let comparison = candidates
+        .iter()
+        .map(|(method, new_list)| (*method, new_list.len()))
+        .collect();
+    // max_by_key returns the last of equal elements, so search in reverse
+    let (method, new_list) = candidates
+        .into_iter()
+        .rev()
+        .max_by_key(|(_method, new_list)| new_list.len())
+        .unwrap();
+    (method, new_list, comparison)
+}
@@ -0,0 +1,264 @@
+//! Named steps of the tidying pipeline, and the order to run them in
+use std::str::FromStr;
+
+/// One of the modifications or removals Tidy can make to a list. A step
+/// only does anything if the user requested it (see `is_requested` in
+/// lib.rs).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PipelineStep {
+    // Steps done to each word independently
+    Field,
+    DeleteBefore,
+    DeleteAfter,
+    DeleteIntegers,
+    DeleteNonalphanumeric,
+    Lowercase,
+    StraightenQuotes,
+    DeleteApostrophes,
+    NormalizeHyphens,
+    JoinMultiword,
+    RemoveNonascii,
+    RemoveNonalphanumeric,
+    RemoveNonalphabetic,
+    RemoveNonLatinAlphabetic,
+    RemoveMixedScript,
+    RemoveMultiword,
+    RemoveIntegers,
+    RemoveConfusableChars,
+    RemoveDoubledLetters,
+    RemoveConsonantClusters,
+    RemoveGibberish,
+    Reject,
+    Approve,
+    MinimumLength,
+    MaximumLength,
+    // Steps that depend on the list as a whole
+    Homophones,
+    RemoveAnagrams,
+    RemoveLeetVariants,
+    RemoveInflections,
+    Lemmatize,
+    SharedPrefix,
+    TruncateToUnique,
+    EditDistance,
+    RemoveSuffixWords,
+    RemovePrefixWords,
+    RemoveInfixWords,
+    RemoveCompoundCollisions,
+    BestPrune,
+    SchlinkertPrune,
+    Dedup,
+    Capitalize,
+    FirstLetters,
+    LengthQuota,
+    AlwaysInclude,
+    PrintFirst,
+    PrintRand,
+    CutToPower,
+    Sort,
+    UpdateFrom,
+}
+
+/// The order Tidy runs steps in, unless told otherwise. Word modifications
+/// come before word removals, which come before the steps that depend on
+/// the list as a whole.
+pub const DEFAULT_PIPELINE: [PipelineStep; 49] = [
+    PipelineStep::Field,
+    PipelineStep::DeleteBefore,
+    PipelineStep::DeleteAfter,
+    PipelineStep::DeleteIntegers,
+    PipelineStep::DeleteNonalphanumeric,
+    PipelineStep::Lowercase,
+    PipelineStep::StraightenQuotes,
+    PipelineStep::DeleteApostrophes,
+    PipelineStep::NormalizeHyphens,
+    PipelineStep::JoinMultiword,
+    PipelineStep::RemoveNonascii,
+    PipelineStep::RemoveNonalphanumeric,
+    PipelineStep::RemoveNonalphabetic,
+    PipelineStep::RemoveNonLatinAlphabetic,
+    PipelineStep::RemoveMixedScript,
+    PipelineStep::RemoveMultiword,
+    PipelineStep::RemoveIntegers,
+    PipelineStep::RemoveConfusableChars,
+    PipelineStep::RemoveDoubledLetters,
+    PipelineStep::RemoveConsonantClusters,
+    PipelineStep::RemoveGibberish,
+    PipelineStep::Reject,
+    PipelineStep::Approve,
+    PipelineStep::MinimumLength,
+    PipelineStep::MaximumLength,
+    PipelineStep::Homophones,
+    PipelineStep::RemoveAnagrams,
+    PipelineStep::RemoveLeetVariants,
+    PipelineStep::RemoveInflections,
+    PipelineStep::Lemmatize,
+    PipelineStep::SharedPrefix,
+    PipelineStep::TruncateToUnique,
+    PipelineStep::EditDistance,
+    PipelineStep::RemoveSuffixWords,
+    PipelineStep::RemovePrefixWords,
+    PipelineStep::RemoveInfixWords,
+    PipelineStep::RemoveCompoundCollisions,
+    PipelineStep::BestPrune,
+    PipelineStep::SchlinkertPrune,
+    PipelineStep::Dedup,
+    PipelineStep::Capitalize,
+    PipelineStep::FirstLetters,
+    PipelineStep::LengthQuota,
+    PipelineStep::AlwaysInclude,
+    PipelineStep::PrintFirst,
+    PipelineStep::PrintRand,
+    PipelineStep::CutToPower,
+    PipelineStep::Sort,
+    PipelineStep::UpdateFrom,
+];
+
+impl PipelineStep {
+    /// The name users give this step in a `--pipeline`
+    pub fn name(&self) -> &'static str {
+        match self {
+            PipelineStep::Field => "field",
+            PipelineStep::DeleteBefore => "delete-before",
+            PipelineStep::DeleteAfter => "delete-after",
+            PipelineStep::DeleteIntegers => "delete-integers",
+            PipelineStep::DeleteNonalphanumeric => "delete-nonalphanumeric",
+            PipelineStep::Lowercase => "lowercase",
+            PipelineStep::StraightenQuotes => "straighten-quotes",
+            PipelineStep::DeleteApostrophes => "delete-apostrophes",
+            PipelineStep::NormalizeHyphens => "normalize-hyphens",
+            PipelineStep::JoinMultiword => "join-multiword",
+            PipelineStep::RemoveNonascii => "remove-nonascii",
+            PipelineStep::RemoveNonalphanumeric => "remove-nonalphanumeric",
+            PipelineStep::RemoveNonalphabetic => "remove-nonalphabetic",
+            PipelineStep::RemoveNonLatinAlphabetic => "remove-non-latin-alphabetic",
+            PipelineStep::RemoveMixedScript => "remove-mixed-script",
+            PipelineStep::RemoveMultiword => "remove-multiword",
+            PipelineStep::RemoveIntegers => "remove-integers",
+            PipelineStep::RemoveConfusableChars => "remove-confusable-chars",
+            PipelineStep::RemoveDoubledLetters => "remove-doubled-letters",
+            PipelineStep::RemoveConsonantClusters => "remove-consonant-clusters",
+            PipelineStep::RemoveGibberish => "remove-gibberish",
+            PipelineStep::Reject => "reject",
+            PipelineStep::Approve => "approve",
+            PipelineStep::MinimumLength => "minimum-length",
+            PipelineStep::MaximumLength => "maximum-length",
+            PipelineStep::Homophones => "homophones",
+            PipelineStep::RemoveAnagrams => "remove-anagrams",
+            PipelineStep::RemoveLeetVariants => "remove-leet-variants",
+            PipelineStep::RemoveInflections => "remove-inflections",
+            PipelineStep::Lemmatize => "lemmatize",
+            PipelineStep::SharedPrefix => "shared-prefix",
+            PipelineStep::TruncateToUnique => "truncate-to-unique",
+            PipelineStep::EditDistance => "edit-distance",
+            PipelineStep::RemoveSuffixWords => "remove-suffix-words",
+            PipelineStep::RemovePrefixWords => "remove-prefix-words",
+            PipelineStep::RemoveInfixWords => "remove-infix-words",
+            PipelineStep::RemoveCompoundCollisions => "remove-compound-collisions",
+            PipelineStep::BestPrune => "best-prune",
+            PipelineStep::SchlinkertPrune => "schlinkert-prune",
+            PipelineStep::Dedup => "dedup",
+            PipelineStep::Capitalize => "capitalize",
+            PipelineStep::FirstLetters => "max-per-first-letter",
+            PipelineStep::LengthQuota => "length-quota",
+            PipelineStep::AlwaysInclude => "always-include",
+            PipelineStep::PrintFirst => "print-first",
+            PipelineStep::PrintRand => "print-rand",
+            PipelineStep::CutToPower => "cut-to-power",
+            PipelineStep::Sort => "sort",
+            PipelineStep::UpdateFrom => "update-from",
+        }
+    }
+
+    /// Whether this step is done to each word independently, rather than
+    /// depending on the list as a whole.
+    pub fn is_word_step(&self) -> bool {
+        let first_list_step = DEFAULT_PIPELINE
+            .iter()
+            .position(|step| *step == PipelineStep::Homophones)
+            .unwrap();
+        DEFAULT_PIPELINE[..first_list_step].contains(self)
+    }
+
+    /// Whether this step changes each word, rather than deciding whether to
+    /// keep it. Only word steps change words this way.
+    pub fn is_word_modification(&self) -> bool {
+        let first_removal = DEFAULT_PIPELINE
+            .iter()
+            .position(|step| *step == PipelineStep::RemoveNonascii)
+            .unwrap();
+        DEFAULT_PIPELINE[..first_removal].contains(self)
+    }
+}
+
+impl FromStr for PipelineStep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        DEFAULT_PIPELINE
+            .iter()
+            .find(|step| step.name() == s)
+            .copied()
+            .ok_or_else(|| {
+                format!(
+                    "Unknown pipeline step {}. Steps are: {}.",
+                    s,
+                    DEFAULT_PIPELINE
+                        .iter()
+                        .map(|step| step.name())
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+/// Turns a user-given order of steps into a full pipeline. Steps the user
+/// didn't name keep their default order, after the named steps of the same
+/// kind (word or whole-list).
+///
+/// Errors if a step is named twice, or if a word step is named after a
+/// whole-list step, since every word is modified and removed before the
+/// list as a whole is worked on.
+/// ```
+/// use tidy_core::pipeline::{complete_pipeline, PipelineStep};
+/// let pipeline = complete_pipeline(&[PipelineStep::Reject, PipelineStep::Lowercase]).unwrap();
+/// assert_eq!(pipeline[0], PipelineStep::Reject);
+/// assert_eq!(pipeline[1], PipelineStep::Lowercase);
+/// assert_eq!(pipeline[2], PipelineStep::Field);
+/// assert!(complete_pipeline(&[PipelineStep::Sort, PipelineStep::Lowercase]).is_err());
+/// ```
+pub fn complete_pipeline(given_steps: &[PipelineStep]) -> Result<Vec<PipelineStep>, String> {
+    for (i, step) in given_steps.iter().enumerate() {
+        if given_steps[..i].contains(step) {
+            return Err(format!(
+                "Error: Pipeline step {} is listed more than once.",
+                step.name()
+            ));
+        }
+        if step.is_word_step() {
+            if let Some(list_step) = given_steps[..i].iter().find(|s| !s.is_word_step()) {
+                return Err(format!(
+                    "Error: Pipeline step {} must come before {}. Steps done to each word run before steps that depend on the whole list.",
+                    step.name(),
+                    list_step.name()
+                ));
+            }
+        }
+    }
+    let mut pipeline = vec![];
+    for is_word_step in [true, false] {
+        pipeline.extend(
+            given_steps
+                .iter()
+                .filter(|step| step.is_word_step() == is_word_step),
+        );
+        pipeline.extend(
+            DEFAULT_PIPELINE
+                .iter()
+                .filter(|step| step.is_word_step() == is_word_step && !given_steps.contains(step)),
+        );
+    }
+    Ok(pipeline)
+}
@@ -0,0 +1,39 @@
+//! A hook for reporting progress through Tidy's slower operations
+use std::fmt;
+use std::sync::Arc;
+
+/// Called with the name of a slow task (like "Schlinkert prune"), how much
+/// of it is done, and how much there is to do in total, so that callers can
+/// show a progress bar. The total may change as the task goes on. Tidy calls
+/// it with `done == total` when the task is finished.
+/// ```
+/// use std::sync::{Arc, Mutex};
+/// use tidy_core::progress::ProgressCallback;
+/// let updates = Arc::new(Mutex::new(vec![]));
+/// let updates_to_push_to = updates.clone();
+/// let progress = ProgressCallback::new(move |task, done, total| {
+///     updates_to_push_to.lock().unwrap().push((task.to_string(), done, total));
+/// });
+/// progress.report("Whittling", 1, 4);
+/// assert_eq!(updates.lock().unwrap()[0], ("Whittling".to_string(), 1, 4));
+/// ```
+#[derive(Clone)]
+pub struct ProgressCallback(Arc<ProgressFn>);
+
+type ProgressFn = dyn Fn(&str, usize, usize) + Send + Sync;
+
+impl ProgressCallback {
+    pub fn new(callback: impl Fn(&str, usize, usize) + Send + Sync + 'static) -> Self {
+        ProgressCallback(Arc::new(callback))
+    }
+
+    pub fn report(&self, task: &str, done: usize, total: usize) {
+        (self.0)(task, done, total)
+    }
+}
+
+impl fmt::Debug for ProgressCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ProgressCallback")
+    }
+}
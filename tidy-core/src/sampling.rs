@@ -0,0 +1,145 @@
+//! Random sampling, for taking or printing a random selection of words
+//! (`take_rand`, `print_rand`, and a random `cut_to_power`).
+//!
+//! Each `RandStrategy` uses a different, well-known algorithm, described
+//! on its variant. Given a `rand_seed`, a request makes the same random
+//! choices every time, as long as it's run by the same version of Tidy.
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::str::FromStr;
+
+/// How a random selection of words is picked
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum RandStrategy {
+    /// Shuffle every word with a Fisher–Yates shuffle, then keep the first
+    /// words. Every selection is equally likely, and the picked words come
+    /// in random order.
+    #[default]
+    Shuffle,
+    /// Reservoir sampling (Vitter's Algorithm R): a single pass over the
+    /// words that only holds on to the words picked so far, so it works on
+    /// streams too long to hold in memory (see `reservoir_sample`). Every
+    /// selection is equally likely, but the picked words aren't in a random
+    /// order.
+    Reservoir,
+    /// Systematic sampling: starting from a random word near the top of the
+    /// list, take words at evenly spaced intervals. Picked words keep their
+    /// order, and are spread evenly through the list, so a list sorted by
+    /// frequency keeps a mix of common and rare words. Not every selection
+    /// is possible.
+    Stride,
+}
+
+impl FromStr for RandStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "shuffle" => Ok(RandStrategy::Shuffle),
+            "reservoir" => Ok(RandStrategy::Reservoir),
+            "stride" => Ok(RandStrategy::Stride),
+            _ => Err(format!(
+                "Unknown random strategy {}. Use shuffle, reservoir, or stride.",
+                s
+            )),
+        }
+    }
+}
+
+/// Makes the random number generator for a request: seeded with `seed`,
+/// if there is one, so its choices can be made again, or from the
+/// operating system (or browser) if not.
+pub fn make_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Picks `amount` of `items` at random, using the given strategy. If there
+/// aren't more than `amount` items, all of them are kept.
+/// ```
+/// use tidy_core::sampling::{make_rng, sample, RandStrategy};
+/// let items: Vec<usize> = (0..100).collect();
+/// let mut rng = make_rng(Some(7));
+/// let picked = sample(&items, 10, RandStrategy::Stride, &mut rng);
+/// assert_eq!(picked.len(), 10);
+/// // Strides keep the items' order, 10 items apart
+/// assert!(picked.windows(2).all(|pair| pair[1] - pair[0] == 10));
+/// // The same seed picks the same items
+/// assert_eq!(
+///     sample(&items, 10, RandStrategy::Shuffle, &mut make_rng(Some(7))),
+///     sample(&items, 10, RandStrategy::Shuffle, &mut make_rng(Some(7)))
+/// );
+/// ```
+pub fn sample<T: Clone, R: Rng>(
+    items: &[T],
+    amount: usize,
+    strategy: RandStrategy,
+    rng: &mut R,
+) -> Vec<T> {
+    match strategy {
+        RandStrategy::Shuffle => {
+            let mut items = items.to_vec();
+            items.shuffle(rng);
+            items.truncate(amount);
+            items
+        }
+        RandStrategy::Reservoir => reservoir_sample(items.iter().cloned(), amount, rng),
+        RandStrategy::Stride => stride_sample(items, amount, rng),
+    }
+}
+
+/// Picks `amount` items at random from an iterator of any length, in a
+/// single pass, holding on to no more than `amount` items at a time
+/// (Algorithm R). Useful for sampling lines as they're read from a file.
+/// ```
+/// use tidy_core::sampling::{make_rng, reservoir_sample};
+/// let picked = reservoir_sample(0..1_000_000, 5, &mut make_rng(None));
+/// assert_eq!(picked.len(), 5);
+/// // Asking for more items than there are keeps them all
+/// assert_eq!(reservoir_sample(0..3, usize::MAX, &mut make_rng(None)).len(), 3);
+/// ```
+pub fn reservoir_sample<T, R: Rng>(
+    items: impl IntoIterator<Item = T>,
+    amount: usize,
+    rng: &mut R,
+) -> Vec<T> {
+    let items = items.into_iter();
+    // The amount can be far more than there are items, so don't reserve
+    // room for more items than the iterator says it has
+    let (lower, upper) = items.size_hint();
+    let mut reservoir = Vec::with_capacity(amount.min(upper.unwrap_or(lower)));
+    for (i, item) in items.enumerate() {
+        if i < amount {
+            reservoir.push(item);
+        } else {
+            // Keep the i-th item with probability amount / (i + 1)
+            let j = rng.gen_range(0..=i);
+            if j < amount {
+                reservoir[j] = item;
+            }
+        }
+    }
+    reservoir
+}
+
+/// Picks `amount` items at evenly spaced intervals, starting at a random
+/// item within the first interval
+fn stride_sample<T: Clone, R: Rng>(items: &[T], amount: usize, rng: &mut R) -> Vec<T> {
+    if amount >= items.len() {
+        return items.to_vec();
+    }
+    if amount == 0 {
+        return vec![];
+    }
+    let stride = items.len() as f64 / amount as f64;
+    let start = rng.gen::<f64>() * stride;
+    (0..amount)
+        .map(|i| {
+            let position = (start + i as f64 * stride) as usize;
+            items[position.min(items.len() - 1)].clone()
+        })
+        .collect()
+}
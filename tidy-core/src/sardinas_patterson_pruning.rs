@@ -0,0 +1,142 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+/// Ways to choose which words to remove when doing a Schlinkert prune.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SchlinkertStrategy {
+    /// Remove every word the Sardinas-Patterson algorithm flags, all at once.
+    /// The fastest strategy.
+    #[default]
+    All,
+    /// Remove flagged words one at a time, longest first, checking the list
+    /// again after each removal.
+    Longest,
+    /// Remove flagged words one at a time, starting with the one closest to
+    /// the end of the list, checking the list again after each removal.
+    /// Assumes the inputted list is sorted by word frequency.
+    LowestFrequency,
+    /// Best-effort attempt to remove as few words as possible: remove every
+    /// flagged word, then add back each one that doesn't make the list
+    /// ambiguous again. The slowest strategy.
+    Fewest,
+}
+
+impl FromStr for SchlinkertStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(SchlinkertStrategy::All),
+            "longest" => Ok(SchlinkertStrategy::Longest),
+            "lowest-frequency" | "frequency" => Ok(SchlinkertStrategy::LowestFrequency),
+            "fewest" => Ok(SchlinkertStrategy::Fewest),
+            _ => Err(format!(
+                "Unknown Schlinkert strategy {}. Use all, longest, lowest-frequency, or fewest.",
+                s
+            )),
+        }
+    }
+}
+
+// Return a Vector of words that "caused" the Sardinas-
+// Patterson algorithm to determine that this list was not
+// uniquely decodable.
+// These "offending" words can then be removed from the original
+// list to, theoretically, make the list uniquely decodable.
+pub fn get_sardinas_patterson_final_intersection(c: &[String]) -> Vec<String> {
+    get_sardinas_patterson_final_intersection_until(c, &|| false).unwrap()
+}
+
+/// Like `get_sardinas_patterson_final_intersection`, but checks
+/// `should_stop` as it goes, and gives up, returning `None`, if it returns
+/// true (like when a timeout passes).
+pub fn get_sardinas_patterson_final_intersection_until(
+    c: &[String],
+    should_stop: &dyn Fn() -> bool,
+) -> Option<Vec<String>> {
+    // Right off the bat, convert inputted Slice to a HashSet
+    // Since we always want this list to be unique, and we're
+    // going to eventually calculate a disjoint boolean!
+    let c = vec_to_hash(c);
+    let c_infinity = generate_c_infinity_with_a_halt_break(c.clone(), should_stop)?;
+    // We want to collect a list of words that "caused" the Sardinas-
+    // Patterson algorithm to determine that this list was not
+    // uniquely decodable.
+    // If the given list is in fact uniquely decodable,
+    // this list of words will be empty, which is what we want.
+    // If there are words in the list, we'll return those to src/lib to be
+    // removed from the final list.
+    let final_intersection = c.intersection(&c_infinity);
+    Some(
+        Vec::from_iter(final_intersection)
+            .iter()
+            .map(|w| w.to_string())
+            .collect(),
+    )
+}
+
+fn vec_to_hash(v: &[String]) -> HashSet<String> {
+    let mut my_hash = HashSet::new();
+    for e in v {
+        my_hash.insert(e.to_string());
+    }
+    my_hash
+}
+
+// Generate c for any number n, or None if told to stop
+fn generate_cn(
+    c: &HashSet<String>,
+    n: usize,
+    should_stop: &dyn Fn() -> bool,
+) -> Option<HashSet<String>> {
+    if n == 0 {
+        Some(c.to_owned())
+    } else {
+        let mut cn = HashSet::new();
+
+        // generate c_(n-1)
+        let cn_minus_1 = generate_cn(c, n - 1, should_stop)?;
+        for w1 in c.iter() {
+            if should_stop() {
+                return None;
+            }
+            for w2 in cn_minus_1.iter() {
+                if w1.len() > w2.len() && w1.starts_with(w2) {
+                    // w2 is a prefix word of w1
+                    // so, we're going to add the dangling suffix to a new HashSet
+                    // called cn
+                    cn.insert(w1[w2.len()..].to_string());
+                } else if w1.len() < w2.len() && w2.starts_with(w1) {
+                    // w2 is a prefix word of w1
+                    // so, we're going to add the dangling suffix to a new HashSet
+                    // called cn
+                    cn.insert(w2[w1.len()..].to_string());
+                }
+            }
+        }
+        Some(cn)
+    }
+}
+
+fn generate_c_infinity_with_a_halt_break(
+    c: HashSet<String>,
+    should_stop: &dyn Fn() -> bool,
+) -> Option<HashSet<String>> {
+    let mut cs = HashSet::new();
+    let mut c_infinity = HashSet::new();
+    let mut n = 1;
+    let mut cn = generate_cn(&c, n, should_stop)?;
+
+    while !cn.is_empty() {
+        if cn.is_subset(&cs) {
+            // Cycle detected. Halting algorithm.
+            break;
+        } else {
+            cs = cs.union(&cn).map(|e| e.to_string()).collect();
+            c_infinity = c_infinity.union(&cn).map(|e| e.to_string()).collect();
+            n += 1;
+            cn = generate_cn(&c, n, should_stop)?;
+        }
+    }
+    Some(c_infinity)
+}
@@ -0,0 +1,100 @@
+//! Collapse words that share a stem, like "connect", "connected", and
+//! "connection", using Snowball stemmers. Needs the `stemming` feature.
+use crate::error::TidyError;
+#[cfg(feature = "stemming")]
+use rust_stemmers::{Algorithm, Stemmer};
+#[cfg(feature = "stemming")]
+use std::collections::HashSet;
+
+/// Languages there are stemmers for
+pub const STEMMER_LANGUAGES: [&str; 18] = [
+    "arabic",
+    "danish",
+    "dutch",
+    "english",
+    "finnish",
+    "french",
+    "german",
+    "greek",
+    "hungarian",
+    "italian",
+    "norwegian",
+    "portuguese",
+    "romanian",
+    "russian",
+    "spanish",
+    "swedish",
+    "tamil",
+    "turkish",
+];
+
+#[cfg(feature = "stemming")]
+fn make_stemmer(language: &str) -> Result<Stemmer, TidyError> {
+    let algorithm = match language.to_lowercase().as_str() {
+        "arabic" => Algorithm::Arabic,
+        "danish" => Algorithm::Danish,
+        "dutch" => Algorithm::Dutch,
+        "english" => Algorithm::English,
+        "finnish" => Algorithm::Finnish,
+        "french" => Algorithm::French,
+        "german" => Algorithm::German,
+        "greek" => Algorithm::Greek,
+        "hungarian" => Algorithm::Hungarian,
+        "italian" => Algorithm::Italian,
+        "norwegian" => Algorithm::Norwegian,
+        "portuguese" => Algorithm::Portuguese,
+        "romanian" => Algorithm::Romanian,
+        "russian" => Algorithm::Russian,
+        "spanish" => Algorithm::Spanish,
+        "swedish" => Algorithm::Swedish,
+        "tamil" => Algorithm::Tamil,
+        "turkish" => Algorithm::Turkish,
+        _ => return Err(TidyError::UnknownStemmerLanguage(language.to_string())),
+    };
+    Ok(Stemmer::create(algorithm))
+}
+
+/// Checks that Tidy can stem words of the given language
+pub fn validate_stemmer_language(language: &str) -> Result<(), TidyError> {
+    #[cfg(feature = "stemming")]
+    {
+        make_stemmer(language).map(|_stemmer| ())
+    }
+    #[cfg(not(feature = "stemming"))]
+    {
+        let _ = language;
+        Err(TidyError::StemmingNotEnabled)
+    }
+}
+
+/// Keeps only the first word of each set of words on the list that share a
+/// stem in the given language, so the list doesn't have several forms of the
+/// same word. Unlike the stems themselves, the kept words are real words.
+/// ```
+/// # #[cfg(feature = "stemming")]
+/// # {
+/// use tidy_core::stemming::collapse_by_stem;
+/// let list: Vec<String> = ["connect", "zoo", "connected", "connection", "zoos"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// assert_eq!(collapse_by_stem(&list, "english").unwrap(), vec!["connect", "zoo"]);
+/// # }
+/// ```
+pub fn collapse_by_stem(list: &[String], language: &str) -> Result<Vec<String>, TidyError> {
+    #[cfg(feature = "stemming")]
+    {
+        let stemmer = make_stemmer(language)?;
+        let mut seen_stems = HashSet::new();
+        Ok(list
+            .iter()
+            .filter(|word| seen_stems.insert(stemmer.stem(&word.to_lowercase()).to_string()))
+            .cloned()
+            .collect())
+    }
+    #[cfg(not(feature = "stemming"))]
+    {
+        let _ = (list, language);
+        Err(TidyError::StemmingNotEnabled)
+    }
+}
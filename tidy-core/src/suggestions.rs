@@ -0,0 +1,189 @@
+//! Suggest which single filter to relax to get a list closer to a target
+//! length, by tidying the list again with each filter relaxed
+use crate::{tidy_lines_explained, TidyRequest};
+
+/// Each way of relaxing one filter of the request: a description of the
+/// change, named by the command-line option it changes, and the relaxed
+/// request. Filters the request doesn't use aren't relaxed.
+/// ```
+/// use tidy_core::suggestions::find_relaxations;
+/// use tidy_core::TidyRequest;
+/// let req = TidyRequest {
+///     minimum_length: Some(4),
+///     should_remove_prefix_words: true,
+///     ..Default::default()
+/// };
+/// let relaxations: Vec<String> = find_relaxations(&req)
+///     .into_iter()
+///     .map(|(description, _relaxed)| description)
+///     .collect();
+/// assert_eq!(
+///     relaxations,
+///     vec!["--minimum-word-length 3", "without --remove-prefix"]
+/// );
+/// ```
+pub fn find_relaxations(req: &TidyRequest) -> Vec<(String, TidyRequest)> {
+    let mut relaxations = vec![];
+    let mut relax = |description: String, change: &dyn Fn(&mut TidyRequest)| {
+        let mut relaxed = req.clone();
+        change(&mut relaxed);
+        relaxations.push((description, relaxed));
+    };
+    if let Some(minimum_length) = req.minimum_length {
+        if minimum_length > 1 {
+            relax(
+                format!("--minimum-word-length {}", minimum_length - 1),
+                &|relaxed| relaxed.minimum_length = Some(minimum_length - 1),
+            );
+        }
+    }
+    if let Some(maximum_length) = req.maximum_length {
+        relax(
+            format!("--maximum-word-length {}", maximum_length + 1),
+            &|relaxed| relaxed.maximum_length = Some(maximum_length + 1),
+        );
+    }
+    if let Some(minimum_edit_distance) = req.minimum_edit_distance {
+        if minimum_edit_distance > 1 {
+            relax(
+                format!("--minimum-edit-distance {}", minimum_edit_distance - 1),
+                &|relaxed| relaxed.minimum_edit_distance = Some(minimum_edit_distance - 1),
+            );
+        }
+        relax("without --minimum-edit-distance".to_string(), &|relaxed| {
+            relaxed.minimum_edit_distance = None
+        });
+    }
+    if let Some(shared_prefix_length) = req.maximum_shared_prefix_length {
+        relax(
+            format!("--shared-prefix-length {}", shared_prefix_length + 1),
+            &|relaxed| relaxed.maximum_shared_prefix_length = Some(shared_prefix_length + 1),
+        );
+    }
+    if let Some(max_per_first_letter) = req.max_per_first_letter {
+        relax("without --max-per-first-letter".to_string(), &|relaxed| {
+            relaxed.max_per_first_letter = None
+        });
+        relax(
+            format!("--max-per-first-letter {}", max_per_first_letter * 2),
+            &|relaxed| relaxed.max_per_first_letter = Some(max_per_first_letter * 2),
+        );
+    }
+    // Options that simply remove words can be left off
+    let removals: [(&str, bool, fn(&mut TidyRequest)); 18] = [
+        ("--remove-prefix", req.should_remove_prefix_words, |r| {
+            r.should_remove_prefix_words = false
+        }),
+        ("--remove-suffix", req.should_remove_suffix_words, |r| {
+            r.should_remove_suffix_words = false
+        }),
+        ("--remove-infix-words", req.should_remove_infix_words, |r| {
+            r.should_remove_infix_words = false
+        }),
+        (
+            "--remove-compound-collisions",
+            req.should_remove_compound_collisions,
+            |r| r.should_remove_compound_collisions = false,
+        ),
+        ("--schlinkert-prune", req.should_schlinkert_prune, |r| {
+            r.should_schlinkert_prune = false
+        }),
+        (
+            "--remove-nonalphanumeric",
+            req.should_remove_nonalphanumeric,
+            |r| r.should_remove_nonalphanumeric = false,
+        ),
+        (
+            "--remove-nonalphabetic",
+            req.should_remove_nonalphabetic,
+            |r| r.should_remove_nonalphabetic = false,
+        ),
+        (
+            "--remove-non-latin-alphabetic",
+            req.should_remove_non_latin_alphabetic,
+            |r| r.should_remove_non_latin_alphabetic = false,
+        ),
+        ("--remove-nonascii", req.should_remove_nonascii, |r| {
+            r.should_remove_nonascii = false
+        }),
+        ("--remove-integers", req.should_remove_integers, |r| {
+            r.should_remove_integers = false
+        }),
+        (
+            "--remove-confusable-chars",
+            req.confusable_chars.is_some(),
+            |r| r.confusable_chars = None,
+        ),
+        (
+            "--remove-doubled-letters",
+            req.should_remove_doubled_letters,
+            |r| r.should_remove_doubled_letters = false,
+        ),
+        (
+            "--remove-consonant-clusters",
+            req.consonant_clusters.is_some(),
+            |r| r.consonant_clusters = None,
+        ),
+        ("--remove-anagrams", req.should_remove_anagrams, |r| {
+            r.should_remove_anagrams = false
+        }),
+        ("--remove-inflections", req.should_remove_inflections, |r| {
+            r.should_remove_inflections = false
+        }),
+        ("--length-quota", !req.length_quotas.is_empty(), |r| {
+            r.length_quotas.clear()
+        }),
+        ("--reject", req.reject_list.is_some(), |r| {
+            r.reject_list = None
+        }),
+        ("--homophones", req.homophones_list.is_some(), |r| {
+            r.homophones_list = None
+        }),
+    ];
+    for (option, is_used, leave_off) in removals {
+        if is_used {
+            relax(format!("without {}", option), &leave_off);
+        }
+    }
+    relaxations
+}
+
+/// Tidies `lines` once with each of the request's `find_relaxations`, and
+/// returns the relaxations that make a longer list than `list_length`
+/// words, with the length of the list each makes, longest first. Trial runs
+/// don't report progress.
+/// ```
+/// use tidy_core::suggestions::suggest_relaxations;
+/// use tidy_core::TidyRequest;
+/// let lines = vec!["a", "to", "cat", "wolf", "horse"];
+/// let req = TidyRequest {
+///     minimum_length: Some(3),
+///     maximum_length: Some(4),
+///     ..Default::default()
+/// };
+/// assert_eq!(
+///     suggest_relaxations(&lines, &req, 2),
+///     vec![
+///         ("--minimum-word-length 2".to_string(), 3),
+///         ("--maximum-word-length 5".to_string(), 3),
+///     ]
+/// );
+/// ```
+pub fn suggest_relaxations(
+    lines: &[&str],
+    req: &TidyRequest,
+    list_length: usize,
+) -> Vec<(String, usize)> {
+    let mut suggestions: Vec<(String, usize)> = find_relaxations(req)
+        .into_iter()
+        .filter_map(|(description, mut relaxed)| {
+            relaxed.progress = None;
+            let (list, _report) = tidy_lines_explained(lines, &relaxed).ok()?;
+            Some((description, list.len()))
+        })
+        .filter(|(_description, relaxed_length)| *relaxed_length > list_length)
+        .collect();
+    // Stable, so ties stay in the order of `find_relaxations`
+    suggestions.sort_by(|a, b| b.1.cmp(&a.1));
+    suggestions
+}
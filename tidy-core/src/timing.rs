@@ -0,0 +1,20 @@
+//! A clock for timing Tidy's steps. WebAssembly in the browser has no
+//! `std::time::Instant`, so there the clock always reads zero: steps are
+//! logged as taking no time, and timeouts never pass.
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+pub use std::time::Instant;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[derive(Debug, Clone, Copy)]
+pub struct Instant;
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+impl Instant {
+    pub fn now() -> Self {
+        Instant
+    }
+
+    pub fn elapsed(&self) -> std::time::Duration {
+        std::time::Duration::ZERO
+    }
+}
@@ -0,0 +1,195 @@
+/// This is a (rather clumsily) implementation of the Sardinas-Patterson algorithm
+/// The goal is to check if a word list (`c`) is uniquely decodable.
+///
+/// I followed
+/// https://github.com/danhales/blog-sardinas-patterson/blob/master/index.ipynb
+/// very closely.
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+pub fn check_decodability(c: &[String]) -> bool {
+    // Right off the bat, convert inputted Slice to a HashSet
+    // Since we always want this list to be unique, and we're
+    // going to eventually calculate a disjoint boolean!
+    let c = vec_to_hash(c);
+    sardinas_patterson_theorem(c)
+}
+
+fn vec_to_hash(v: &[String]) -> HashSet<String> {
+    let mut my_hash = HashSet::new();
+    for e in v {
+        my_hash.insert(e.to_string());
+    }
+    my_hash
+}
+
+// Generate c for any number n
+fn generate_cn(c: &HashSet<String>, n: usize) -> HashSet<String> {
+    if n == 0 {
+        c.to_owned()
+    } else {
+        let mut cn = HashSet::new();
+
+        // generate c_(n-1)
+        let cn_minus_1 = generate_cn(c, n - 1);
+        for w1 in c.iter() {
+            for w2 in cn_minus_1.iter() {
+                if w1.len() > w2.len() && w1.starts_with(w2) {
+                    // w2 is a prefix word of w1
+                    // so, we're going to add the dangling suffix to a new HashSet
+                    // called cn
+                    cn.insert(w1[w2.len()..].to_string());
+                }
+            }
+        }
+        // Now the other way? Could we clean this up?
+        for w1 in cn_minus_1.iter() {
+            for w2 in c.iter() {
+                if w1.len() > w2.len() && w1.starts_with(w2) {
+                    // w2 is a prefix word of w1
+                    // so, we're going to add the dangling suffix to a new HashSet
+                    // called cn
+                    cn.insert(w1[w2.len()..].to_string());
+                }
+            }
+        }
+        cn
+    }
+}
+
+fn generate_c_infinity_with_a_halt_break(c: HashSet<String>) -> HashSet<String> {
+    let mut cs = HashSet::new();
+    let mut c_infinity = HashSet::new();
+    let mut n = 1;
+    let mut cn = generate_cn(&c, n);
+
+    while !cn.is_empty() {
+        if cn.is_subset(&cs) {
+            // Cycle detected. Halting algorithm.
+            break;
+        } else {
+            cs = cs.union(&cn).map(|e| e.to_string()).collect();
+            c_infinity = c_infinity.union(&cn).map(|e| e.to_string()).collect();
+            n += 1;
+            cn = generate_cn(&c, n);
+        }
+    }
+    c_infinity
+}
+
+/// Returns true if c is uniquely decodable
+fn sardinas_patterson_theorem(c: HashSet<String>) -> bool {
+    let c_infinity = generate_c_infinity_with_a_halt_break(c.clone());
+    c.is_disjoint(&c_infinity)
+}
+
+/// If the list is not uniquely decodable, finds a string that can be made
+/// by concatenating words from the list in two different ways, returning
+/// both sequences of words. Returns `None` if the list is uniquely decodable.
+///
+/// This is a breadth-first search over the same "dangling suffixes" the
+/// Sardinas-Patterson algorithm uses, so the example it finds is one with
+/// the fewest words.
+/// ```
+/// use tidy_core::uniquely_decodable::find_ambiguous_concatenation;
+/// let list: Vec<String> = ["news", "newspaper", "paper", "elephant"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// assert_eq!(
+///     find_ambiguous_concatenation(&list),
+///     Some((
+///         vec!["newspaper".to_string()],
+///         vec!["news".to_string(), "paper".to_string()]
+///     ))
+/// );
+/// ```
+pub fn find_ambiguous_concatenation(c: &[String]) -> Option<(Vec<String>, Vec<String>)> {
+    find_ambiguous_concatenations(c, 1).into_iter().next()
+}
+
+/// Like `find_ambiguous_concatenation`, but finds up to `limit` examples,
+/// fewest words first. Each dangling suffix is only followed up once, so
+/// examples aren't just other examples with more words tacked on.
+/// ```
+/// use tidy_core::uniquely_decodable::find_ambiguous_concatenations;
+/// let list: Vec<String> = ["news", "newspaper", "paper", "in", "put", "input"]
+///     .iter()
+///     .map(|w| w.to_string())
+///     .collect();
+/// let examples = find_ambiguous_concatenations(&list, 10);
+/// assert_eq!(examples.len(), 2);
+/// assert!(examples.contains(&(
+///     vec!["input".to_string()],
+///     vec!["in".to_string(), "put".to_string()]
+/// )));
+/// ```
+pub fn find_ambiguous_concatenations(
+    c: &[String],
+    limit: usize,
+) -> Vec<(Vec<String>, Vec<String>)> {
+    let mut examples = vec![];
+    if limit == 0 {
+        return examples;
+    }
+    // Sort words so that the example found is the same every time, and so
+    // that words beginning with a given string can be found with a binary search
+    let mut words: Vec<&str> = c.iter().map(|w| w.as_str()).collect();
+    words.sort_unstable();
+    words.dedup();
+    let word_set: HashSet<&str> = words.iter().copied().collect();
+
+    // Each item of the queue is a pair of word sequences, where the first
+    // (longer) sequence spells out the second sequence plus a dangling suffix.
+    let mut queue: VecDeque<(Vec<&str>, Vec<&str>, &str)> = VecDeque::new();
+    let mut seen_suffixes: HashSet<&str> = HashSet::new();
+    for w1 in &words {
+        for (i, _) in w1.char_indices().skip(1) {
+            if word_set.contains(&w1[..i]) && seen_suffixes.insert(&w1[i..]) {
+                queue.push_back((vec![w1], vec![&w1[..i]], &w1[i..]));
+            }
+        }
+    }
+    while let Some((ahead, behind, dangling_suffix)) = queue.pop_front() {
+        // Words that cover part (or all) of the dangling suffix, so the same
+        // sequence stays ahead
+        for (i, _) in dangling_suffix
+            .char_indices()
+            .skip(1)
+            .chain([(dangling_suffix.len(), ' ')])
+        {
+            let word = &dangling_suffix[..i];
+            if !word_set.contains(word) {
+                continue;
+            }
+            let mut new_behind = behind.clone();
+            new_behind.push(word);
+            if i == dangling_suffix.len() {
+                examples.push((to_strings(&ahead), to_strings(&new_behind)));
+                if examples.len() == limit {
+                    return examples;
+                }
+            } else if seen_suffixes.insert(&dangling_suffix[i..]) {
+                queue.push_back((ahead.clone(), new_behind, &dangling_suffix[i..]));
+            }
+        }
+        // Words that overshoot the dangling suffix, so the other sequence is
+        // now ahead
+        let start = words.partition_point(|w| *w <= dangling_suffix);
+        for word in words[start..]
+            .iter()
+            .take_while(|w| w.starts_with(dangling_suffix))
+        {
+            if seen_suffixes.insert(&word[dangling_suffix.len()..]) {
+                let mut new_behind = behind.clone();
+                new_behind.push(word);
+                queue.push_back((new_behind, ahead.clone(), &word[dangling_suffix.len()..]));
+            }
+        }
+    }
+    examples
+}
+
+fn to_strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
@@ -0,0 +1,182 @@
+//! Words that carry what's known about them, like how common they are and
+//! which list they came from, through tidying. Tidy's steps work on the text
+//! of each word, and keep track of the line each word was made from, even
+//! through steps that change the word, like truncating it. `tidy_words` uses
+//! that to give each tidied word what was known about the word it was made
+//! from. `tidy_list` is for callers that only need the text.
+use crate::error::TidyError;
+use crate::{listed_word, split_line, tidy_lines_with_origins, TidyReport, TidyRequest};
+use std::collections::HashMap;
+
+/// A word of a list, along with what's known about it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Word {
+    /// The word itself (along with any metadata the request keeps)
+    pub text: String,
+    /// How common the word is, like a count of it in a corpus
+    pub frequency: Option<u64>,
+    /// Which of the inputted lists the word came from
+    pub source: Option<usize>,
+    /// The line the word was made from, before it was tidied
+    pub original: String,
+}
+
+impl Word {
+    /// Makes a word from a line of a list, with nothing else known about it
+    /// yet
+    pub fn new(line: &str) -> Word {
+        Word {
+            text: line.to_string(),
+            original: line.to_string(),
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&str> for Word {
+    fn from(line: &str) -> Word {
+        Word::new(line)
+    }
+}
+
+impl From<String> for Word {
+    fn from(line: String) -> Word {
+        Word::new(&line)
+    }
+}
+
+/// Makes words of the lines of several lists, each with the index of the
+/// list it came from as its source.
+/// ```
+/// use tidy_core::words::words_from_lists;
+/// let lists = vec![vec!["apple".to_string()], vec!["pear".to_string()]];
+/// let words = words_from_lists(&lists);
+/// assert_eq!(words[1].text, "pear");
+/// assert_eq!(words[1].source, Some(1));
+/// ```
+pub fn words_from_lists(lists: &[Vec<String>]) -> Vec<Word> {
+    lists
+        .iter()
+        .enumerate()
+        .flat_map(|(source, lines)| {
+            lines.iter().map(move |line| Word {
+                source: Some(source),
+                ..Word::new(line)
+            })
+        })
+        .collect()
+}
+
+/// Tidies the text of each word the way `req` asks (ignoring `req.list`),
+/// like `tidy_list_explained`. Each word of the new list keeps the
+/// frequency, source, and original line of the word it was made from (of
+/// words with the same text, the first of them). If that word has no
+/// frequency, but the tidied word has a frequency count in its metadata,
+/// that count is its frequency. Words that weren't made from any of the
+/// given words, like words the request always includes, are new words, as
+/// `Word::new` makes them.
+/// ```
+/// use tidy_core::list_manipulations::SortOrder;
+/// use tidy_core::words::{tidy_words, Word};
+/// use tidy_core::TidyRequest;
+/// let words = vec![
+///     Word { frequency: Some(300), ..Word::new("Apple") },
+///     Word { frequency: Some(5), ..Word::new("apple") },
+///     Word::new("pear"),
+///     Word { source: Some(1), ..Word::new("Plum,20") },
+/// ];
+/// let req = TidyRequest {
+///     to_lowercase: true,
+///     ignore_after_delimiter: Some(','),
+///     truncate_to_unique_length: Some(3),
+///     sort_by: SortOrder::Alphabetical,
+///     ..Default::default()
+/// };
+/// let (tidied, _report) = tidy_words(&words, &req).unwrap();
+/// assert_eq!(tidied[0].text, "app");
+/// assert_eq!(tidied[0].original, "Apple");
+/// assert_eq!(tidied[0].frequency, Some(300));
+/// assert_eq!(tidied[1], Word { text: "pea".to_string(), ..Word::new("pear") });
+/// assert_eq!(tidied[2].text, "plu,20");
+/// assert_eq!(tidied[2].source, Some(1));
+/// assert_eq!(tidied[2].frequency, Some(20));
+/// ```
+pub fn tidy_words(words: &[Word], req: &TidyRequest) -> Result<(Vec<Word>, TidyReport), TidyError> {
+    let lines: Vec<&str> = words.iter().map(|word| word.text.as_str()).collect();
+    let mut word_of_lines: HashMap<&str, &Word> = HashMap::new();
+    for word in words {
+        word_of_lines.entry(word.text.as_str()).or_insert(word);
+    }
+    let (list, report) = tidy_lines_with_origins(&lines, req)?;
+    let tidied_words = list
+        .into_iter()
+        .map(|(line, origin)| {
+            let word = match origin.and_then(|origin| word_of_lines.get(origin)) {
+                Some(word) => Word {
+                    text: line,
+                    ..(*word).clone()
+                },
+                None => Word::new(&line),
+            };
+            let frequency = match word.frequency {
+                Some(frequency) => Some(frequency),
+                None => frequency_in_metadata(&word.text, req)?,
+            };
+            Ok(Word { frequency, ..word })
+        })
+        .collect::<Result<_, TidyError>>()?;
+    Ok((tidied_words, report))
+}
+
+/// The frequency count at the start of a line's metadata, if the request
+/// asks to ignore metadata and the line has a count
+fn frequency_in_metadata(line: &str, req: &TidyRequest) -> Result<Option<u64>, TidyError> {
+    let (_word, metadata) = split_line(line, req)?;
+    Ok(metadata.and_then(|metadata| {
+        let count = metadata.text.split(metadata.delimiter).next()?;
+        count.trim().parse().ok()
+    }))
+}
+
+/// Adds up how many times each word of a tidied list appeared, from counts of
+/// the words it was made from, like the counts `tokenize_prose` makes. Every
+/// counted word that tidies to the same word adds to that word's total, so
+/// with `to_lowercase`, "The" and "the" are counted together. Words none of
+/// the counted words were tidied into, like words the request always
+/// includes, are counted 0.
+/// ```
+/// use tidy_core::words::count_tidied_words;
+/// use tidy_core::TidyRequest;
+/// let counts = vec![
+///     ("the".to_string(), 3),
+///     ("The".to_string(), 2),
+///     ("cat".to_string(), 1),
+/// ];
+/// let req = TidyRequest {
+///     to_lowercase: true,
+///     ..Default::default()
+/// };
+/// let tidied_list = vec!["cat".to_string(), "the".to_string()];
+/// assert_eq!(count_tidied_words(&tidied_list, &counts, &req), Ok(vec![1, 5]));
+/// ```
+pub fn count_tidied_words(
+    tidied_list: &[String],
+    counts: &[(String, usize)],
+    req: &TidyRequest,
+) -> Result<Vec<usize>, TidyError> {
+    let mut totals: HashMap<String, usize> = HashMap::new();
+    for (word, count) in counts {
+        if let Some(tidied) = listed_word(word, req)? {
+            *totals.entry(tidied).or_insert(0) += count;
+        }
+    }
+    tidied_list
+        .iter()
+        .map(|line| {
+            Ok(totals
+                .get(split_line(line, req)?.0.trim())
+                .copied()
+                .unwrap_or(0))
+        })
+        .collect()
+}